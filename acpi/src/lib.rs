@@ -75,7 +75,7 @@ pub mod platform;
 
 pub use crate::{fadt::PowerProfile, hpet::HpetInfo, madt::MadtError, mcfg::PciConfigRegions};
 pub use rsdp::{
-    handler::{AcpiHandler, PhysicalMapping},
+    handler::{AcpiHandler, MapError, PhysicalMapping},
     RsdpError,
 };
 
@@ -124,6 +124,8 @@ pub enum AcpiError {
     InvalidGenericAddress,
 
     AllocError,
+    /// The handler couldn't map a region of physical memory needed to access a table.
+    MapError(MapError),
 }
 
 /// Type capable of enumerating the existing ACPI tables on the system.
@@ -148,7 +150,8 @@ where
     ///
     /// ### Safety: Caller must ensure the provided address is valid to read as an RSDP.
     pub unsafe fn from_rsdp(handler: H, address: usize) -> AcpiResult<Self> {
-        let rsdp_mapping = unsafe { handler.map_physical_region::<Rsdp>(address, mem::size_of::<Rsdp>()) };
+        let rsdp_mapping = unsafe { handler.try_map_physical_region::<Rsdp>(address, mem::size_of::<Rsdp>()) }
+            .map_err(AcpiError::MapError)?;
         rsdp_mapping.validate().map_err(AcpiError::Rsdp)?;
 
         // Safety: `RSDP` has been validated.
@@ -179,11 +182,12 @@ where
 
             // Safety: Addresses from a validated `RSDP` are also guaranteed to be valid.
             let rsdt_mapping: PhysicalMapping<H, SdtHeader> = unsafe {
-                handler.map_physical_region::<SdtHeader>(
+                handler.try_map_physical_region::<SdtHeader>(
                     rsdp_mapping.rsdt_address() as usize,
                     core::mem::size_of::<SdtHeader>(),
                 )
-            };
+            }
+            .map_err(AcpiError::MapError)?;
             rsdt_mapping.validate(Signature::RSDT)?;
 
             Ok(Self { mapping: rsdt_mapping, revision, handler })
@@ -195,11 +199,12 @@ where
 
             // Safety: Addresses from a validated `RSDP` are also guaranteed to be valid.
             let xsdt_mapping: PhysicalMapping<H, SdtHeader> = unsafe {
-                handler.map_physical_region::<SdtHeader>(
+                handler.try_map_physical_region::<SdtHeader>(
                     rsdp_mapping.xsdt_address() as usize,
                     core::mem::size_of::<SdtHeader>(),
                 )
-            };
+            }
+            .map_err(AcpiError::MapError)?;
             xsdt_mapping.validate(Signature::XSDT)?;
 
             Ok(Self { mapping: xsdt_mapping, revision, handler })
@@ -219,7 +224,7 @@ where
         if self.revision == 0 {
             let num_tables = ((self.mapping.length as usize) - size_of::<SdtHeader>()) / size_of::<u32>();
             // Safety: Table pointer is known-good for these offsets and types.
-            let tables_base = unsafe { self.mapping.virtual_start().as_ptr().add(1).cast::<u32>() };
+            let tables_base = unsafe { self.mapping.as_ptr().add(1).cast::<u32>() };
 
             for offset in 0..num_tables {
                 // Safety: Table pointer is known-good for these offsets and types.
@@ -234,7 +239,7 @@ where
         } else {
             let num_tables = ((self.mapping.length as usize) - size_of::<SdtHeader>()) / size_of::<u64>();
             // Safety: Table pointer is known-good for these offsets and types.
-            let tables_base = unsafe { self.mapping.virtual_start().as_ptr().add(1).cast::<u64>() };
+            let tables_base = unsafe { self.mapping.as_ptr().add(1).cast::<u64>() };
 
             for offset in 0..num_tables {
                 // Safety: Table pointer is known-good for these offsets and types.
@@ -274,8 +279,7 @@ where
 
     /// Iterates through all of the SSDT tables.
     pub fn ssdts(&self) -> SsdtIterator<H> {
-        let header_ptrs_base_ptr =
-            unsafe { self.mapping.virtual_start().as_ptr().add(1).cast::<*const SdtHeader>() };
+        let header_ptrs_base_ptr = unsafe { self.mapping.as_ptr().add(1).cast::<*const SdtHeader>() };
         let header_ptr_count = ((self.mapping.length as usize) - mem::size_of::<SdtHeader>())
             / core::mem::size_of::<*const *const SdtHeader>();
 
@@ -334,34 +338,43 @@ unsafe fn read_table<H: AcpiHandler, T: AcpiTable>(
 ) -> AcpiResult<PhysicalMapping<H, T>> {
     // Attempt to peek at the SDT header to correctly enumerate the entire table.
     // Safety: `address` needs to be valid for the size of `SdtHeader`, or the ACPI tables are malformed (not a software issue).
-    let mapping = unsafe { handler.map_physical_region::<SdtHeader>(address, core::mem::size_of::<SdtHeader>()) };
+    let mapping =
+        unsafe { handler.try_map_physical_region::<SdtHeader>(address, core::mem::size_of::<SdtHeader>()) }
+            .map_err(AcpiError::MapError)?;
     mapping.validate(T::SIGNATURE)?;
 
     // If possible (if the existing mapping covers enough memory), resuse the existing physical mapping.
     // This allows allocators/memory managers that map in chunks larger than `size_of::<SdtHeader>()` to be used more efficiently.
     if mapping.mapped_length() >= (mapping.length as usize) {
-        // Safety: Pointer is known non-null.
-        let virtual_start =
-            unsafe { core::ptr::NonNull::new_unchecked(mapping.virtual_start().as_ptr() as *mut _) };
-
-        // Safety: Mapping is known-good and validated.
-        Ok(unsafe {
+        // Safety: Mapping is known-good and validated, and covers the whole table.
+        let table_mapping = unsafe {
             PhysicalMapping::new(
                 mapping.physical_start(),
-                virtual_start,
-                mapping.region_length(),
+                mapping.virtual_start(),
+                mapping.length as usize,
                 mapping.mapped_length(),
                 handler.clone(),
             )
-        })
-    } else {
-        let sdt_length = mapping.length;
-        // Drop the old mapping here, to ensure it's unmapped in software before requesting an overlapping mapping.
-        drop(mapping);
-
-        // Safety: Address and length are already known-good.
-        Ok(unsafe { handler.map_physical_region(address, sdt_length as usize) })
+        };
+
+        // The table's address comes from firmware, so check it can actually hold a `T` before reinterpreting it.
+        match table_mapping.try_coerce_type::<T>() {
+            Ok(table_mapping) => {
+                // The region is now unmapped when the new mapping is dropped.
+                mem::forget(mapping);
+                return Ok(table_mapping);
+            }
+            // The new mapping shares the old one's region, which is unmapped below.
+            Err(table_mapping) => mem::forget(table_mapping),
+        }
     }
+
+    let sdt_length = mapping.length;
+    // Drop the old mapping here, to ensure it's unmapped in software before requesting an overlapping mapping.
+    drop(mapping);
+
+    // Safety: Address and length are already known-good.
+    unsafe { handler.try_map_physical_region(address, sdt_length as usize) }.map_err(AcpiError::MapError)
 }
 
 /// Iterator that steps through all of the tables, and returns only the SSDTs as `AmlTable`s.
@@ -400,7 +413,7 @@ where
                 // Attempt to peek at the SDT header to correctly enumerate the entire table.
                 // Safety: `address` needs to be valid for the size of `SdtHeader`, or the ACPI tables are malformed (not a software issue).
                 let sdt_header = unsafe {
-                    self.handler.map_physical_region::<SdtHeader>(
+                    self.handler.try_map_physical_region::<SdtHeader>(
                         self.current_sdt_ptr.read() as usize,
                         core::mem::size_of::<SdtHeader>(),
                     )
@@ -408,6 +421,12 @@ where
 
                 self.remaining -= 1;
 
+                // Tables that can't be mapped are skipped, in the same way as invalid ones
+                let sdt_header = match sdt_header {
+                    Ok(sdt_header) => sdt_header,
+                    Err(_) => continue,
+                };
+
                 if sdt_header.validate(Ssdt::SIGNATURE).is_err() {
                     continue;
                 } else {
@@ -417,3 +436,125 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    /// A handler that fails to map any region of physical memory.
+    #[derive(Clone)]
+    struct FailingHandler;
+
+    impl AcpiHandler for FailingHandler {
+        unsafe fn map_physical_region<T>(
+            &self,
+            _physical_address: usize,
+            _size: usize,
+        ) -> PhysicalMapping<Self, T> {
+            panic!("The library should only use try_map_physical_region");
+        }
+
+        unsafe fn try_map_physical_region<T>(
+            &self,
+            _physical_address: usize,
+            _size: usize,
+        ) -> Result<PhysicalMapping<Self, T>, MapError> {
+            Err(MapError::OutOfVirtualMemory)
+        }
+
+        fn unmap_physical_region<T>(_region: &PhysicalMapping<Self, T>) {}
+    }
+
+    #[test]
+    fn test_map_errors_are_propagated() {
+        assert!(matches!(
+            unsafe { AcpiTables::from_rsdp(FailingHandler, 0xe0000) },
+            Err(AcpiError::MapError(MapError::OutOfVirtualMemory))
+        ));
+        assert!(matches!(
+            unsafe { AcpiTables::search_for_rsdp_bios(FailingHandler) },
+            Err(AcpiError::Rsdp(RsdpError::MapError(MapError::OutOfVirtualMemory)))
+        ));
+        assert!(matches!(
+            unsafe { read_table::<FailingHandler, fadt::Fadt>(FailingHandler, 0x1000) },
+            Err(AcpiError::MapError(MapError::OutOfVirtualMemory))
+        ));
+    }
+
+    static IDENTITY_MAPPINGS: AtomicUsize = AtomicUsize::new(0);
+    static IDENTITY_UNMAPPINGS: AtomicUsize = AtomicUsize::new(0);
+
+    /// A handler that identity-maps physical memory, and reports each mapping as covering at least a page.
+    #[derive(Clone)]
+    struct IdentityHandler;
+
+    impl AcpiHandler for IdentityHandler {
+        unsafe fn map_physical_region<T>(&self, physical_address: usize, size: usize) -> PhysicalMapping<Self, T> {
+            IDENTITY_MAPPINGS.fetch_add(1, Ordering::Relaxed);
+            unsafe {
+                PhysicalMapping::new(
+                    physical_address,
+                    core::ptr::NonNull::new(physical_address as *mut T).unwrap(),
+                    size,
+                    size.max(0x1000),
+                    self.clone(),
+                )
+            }
+        }
+
+        fn unmap_physical_region<T>(_region: &PhysicalMapping<Self, T>) {
+            IDENTITY_UNMAPPINGS.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    #[test]
+    fn test_read_table_reuses_header_mapping() {
+        #[repr(C, packed)]
+        struct TestTable {
+            header: SdtHeader,
+            value: u32,
+        }
+
+        unsafe impl AcpiTable for TestTable {
+            const SIGNATURE: Signature = Signature::SSDT;
+
+            fn header(&self) -> &SdtHeader {
+                &self.header
+            }
+        }
+
+        let make_table = |length: u8| {
+            let mut table = [0u8; mem::size_of::<TestTable>()];
+            table[0..4].copy_from_slice(b"SSDT");
+            table[4] = length;
+            table[9] = 0u8.wrapping_sub(table.iter().fold(0u8, |sum, &byte| sum.wrapping_add(byte)));
+            table
+        };
+
+        // The header mapping covers the whole table, so it is reused
+        let table = make_table(mem::size_of::<TestTable>() as u8);
+        IDENTITY_MAPPINGS.store(0, Ordering::Relaxed);
+        IDENTITY_UNMAPPINGS.store(0, Ordering::Relaxed);
+        let mapping =
+            unsafe { read_table::<IdentityHandler, TestTable>(IdentityHandler, table.as_ptr() as usize) }.unwrap();
+        assert_eq!(mapping.region_length(), mem::size_of::<TestTable>());
+        assert_eq!({ mapping.value }, 0);
+        assert_eq!(IDENTITY_MAPPINGS.load(Ordering::Relaxed), 1);
+        assert_eq!(IDENTITY_UNMAPPINGS.load(Ordering::Relaxed), 0);
+        drop(mapping);
+        assert_eq!(IDENTITY_UNMAPPINGS.load(Ordering::Relaxed), 1);
+
+        // The table is too short to hold a `TestTable`, so the header mapping can't be reinterpreted as one
+        let table = make_table(mem::size_of::<SdtHeader>() as u8);
+        IDENTITY_MAPPINGS.store(0, Ordering::Relaxed);
+        IDENTITY_UNMAPPINGS.store(0, Ordering::Relaxed);
+        let mapping =
+            unsafe { read_table::<IdentityHandler, TestTable>(IdentityHandler, table.as_ptr() as usize) }.unwrap();
+        assert_eq!(mapping.region_length(), mem::size_of::<SdtHeader>());
+        assert_eq!(IDENTITY_MAPPINGS.load(Ordering::Relaxed), 2);
+        assert_eq!(IDENTITY_UNMAPPINGS.load(Ordering::Relaxed), 1);
+        drop(mapping);
+        assert_eq!(IDENTITY_UNMAPPINGS.load(Ordering::Relaxed), 2);
+    }
+}
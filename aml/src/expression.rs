@@ -1,20 +1,38 @@
 use crate::{
-    name_object::{name_string, super_name, target},
-    opcode::{self, opcode},
-    parser::{choice, comment_scope, n_of, take, take_to_end_of_pkglength, try_with_context, Parser, Propagate},
+    name_object::{name_string, simple_name, super_name, target, Target},
+    opcode::{self, ext_opcode, opcode},
+    parser::{
+        choice,
+        comment_scope,
+        n_of,
+        take,
+        take_to_end_of_pkglength,
+        take_u16,
+        try_with_context,
+        within_pkglength,
+        Parser,
+        Propagate,
+    },
     pkg_length::pkg_length,
     term_object::{data_ref_object, term_arg},
     value::{AmlType, AmlValue, Args},
+    AmlContext,
     AmlError,
+    AmlName,
     DebugVerbosity,
 };
 use alloc::{
+    boxed::Box,
     string::{String, ToString},
     sync::Arc,
-    vec,
     vec::Vec,
 };
-use core::{cmp::Ordering, convert::TryInto, mem, ops::Deref};
+use core::{
+    cmp::Ordering,
+    convert::{TryFrom, TryInto},
+    mem,
+    ops::Deref,
+};
 
 pub fn expression_opcode<'a, 'c>() -> impl Parser<'a, 'c, AmlValue>
 where
@@ -35,31 +53,86 @@ where
         DebugVerbosity::AllScopes,
         "ExpressionOpcode",
         choice!(
+            def_acquire(),
             def_add(),
             def_and(),
             def_buffer(),
             def_concat(),
             def_concat_res(),
+            def_cond_ref_of(),
+            def_copy_object(),
             def_increment(),
+            def_index(),
             def_decrement(),
+            def_deref_of(),
+            def_divide(),
+            def_find_set_left_bit(),
+            def_find_set_right_bit(),
+            def_from_bcd(),
+            def_l_and(),
             def_l_equal(),
             def_l_greater(),
             def_l_greater_equal(),
             def_l_less(),
             def_l_less_equal(),
             def_l_not_equal(),
+            def_l_not(),
             def_l_or(),
+            def_match(),
             def_mid(),
+            def_mod(),
+            def_multiply(),
+            def_nand(),
+            def_nor(),
+            def_not(),
+            def_object_type(),
+            def_or(),
             def_package(),
+            def_var_package(),
+            def_ref_of(),
             def_shift_left(),
             def_shift_right(),
+            def_size_of(),
             def_store(),
+            def_subtract(),
+            def_timer(),
+            def_to_bcd(),
+            def_to_buffer(),
+            def_to_decimal_string(),
+            def_to_hex_string(),
             def_to_integer(),
+            def_to_string(),
+            def_wait(),
+            def_xor(),
             method_invocation() // XXX: this must always appear last. See how we have to parse it to see why.
         ),
     )
 }
 
+pub fn def_acquire<'a, 'c>() -> impl Parser<'a, 'c, AmlValue>
+where
+    'c: 'a,
+{
+    /*
+     * DefAcquire := ExtOpPrefix 0x23 MutexObject Timeout
+     * MutexObject := SuperName
+     * Timeout := WordData
+     *
+     * This evaluates to `True` if the wait for the mutex timed out, and `False` if it was acquired.
+     */
+    ext_opcode(opcode::EXT_DEF_ACQUIRE_OP)
+        .then(comment_scope(
+            DebugVerbosity::AllScopes,
+            "DefAcquire",
+            super_name().then(take_u16()).map_with_context(|(mutex, timeout), context| {
+                let mutex = try_with_context!(context, context.resolve_mutex(mutex));
+                let acquired = context.handler.acquire_mutex(&mutex, timeout);
+                (Ok(AmlValue::Boolean(!acquired)), context)
+            }),
+        ))
+        .map(|((), result)| Ok(result))
+}
+
 pub fn def_add<'a, 'c>() -> impl Parser<'a, 'c, AmlValue>
 where
     'c: 'a,
@@ -76,7 +149,7 @@ where
                 |((left_arg, right_arg), target), context| {
                     let left = try_with_context!(context, left_arg.as_integer(context));
                     let right = try_with_context!(context, right_arg.as_integer(context));
-                    let result = AmlValue::Integer(left.wrapping_add(right));
+                    let result = AmlValue::Integer(context.truncate_integer(left.wrapping_add(right)));
 
                     try_with_context!(context, context.store(target, result.clone()));
                     (Ok(result), context)
@@ -124,7 +197,8 @@ where
      * we've encountered them in QEMU-generated tables, so we return an empty buffer in these
      * cases.
      *
-     * Uninitialized elements are initialized to zero.
+     * Uninitialized elements are initialized to zero. If the `ByteList` is longer than `BufferSize`, the buffer is
+     * made large enough to hold the whole list (this matches what ACPICA does).
      */
     opcode(opcode::DEF_BUFFER_OP)
         .then(comment_scope(
@@ -132,13 +206,14 @@ where
             "DefBuffer",
             pkg_length().then(term_arg()).feed(|(pkg_length, buffer_size)| {
                 take_to_end_of_pkglength(pkg_length).map_with_context(move |bytes, context| {
-                    let buffer_size = try_with_context!(context, buffer_size.as_integer(context)) as usize;
+                    let buffer_size = try_with_context!(context, buffer_size.as_integer(context));
+                    let buffer_size = usize::max(usize::try_from(buffer_size).unwrap_or(usize::MAX), bytes.len());
 
-                    if buffer_size < bytes.len() {
-                        return (Err(Propagate::Err(AmlError::MalformedBuffer)), context);
+                    let mut buffer = Vec::new();
+                    if buffer.try_reserve_exact(buffer_size).is_err() {
+                        return (Err(Propagate::Err(AmlError::ObjectTooLarge)), context);
                     }
-
-                    let mut buffer = vec![0; buffer_size];
+                    buffer.resize(buffer_size, 0);
                     (&mut buffer[0..bytes.len()]).copy_from_slice(bytes);
                     (Ok(buffer), context)
                 })
@@ -259,6 +334,145 @@ where
         .map(|((), result)| Ok(result))
 }
 
+fn def_cond_ref_of<'a, 'c>() -> impl Parser<'a, 'c, AmlValue>
+where
+    'c: 'a,
+{
+    /*
+     * DefCondRefOf := ExtOpPrefix 0x12 SuperName Target
+     *
+     * If the object referred to by `SuperName` exists, a reference to it is stored into `Target`, and this
+     * evaluates to `true`. Otherwise, `Target` is left alone and this evaluates to `false`. Objects that have only
+     * been declared by `DefExternal` don't exist yet.
+     */
+    ext_opcode(opcode::EXT_DEF_COND_REF_OF_OP)
+        .then(comment_scope(
+            DebugVerbosity::AllScopes,
+            "DefCondRefOf",
+            super_name().then(target()).map_with_context(|(source, target), context| {
+                let reference = match context.read_target(&source) {
+                    Ok(AmlValue::External { .. }) => None,
+                    Ok(_) => Some(try_with_context!(context, context.reference_to(&source))),
+                    Err(AmlError::ValueDoesNotExist(_))
+                    | Err(AmlError::LevelDoesNotExist(_))
+                    | Err(AmlError::InvalidArgAccess(_))
+                    | Err(AmlError::InvalidLocalAccess(_)) => None,
+                    Err(err) => return (Err(Propagate::Err(err)), context),
+                };
+
+                match reference {
+                    Some(reference) => {
+                        try_with_context!(context, context.store(target, reference));
+                        (Ok(AmlValue::Boolean(true)), context)
+                    }
+                    None => (Ok(AmlValue::Boolean(false)), context),
+                }
+            }),
+        ))
+        .map(|((), result)| Ok(result))
+}
+
+fn def_find_set_left_bit<'a, 'c>() -> impl Parser<'a, 'c, AmlValue>
+where
+    'c: 'a,
+{
+    /*
+     * DefFindSetLeftBit := 0x81 Operand Target
+     * Operand := TermArg => Integer
+     */
+    opcode(opcode::DEF_FIND_SET_LEFT_BIT_OP)
+        .then(comment_scope(
+            DebugVerbosity::AllScopes,
+            "DefFindSetLeftBit",
+            term_arg().then(target()).map_with_context(|(operand, target), context| {
+                let operand = try_with_context!(context, operand.as_integer(context));
+
+                /*
+                 * The result is the one-based index of the most significant set bit, or zero if no bits are set.
+                 * Bits above the integer width are ignored.
+                 */
+                let operand = context.truncate_integer(operand);
+                let result = AmlValue::Integer((u64::BITS - operand.leading_zeros()) as u64);
+
+                try_with_context!(context, context.store(target, result.clone()));
+                (Ok(result), context)
+            }),
+        ))
+        .map(|((), result)| Ok(result))
+}
+
+fn def_find_set_right_bit<'a, 'c>() -> impl Parser<'a, 'c, AmlValue>
+where
+    'c: 'a,
+{
+    /*
+     * DefFindSetRightBit := 0x82 Operand Target
+     * Operand := TermArg => Integer
+     */
+    opcode(opcode::DEF_FIND_SET_RIGHT_BIT_OP)
+        .then(comment_scope(
+            DebugVerbosity::AllScopes,
+            "DefFindSetRightBit",
+            term_arg().then(target()).map_with_context(|(operand, target), context| {
+                let operand = try_with_context!(context, operand.as_integer(context));
+
+                /*
+                 * The result is the one-based index of the least significant set bit, or zero if no bits are set.
+                 * Bits above the integer width are ignored.
+                 */
+                let operand = context.truncate_integer(operand);
+                let result = AmlValue::Integer(if operand == 0 { 0 } else { operand.trailing_zeros() as u64 + 1 });
+
+                try_with_context!(context, context.store(target, result.clone()));
+                (Ok(result), context)
+            }),
+        ))
+        .map(|((), result)| Ok(result))
+}
+
+fn def_from_bcd<'a, 'c>() -> impl Parser<'a, 'c, AmlValue>
+where
+    'c: 'a,
+{
+    /*
+     * DefFromBCD := ExtOpPrefix 0x28 BCDValue Target
+     * BCDValue := TermArg => Integer
+     */
+    ext_opcode(opcode::EXT_DEF_FROM_BCD_OP)
+        .then(comment_scope(
+            DebugVerbosity::AllScopes,
+            "DefFromBCD",
+            term_arg().then(target()).map_with_context(|(value, target), context| {
+                let bcd = try_with_context!(context, value.as_integer(context));
+
+                let mut result = 0u64;
+                for i in (0..16).rev() {
+                    let digit = (bcd >> (i * 4)) & 0xf;
+                    if digit > 9 {
+                        return (Err(Propagate::Err(AmlError::InvalidBcdConversion(bcd))), context);
+                    }
+                    result = result * 10 + digit;
+                }
+                let result = AmlValue::Integer(result);
+
+                try_with_context!(context, context.store(target, result.clone()));
+                (Ok(result), context)
+            }),
+        ))
+        .map(|((), result)| Ok(result))
+}
+
+fn def_timer<'a, 'c>() -> impl Parser<'a, 'c, AmlValue>
+where
+    'c: 'a,
+{
+    /*
+     * DefTimer := ExtOpPrefix 0x33
+     */
+    ext_opcode(opcode::EXT_DEF_TIMER_OP)
+        .map_with_context(|(), context| (Ok(AmlValue::Integer(context.handler.monotonic_timer())), context))
+}
+
 fn def_increment<'a, 'c>() -> impl Parser<'a, 'c, AmlValue>
 where
     'c: 'a,
@@ -273,7 +487,7 @@ where
             super_name().map_with_context(|addend, context| {
                 let value = try_with_context!(context, context.read_target(&addend));
                 let value = try_with_context!(context, value.as_integer(context));
-                let new_value = AmlValue::Integer(value + 1);
+                let new_value = AmlValue::Integer(context.truncate_integer(value.wrapping_add(1)));
                 try_with_context!(context, context.store(addend, new_value.clone()));
                 (Ok(new_value), context)
             }),
@@ -281,6 +495,23 @@ where
         .map(|((), result)| Ok(result))
 }
 
+fn def_copy_object<'a, 'c>() -> impl Parser<'a, 'c, AmlValue>
+where
+    'c: 'a,
+{
+    /*
+     * DefCopyObject := 0x9d TermArg SimpleName
+     *
+     * Unlike `DefStore`, no implicit conversion is applied - the object in the destination is replaced with a
+     * copy of the source.
+     */
+    opcode(opcode::DEF_COPY_OBJECT_OP)
+        .then(comment_scope(DebugVerbosity::Scopes, "DefCopyObject", term_arg().then(simple_name())))
+        .map_with_context(|((), (value, target)), context| {
+            (Ok(try_with_context!(context, context.copy_object(target, value.deep_clone()))), context)
+        })
+}
+
 fn def_decrement<'a, 'c>() -> impl Parser<'a, 'c, AmlValue>
 where
     'c: 'a,
@@ -295,7 +526,7 @@ where
             super_name().map_with_context(|minuend, context| {
                 let value = try_with_context!(context, context.read_target(&minuend));
                 let value = try_with_context!(context, value.as_integer(context));
-                let new_value = AmlValue::Integer(value - 1);
+                let new_value = AmlValue::Integer(context.truncate_integer(value.wrapping_sub(1)));
                 try_with_context!(context, context.store(minuend, new_value.clone()));
                 (Ok(new_value), context)
             }),
@@ -303,6 +534,175 @@ where
         .map(|((), result)| Ok(result))
 }
 
+fn def_deref_of<'a, 'c>() -> impl Parser<'a, 'c, AmlValue>
+where
+    'c: 'a,
+{
+    /*
+     * DefDerefOf := 0x83 ObjReference
+     * ObjReference := TermArg => ObjectReference | String
+     */
+    opcode(opcode::DEF_DEREF_OF_OP)
+        .then(comment_scope(
+            DebugVerbosity::AllScopes,
+            "DefDerefOf",
+            term_arg().map_with_context(|reference, context| {
+                let result = match reference {
+                    AmlValue::Reference(object) => *object,
+                    AmlValue::NamedReference(object) => {
+                        try_with_context!(context, context.namespace.get(object)).clone()
+                    }
+                    AmlValue::PackageElement { package, index } => {
+                        try_with_context!(context, context.package_element(package, index)).clone()
+                    }
+                    AmlValue::BufferField { .. } => {
+                        try_with_context!(context, reference.read_buffer_field(context))
+                    }
+                    AmlValue::String(ref name) => {
+                        let name = try_with_context!(context, AmlName::from_str(name));
+                        let (_, handle) =
+                            try_with_context!(context, context.namespace.search(&name, &context.current_scope));
+                        try_with_context!(context, context.namespace.get(handle)).clone()
+                    }
+                    _ => {
+                        return (
+                            Err(Propagate::Err(AmlError::TypeCannotBeDereferenced(reference.type_of()))),
+                            context,
+                        )
+                    }
+                };
+
+                (Ok(result), context)
+            }),
+        ))
+        .map(|((), result)| Ok(result))
+}
+
+fn def_divide<'a, 'c>() -> impl Parser<'a, 'c, AmlValue>
+where
+    'c: 'a,
+{
+    /*
+     * DefDivide := 0x78 Dividend Divisor Remainder Quotient
+     * Dividend := TermArg => Integer
+     * Divisor := TermArg => Integer
+     * Remainder := Target
+     * Quotient := Target
+     */
+    opcode(opcode::DEF_DIVIDE_OP)
+        .then(comment_scope(
+            DebugVerbosity::AllScopes,
+            "DefDivide",
+            term_arg().then(term_arg()).then(target()).then(target()).map_with_context(
+                |(((dividend, divisor), remainder_target), quotient_target), context| {
+                    let dividend = try_with_context!(context, dividend.as_integer(context));
+                    let divisor = try_with_context!(context, divisor.as_integer(context));
+                    if divisor == 0 {
+                        return (Err(Propagate::Err(AmlError::DivideByZero)), context);
+                    }
+                    let quotient = AmlValue::Integer(dividend / divisor);
+                    let remainder = AmlValue::Integer(dividend % divisor);
+
+                    try_with_context!(context, context.store(remainder_target, remainder));
+                    try_with_context!(context, context.store(quotient_target, quotient.clone()));
+                    (Ok(quotient), context)
+                },
+            ),
+        ))
+        .map(|((), result)| Ok(result))
+}
+
+fn def_index<'a, 'c>() -> impl Parser<'a, 'c, AmlValue>
+where
+    'c: 'a,
+{
+    /*
+     * DefIndex := 0x88 BuffPkgStrObj IndexValue Target
+     * BuffPkgStrObj := TermArg => Buffer, Package, or String
+     * IndexValue := TermArg => Integer
+     *
+     * Indexing into a `Buffer` produces a `BufferField` that covers the indexed byte, so reads and writes through
+     * it affect the buffer. Indexing into a named `Package` produces a `PackageElement` that refers to the element
+     * in place. Indexing into any other `Package`, or a `String`, produces a `Reference` to a copy of the element.
+     */
+    let named_package = move |input: &'a [u8], context: &'c mut AmlContext| {
+        let (new_input, context, name) = match name_string().parse(input, context) {
+            Ok(result) => result,
+            Err((_, context, _)) => return Err((input, context, Propagate::Err(AmlError::WrongParser))),
+        };
+        match context.namespace.search(&name, &context.current_scope) {
+            Ok((_, handle)) if matches!(context.namespace.get(handle), Ok(AmlValue::Package(_))) => {
+                Ok((new_input, context, handle))
+            }
+            _ => Err((input, context, Propagate::Err(AmlError::WrongParser))),
+        }
+    };
+
+    opcode(opcode::DEF_INDEX_OP)
+        .then(comment_scope(
+            DebugVerbosity::AllScopes,
+            "DefIndex",
+            choice!(
+                named_package.map_with_context(|handle, context| {
+                    let object = try_with_context!(context, context.namespace.get(handle)).clone();
+                    (Ok((object, Some(handle))), context)
+                }),
+                term_arg().map(|object| Ok((object, None)))
+            )
+            .then(term_arg())
+            .then(target())
+            .map_with_context(|(((object, package), index), target), context| {
+                let index = try_with_context!(context, index.as_integer(context));
+                let index: usize =
+                    try_with_context!(context, index.try_into().map_err(|_| AmlError::IndexOutOfBounds));
+
+                let result = match object {
+                    AmlValue::Buffer(buffer_data) => {
+                        if index >= buffer_data.lock().len() {
+                            return (Err(Propagate::Err(AmlError::IndexOutOfBounds)), context);
+                        }
+                        AmlValue::BufferField { buffer_data, offset: index as u64 * 8, length: 8 }
+                    }
+                    AmlValue::Package(ref elements) => match (elements.get(index), package) {
+                        (Some(_), Some(package)) => AmlValue::PackageElement { package, index },
+                        (Some(element), None) => AmlValue::Reference(Box::new(element.clone())),
+                        (None, _) => return (Err(Propagate::Err(AmlError::IndexOutOfBounds)), context),
+                    },
+                    AmlValue::String(ref string) => match string.as_bytes().get(index) {
+                        Some(&byte) => AmlValue::Reference(Box::new(AmlValue::Integer(byte as u64))),
+                        None => return (Err(Propagate::Err(AmlError::IndexOutOfBounds)), context),
+                    },
+                    _ => return (Err(Propagate::Err(AmlError::TypeCannotBeIndexed(object.type_of()))), context),
+                };
+
+                try_with_context!(context, context.store(target, result.clone()));
+                (Ok(result), context)
+            }),
+        ))
+        .map(|((), result)| Ok(result))
+}
+
+fn def_l_and<'a, 'c>() -> impl Parser<'a, 'c, AmlValue>
+where
+    'c: 'a,
+{
+    /*
+     * DefLAnd := 0x90 Operand Operand
+     * Operand := TermArg => Integer
+     */
+    opcode(opcode::DEF_L_AND_OP)
+        .then(comment_scope(
+            DebugVerbosity::AllScopes,
+            "DefLAnd",
+            term_arg().then(term_arg()).map_with_context(|(left_arg, right_arg), context| {
+                let left = try_with_context!(context, left_arg.as_integer(context)) != 0;
+                let right = try_with_context!(context, right_arg.as_integer(context)) != 0;
+                (Ok(AmlValue::Boolean(left && right)), context)
+            }),
+        ))
+        .map(|((), result)| Ok(result))
+}
+
 fn def_l_or<'a, 'c>() -> impl Parser<'a, 'c, AmlValue>
 where
     'c: 'a,
@@ -316,8 +716,8 @@ where
             DebugVerbosity::AllScopes,
             "DefLOr",
             term_arg().then(term_arg()).map_with_context(|(left_arg, right_arg), context| {
-                let left = try_with_context!(context, left_arg.as_bool());
-                let right = try_with_context!(context, right_arg.as_bool());
+                let left = try_with_context!(context, left_arg.as_integer(context)) != 0;
+                let right = try_with_context!(context, right_arg.as_integer(context)) != 0;
                 (Ok(AmlValue::Boolean(left || right)), context)
             }),
         ))
@@ -442,6 +842,114 @@ where
         .map(|(((), ()), result)| Ok(result))
 }
 
+fn def_l_not<'a, 'c>() -> impl Parser<'a, 'c, AmlValue>
+where
+    'c: 'a,
+{
+    /*
+     * DefLNot := 0x92 Operand
+     * Operand := TermArg => Integer
+     *
+     * This must be tried after `DefLNotEqual`, `DefLLessEqual`, and `DefLGreaterEqual`, which share its opcode as
+     * a prefix.
+     */
+    opcode(opcode::DEF_L_NOT_OP)
+        .then(comment_scope(
+            DebugVerbosity::AllScopes,
+            "DefLNot",
+            term_arg().map_with_context(|operand, context| {
+                let operand = try_with_context!(context, operand.as_integer(context)) != 0;
+                (Ok(AmlValue::Boolean(!operand)), context)
+            }),
+        ))
+        .map(|((), result)| Ok(result))
+}
+
+fn def_match<'a, 'c>() -> impl Parser<'a, 'c, AmlValue>
+where
+    'c: 'a,
+{
+    /*
+     * DefMatch := 0x89 SearchPkg MatchOpcode Operand MatchOpcode Operand StartIndex
+     * SearchPkg := TermArg => Package
+     * MatchOpcode := ByteData
+     * Operand := TermArg => ComputationalData
+     * StartIndex := TermArg => Integer
+     *
+     * This evaluates to the index of the first element, from `StartIndex` onwards, that satisfies both
+     * predicates, or `Ones` if there isn't one. Elements that can't be compared with an operand only match `MTR`.
+     */
+    opcode(opcode::DEF_MATCH_OP)
+        .then(comment_scope(
+            DebugVerbosity::AllScopes,
+            "DefMatch",
+            term_arg()
+                .then(take())
+                .then(term_arg())
+                .then(take())
+                .then(term_arg())
+                .then(term_arg())
+                .map_with_context(
+                    |(((((package, first_op), first_operand), second_op), second_operand), start_index),
+                     context| {
+                        let elements = match package {
+                            AmlValue::Package(elements) => elements,
+                            _ => {
+                                return (
+                                    Err(Propagate::Err(AmlError::IncompatibleValueConversion {
+                                        current: package.type_of(),
+                                        target: AmlType::Package,
+                                    })),
+                                    context,
+                                )
+                            }
+                        };
+                        for &op in &[first_op, second_op] {
+                            if op > 5 {
+                                return (Err(Propagate::Err(AmlError::InvalidMatchOpcode(op))), context);
+                            }
+                        }
+                        let start_index = try_with_context!(context, start_index.as_integer(context));
+                        if start_index >= elements.len() as u64 {
+                            return (Err(Propagate::Err(AmlError::IndexOutOfBounds)), context);
+                        }
+
+                        let matches =
+                            |element: &AmlValue, op: u8, operand: &AmlValue, context: &mut AmlContext| {
+                                // `MTR` matches every element, even those that can't be compared with the operand
+                                if op == 0 {
+                                    return true;
+                                }
+                                let ordering = match element.cmp(operand.clone(), context) {
+                                    Ok(ordering) => ordering,
+                                    Err(_) => return false,
+                                };
+
+                                match op {
+                                    1 => ordering == Ordering::Equal,   // MEQ
+                                    2 => ordering != Ordering::Greater, // MLE
+                                    3 => ordering == Ordering::Less,    // MLT
+                                    4 => ordering != Ordering::Less,    // MGE
+                                    5 => ordering == Ordering::Greater, // MGT
+                                    _ => unreachable!(),
+                                }
+                            };
+
+                        for (index, element) in elements.iter().enumerate().skip(start_index as usize) {
+                            if matches(element, first_op, &first_operand, context)
+                                && matches(element, second_op, &second_operand, context)
+                            {
+                                return (Ok(AmlValue::Integer(index as u64)), context);
+                            }
+                        }
+
+                        (Ok(AmlValue::Integer(context.truncate_integer(u64::MAX))), context)
+                    },
+                ),
+        ))
+        .map(|((), result)| Ok(result))
+}
+
 fn def_mid<'a, 'c>() -> impl Parser<'a, 'c, AmlValue>
 where
     'c: 'a,
@@ -456,39 +964,37 @@ where
             "DefMid",
             term_arg().then(term_arg()).then(term_arg()).then(target()).map_with_context(
                 |(((source, index), length), target), context| {
-                    let index = try_with_context!(context, index.as_integer(context)) as usize;
-                    let length = try_with_context!(context, length.as_integer(context)) as usize;
+                    let index = try_with_context!(context, index.as_integer(context));
+                    let length = try_with_context!(context, length.as_integer(context));
+
+                    /*
+                     * The range is clamped to the bounds of the source, rather than producing an error. This is
+                     * done on `u64`s so that large indices and lengths (often `Ones`) can't overflow.
+                     */
+                    let bounds = |source_len: usize| {
+                        let start = index.min(source_len as u64) as usize;
+                        let end = index.saturating_add(length).min(source_len as u64) as usize;
+                        start..end
+                    };
 
                     let result = try_with_context!(
                         context,
                         match source {
                             AmlValue::Buffer(bytes) => {
-                                let foo = bytes.lock();
-                                if index >= foo.len() {
-                                    Ok(AmlValue::Buffer(Arc::new(spinning_top::Spinlock::new(vec![]))))
-                                } else if (index + length) >= foo.len() {
-                                    Ok(AmlValue::Buffer(Arc::new(spinning_top::Spinlock::new(
-                                        foo[index..].to_vec(),
-                                    ))))
-                                } else {
-                                    Ok(AmlValue::Buffer(Arc::new(spinning_top::Spinlock::new(
-                                        foo[index..(index + length)].to_vec(),
-                                    ))))
-                                }
+                                let bytes = bytes.lock();
+                                Ok(AmlValue::Buffer(Arc::new(spinning_top::Spinlock::new(
+                                    bytes[bounds(bytes.len())].to_vec(),
+                                ))))
                             }
                             /*
                              * XXX: The spec conflates characters and bytes, so we effectively ignore unicode and do
-                             * this bytewise, to hopefully match other implementations.
+                             * this bytewise, to hopefully match other implementations. Strings that didn't come from
+                             * AML can contain multi-byte characters, and a range that splits one can't be sliced.
                              */
-                            AmlValue::String(string) => {
-                                if index >= string.len() {
-                                    Ok(AmlValue::String(String::new()))
-                                } else if (index + length) >= string.len() {
-                                    Ok(AmlValue::String(string[index..].to_string()))
-                                } else {
-                                    Ok(AmlValue::String(string[index..(index + length)].to_string()))
-                                }
-                            }
+                            AmlValue::String(string) => string
+                                .get(bounds(string.len()))
+                                .map(|string| AmlValue::String(string.to_string()))
+                                .ok_or(AmlError::TypeCannotBeSliced(AmlType::String)),
                             _ => Err(AmlError::TypeCannotBeSliced(source.type_of())),
                         }
                     );
@@ -501,53 +1007,339 @@ where
         .map(|((), result)| Ok(result))
 }
 
-pub fn def_package<'a, 'c>() -> impl Parser<'a, 'c, AmlValue>
+fn def_mod<'a, 'c>() -> impl Parser<'a, 'c, AmlValue>
 where
     'c: 'a,
 {
     /*
-     * DefPackage := 0x12 PkgLength NumElements PackageElementList
-     * NumElements := ByteData
-     * PackageElementList := Nothing | <PackageElement PackageElementList>
-     * PackageElement := DataRefObject | NameString
+     * DefMod := 0x85 Dividend Divisor Target
+     * Dividend := TermArg => Integer
+     * Divisor := TermArg => Integer
      */
-    opcode(opcode::DEF_PACKAGE_OP)
+    opcode(opcode::DEF_MOD_OP)
         .then(comment_scope(
             DebugVerbosity::AllScopes,
-            "DefPackage",
-            pkg_length().then(take()).feed(|(pkg_length, num_elements)| {
-                move |mut input, mut context| {
-                    let mut package_contents = Vec::new();
-
-                    while pkg_length.still_parsing(input) {
-                        let (new_input, new_context, value) = package_element().parse(input, context)?;
-                        input = new_input;
-                        context = new_context;
-
-                        package_contents.push(value);
-                    }
-
-                    if package_contents.len() != num_elements as usize {
-                        return Err((input, context, Propagate::Err(AmlError::MalformedPackage)));
+            "DefMod",
+            term_arg().then(term_arg()).then(target()).map_with_context(
+                |((dividend, divisor), target), context| {
+                    let dividend = try_with_context!(context, dividend.as_integer(context));
+                    let divisor = try_with_context!(context, divisor.as_integer(context));
+                    if divisor == 0 {
+                        return (Err(Propagate::Err(AmlError::DivideByZero)), context);
                     }
+                    let result = AmlValue::Integer(dividend % divisor);
 
-                    Ok((input, context, AmlValue::Package(package_contents)))
-                }
-            }),
+                    try_with_context!(context, context.store(target, result.clone()));
+                    (Ok(result), context)
+                },
+            ),
         ))
-        .map(|((), package)| Ok(package))
+        .map(|((), result)| Ok(result))
 }
 
-pub fn package_element<'a, 'c>() -> impl Parser<'a, 'c, AmlValue>
+fn def_multiply<'a, 'c>() -> impl Parser<'a, 'c, AmlValue>
 where
     'c: 'a,
 {
-    choice!(data_ref_object(), name_string().map(|string| Ok(AmlValue::String(string.as_string()))))
-}
-
-fn def_shift_left<'a, 'c>() -> impl Parser<'a, 'c, AmlValue>
-where
-    'c: 'a,
+    /*
+     * DefMultiply := 0x77 Operand Operand Target
+     * Operand := TermArg => Integer
+     */
+    opcode(opcode::DEF_MULTIPLY_OP)
+        .then(comment_scope(
+            DebugVerbosity::AllScopes,
+            "DefMultiply",
+            term_arg().then(term_arg()).then(target()).map_with_context(
+                |((left_arg, right_arg), target), context| {
+                    let left = try_with_context!(context, left_arg.as_integer(context));
+                    let right = try_with_context!(context, right_arg.as_integer(context));
+                    let result = AmlValue::Integer(context.truncate_integer(left.wrapping_mul(right)));
+
+                    try_with_context!(context, context.store(target, result.clone()));
+                    (Ok(result), context)
+                },
+            ),
+        ))
+        .map(|((), result)| Ok(result))
+}
+
+fn def_nand<'a, 'c>() -> impl Parser<'a, 'c, AmlValue>
+where
+    'c: 'a,
+{
+    /*
+     * DefNand := 0x7c Operand Operand Target
+     * Operand := TermArg => Integer
+     */
+    opcode(opcode::DEF_NAND_OP)
+        .then(comment_scope(
+            DebugVerbosity::AllScopes,
+            "DefNand",
+            term_arg().then(term_arg()).then(target()).map_with_context(
+                |((left_arg, right_arg), target), context| {
+                    let left = try_with_context!(context, left_arg.as_integer(context));
+                    let right = try_with_context!(context, right_arg.as_integer(context));
+                    let result = AmlValue::Integer(context.truncate_integer(!(left & right)));
+
+                    try_with_context!(context, context.store(target, result.clone()));
+                    (Ok(result), context)
+                },
+            ),
+        ))
+        .map(|((), result)| Ok(result))
+}
+
+fn def_nor<'a, 'c>() -> impl Parser<'a, 'c, AmlValue>
+where
+    'c: 'a,
+{
+    /*
+     * DefNor := 0x7e Operand Operand Target
+     * Operand := TermArg => Integer
+     */
+    opcode(opcode::DEF_NOR_OP)
+        .then(comment_scope(
+            DebugVerbosity::AllScopes,
+            "DefNor",
+            term_arg().then(term_arg()).then(target()).map_with_context(
+                |((left_arg, right_arg), target), context| {
+                    let left = try_with_context!(context, left_arg.as_integer(context));
+                    let right = try_with_context!(context, right_arg.as_integer(context));
+                    let result = AmlValue::Integer(context.truncate_integer(!(left | right)));
+
+                    try_with_context!(context, context.store(target, result.clone()));
+                    (Ok(result), context)
+                },
+            ),
+        ))
+        .map(|((), result)| Ok(result))
+}
+
+fn def_not<'a, 'c>() -> impl Parser<'a, 'c, AmlValue>
+where
+    'c: 'a,
+{
+    /*
+     * DefNot := 0x80 Operand Target
+     * Operand := TermArg => Integer
+     */
+    opcode(opcode::DEF_NOT_OP)
+        .then(comment_scope(
+            DebugVerbosity::AllScopes,
+            "DefNot",
+            term_arg().then(target()).map_with_context(|(operand, target), context| {
+                let operand = try_with_context!(context, operand.as_integer(context));
+                let result = AmlValue::Integer(context.truncate_integer(!operand));
+
+                try_with_context!(context, context.store(target, result.clone()));
+                (Ok(result), context)
+            }),
+        ))
+        .map(|((), result)| Ok(result))
+}
+
+fn def_object_type<'a, 'c>() -> impl Parser<'a, 'c, AmlValue>
+where
+    'c: 'a,
+{
+    /*
+     * DefObjectType := 0x8e <SimpleName | DebugObj | DefRefOf | DefDerefOf | DefIndex>
+     */
+    opcode(opcode::DEF_OBJECT_TYPE_OP)
+        .then(comment_scope(
+            DebugVerbosity::AllScopes,
+            "DefObjectType",
+            choice!(
+                def_ref_of().map_with_context(|reference, context| {
+                    let object_type =
+                        try_with_context!(context, context.dereference(&reference)).object_type_code();
+                    (Ok(object_type), context)
+                }),
+                def_deref_of().map(|object| Ok(object.object_type_code())),
+                def_index().map_with_context(|object, context| {
+                    let object_type = try_with_context!(context, context.dereference(&object)).object_type_code();
+                    (Ok(object_type), context)
+                }),
+                // XXX: this must appear last, as a name that fails to parse produces an error other than `WrongParser`
+                super_name().map_with_context(|target, context| {
+                    let object_type = match target {
+                        Target::Debug => AmlType::DebugObject.object_type_code(),
+                        target => {
+                            let object = try_with_context!(context, context.read_target(&target));
+                            try_with_context!(context, context.dereference(object)).object_type_code()
+                        }
+                    };
+                    (Ok(object_type), context)
+                })
+            ),
+        ))
+        .map(|((), object_type)| Ok(AmlValue::Integer(object_type)))
+}
+
+fn def_or<'a, 'c>() -> impl Parser<'a, 'c, AmlValue>
+where
+    'c: 'a,
+{
+    /*
+     * DefOr := 0x7d Operand Operand Target
+     * Operand := TermArg => Integer
+     */
+    opcode(opcode::DEF_OR_OP)
+        .then(comment_scope(
+            DebugVerbosity::AllScopes,
+            "DefOr",
+            term_arg().then(term_arg()).then(target()).map_with_context(
+                |((left_arg, right_arg), target), context| {
+                    let left = try_with_context!(context, left_arg.as_integer(context));
+                    let right = try_with_context!(context, right_arg.as_integer(context));
+                    let result = AmlValue::Integer(left | right);
+
+                    try_with_context!(context, context.store(target, result.clone()));
+                    (Ok(result), context)
+                },
+            ),
+        ))
+        .map(|((), result)| Ok(result))
+}
+
+pub fn def_package<'a, 'c>() -> impl Parser<'a, 'c, AmlValue>
+where
+    'c: 'a,
+{
+    /*
+     * DefPackage := 0x12 PkgLength NumElements PackageElementList
+     * NumElements := ByteData
+     * PackageElementList := Nothing | <PackageElement PackageElementList>
+     * PackageElement := DataRefObject | NameString
+     *
+     * If `NumElements` is larger than the number of elements in the `PackageElementList`, the remaining elements
+     * are uninitialized.
+     */
+    opcode(opcode::DEF_PACKAGE_OP)
+        .then(comment_scope(
+            DebugVerbosity::AllScopes,
+            "DefPackage",
+            pkg_length().then(take()).feed(|(pkg_length, num_elements)| {
+                within_pkglength(pkg_length, move |mut input: &'a [u8], mut context: &'c mut AmlContext| {
+                    let mut package_contents = Vec::new();
+
+                    while !input.is_empty() {
+                        let (new_input, new_context, value) = package_element().parse(input, context)?;
+                        input = new_input;
+                        context = new_context;
+
+                        package_contents.push(value);
+                    }
+
+                    if package_contents.len() > num_elements as usize {
+                        return Err((input, context, Propagate::Err(AmlError::MalformedPackage)));
+                    }
+                    package_contents.resize(num_elements as usize, AmlValue::Uninitialized);
+
+                    Ok((input, context, AmlValue::Package(package_contents)))
+                })
+            }),
+        ))
+        .map(|((), package)| Ok(package))
+}
+
+pub fn def_var_package<'a, 'c>() -> impl Parser<'a, 'c, AmlValue>
+where
+    'c: 'a,
+{
+    /*
+     * DefVarPackage := 0x13 PkgLength VarNumElements PackageElementList
+     * VarNumElements := TermArg => Integer
+     *
+     * Like `DefPackage`, any elements not initialized by the `PackageElementList` are uninitialized.
+     */
+    opcode(opcode::DEF_VAR_PACKAGE_OP)
+        .then(comment_scope(
+            DebugVerbosity::AllScopes,
+            "DefVarPackage",
+            pkg_length().then(term_arg()).feed(|(pkg_length, num_elements)| {
+                within_pkglength(pkg_length, move |mut input: &'a [u8], mut context: &'c mut AmlContext| {
+                    let num_elements = match num_elements.as_integer(context) {
+                        Ok(num_elements) => usize::try_from(num_elements).unwrap_or(usize::MAX),
+                        Err(err) => return Err((input, context, Propagate::Err(err))),
+                    };
+                    let mut package_contents = Vec::new();
+
+                    while !input.is_empty() {
+                        let (new_input, new_context, value) = package_element().parse(input, context)?;
+                        input = new_input;
+                        context = new_context;
+
+                        package_contents.push(value);
+                    }
+
+                    if package_contents.len() > num_elements {
+                        return Err((input, context, Propagate::Err(AmlError::MalformedPackage)));
+                    }
+                    if package_contents.try_reserve_exact(num_elements - package_contents.len()).is_err() {
+                        return Err((input, context, Propagate::Err(AmlError::ObjectTooLarge)));
+                    }
+                    package_contents.resize(num_elements, AmlValue::Uninitialized);
+
+                    Ok((input, context, AmlValue::Package(package_contents)))
+                })
+            }),
+        ))
+        .map(|((), package)| Ok(package))
+}
+
+pub fn package_element<'a, 'c>() -> impl Parser<'a, 'c, AmlValue>
+where
+    'c: 'a,
+{
+    choice!(data_ref_object(), name_string().map(|string| Ok(AmlValue::String(string.as_string()))))
+}
+
+fn def_ref_of<'a, 'c>() -> impl Parser<'a, 'c, AmlValue>
+where
+    'c: 'a,
+{
+    /*
+     * DefRefOf := 0x71 SuperName
+     */
+    opcode(opcode::DEF_REF_OF_OP)
+        .then(comment_scope(
+            DebugVerbosity::AllScopes,
+            "DefRefOf",
+            super_name().map_with_context(|source, context| {
+                (Ok(try_with_context!(context, context.reference_to(&source))), context)
+            }),
+        ))
+        .map(|((), result)| Ok(result))
+}
+
+pub fn reference_type_opcode<'a, 'c>() -> impl Parser<'a, 'c, Target>
+where
+    'c: 'a,
+{
+    /*
+     * ReferenceTypeOpcode := DefRefOf | DefDerefOf | DefIndex | UserTermObj
+     *
+     * When used as a `SuperName`, these refer to the object that the reference they produce refers to. For
+     * `DerefOf`, that's the object its operand refers to, so the operand is used directly.
+     * TODO: this doesn't cover UserTermObj (method invocations that return a reference) yet
+     */
+    comment_scope(
+        DebugVerbosity::AllScopes,
+        "ReferenceTypeOpcode",
+        choice!(
+            def_ref_of().map(|reference| Ok(Target::Reference(reference))),
+            opcode(opcode::DEF_DEREF_OF_OP)
+                .then(term_arg())
+                .map(|((), reference)| Ok(Target::Reference(reference))),
+            def_index().map(|reference| Ok(Target::Reference(reference)))
+        ),
+    )
+}
+
+fn def_shift_left<'a, 'c>() -> impl Parser<'a, 'c, AmlValue>
+where
+    'c: 'a,
 {
     /*
      * DefShiftLeft := 0x79 Operand ShiftCount Target
@@ -559,12 +1351,10 @@ where
         .map_with_context(|((), ((operand, shift_count), target)), context| {
             let operand = try_with_context!(context, operand.as_integer(context));
             let shift_count = try_with_context!(context, shift_count.as_integer(context));
-            let shift_count =
-                try_with_context!(context, shift_count.try_into().map_err(|_| AmlError::InvalidShiftLeft));
 
-            let result = AmlValue::Integer(try_with_context!(
-                context,
-                operand.checked_shl(shift_count).ok_or(AmlError::InvalidShiftLeft)
+            // Shifting by the width of an integer or more shifts every bit out, which leaves zero
+            let result = AmlValue::Integer(context.truncate_integer(
+                shift_count.try_into().ok().and_then(|shift_count| operand.checked_shl(shift_count)).unwrap_or(0),
             ));
 
             try_with_context!(context, context.store(target, result.clone()));
@@ -586,19 +1376,44 @@ where
         .map_with_context(|((), ((operand, shift_count), target)), context| {
             let operand = try_with_context!(context, operand.as_integer(context));
             let shift_count = try_with_context!(context, shift_count.as_integer(context));
-            let shift_count =
-                try_with_context!(context, shift_count.try_into().map_err(|_| AmlError::InvalidShiftRight));
 
-            let result = AmlValue::Integer(try_with_context!(
-                context,
-                operand.checked_shr(shift_count).ok_or(AmlError::InvalidShiftRight)
-            ));
+            let result = AmlValue::Integer(
+                shift_count.try_into().ok().and_then(|shift_count| operand.checked_shr(shift_count)).unwrap_or(0),
+            );
 
             try_with_context!(context, context.store(target, result.clone()));
             (Ok(result), context)
         })
 }
 
+fn def_size_of<'a, 'c>() -> impl Parser<'a, 'c, AmlValue>
+where
+    'c: 'a,
+{
+    /*
+     * DefSizeOf := 0x87 SuperName
+     */
+    opcode(opcode::DEF_SIZE_OF_OP)
+        .then(comment_scope(
+            DebugVerbosity::AllScopes,
+            "DefSizeOf",
+            super_name().map_with_context(|source, context| {
+                let object = try_with_context!(context, context.read_target(&source));
+                let object = try_with_context!(context, context.dereference(object));
+
+                let size = match object {
+                    AmlValue::Buffer(bytes) => bytes.lock().len(),
+                    AmlValue::String(string) => string.len(),
+                    AmlValue::Package(elements) => elements.len(),
+                    _ => return (Err(Propagate::Err(AmlError::TypeCannotBeSized(object.type_of()))), context),
+                };
+
+                (Ok(AmlValue::Integer(size as u64)), context)
+            }),
+        ))
+        .map(|((), result)| Ok(result))
+}
+
 fn def_store<'a, 'c>() -> impl Parser<'a, 'c, AmlValue>
 where
     'c: 'a,
@@ -619,6 +1434,176 @@ where
         })
 }
 
+fn def_subtract<'a, 'c>() -> impl Parser<'a, 'c, AmlValue>
+where
+    'c: 'a,
+{
+    /*
+     * DefSubtract := 0x74 Operand Operand Target
+     * Operand := TermArg => Integer
+     */
+    opcode(opcode::DEF_SUBTRACT_OP)
+        .then(comment_scope(
+            DebugVerbosity::AllScopes,
+            "DefSubtract",
+            term_arg().then(term_arg()).then(target()).map_with_context(
+                |((left_arg, right_arg), target), context| {
+                    let left = try_with_context!(context, left_arg.as_integer(context));
+                    let right = try_with_context!(context, right_arg.as_integer(context));
+                    let result = AmlValue::Integer(context.truncate_integer(left.wrapping_sub(right)));
+
+                    try_with_context!(context, context.store(target, result.clone()));
+                    (Ok(result), context)
+                },
+            ),
+        ))
+        .map(|((), result)| Ok(result))
+}
+
+fn def_to_bcd<'a, 'c>() -> impl Parser<'a, 'c, AmlValue>
+where
+    'c: 'a,
+{
+    /*
+     * DefToBCD := ExtOpPrefix 0x29 Operand Target
+     * Operand := TermArg => Integer
+     */
+    ext_opcode(opcode::EXT_DEF_TO_BCD_OP)
+        .then(comment_scope(
+            DebugVerbosity::AllScopes,
+            "DefToBCD",
+            term_arg().then(target()).map_with_context(|(operand, target), context| {
+                let value = try_with_context!(context, operand.as_integer(context));
+
+                // A 64-bit integer can only hold 16 BCD digits
+                if value > 9_999_999_999_999_999 {
+                    return (Err(Propagate::Err(AmlError::InvalidBcdConversion(value))), context);
+                }
+
+                let mut remaining = value;
+                let mut result = 0u64;
+                let mut shift = 0;
+                while remaining != 0 {
+                    result |= (remaining % 10) << shift;
+                    remaining /= 10;
+                    shift += 4;
+                }
+                let result = AmlValue::Integer(result);
+
+                try_with_context!(context, context.store(target, result.clone()));
+                (Ok(result), context)
+            }),
+        ))
+        .map(|((), result)| Ok(result))
+}
+
+fn def_to_buffer<'a, 'c>() -> impl Parser<'a, 'c, AmlValue>
+where
+    'c: 'a,
+{
+    /*
+     * DefToBuffer := 0x96 Operand Target
+     * Operand := TermArg => ComputationalData
+     *
+     * Integers are converted to 8 bytes (or 4, if integers are 32 bits wide), least significant byte first.
+     * Strings are copied byte-for-byte, including the null terminator.
+     */
+    opcode(opcode::DEF_TO_BUFFER_OP)
+        .then(comment_scope(
+            DebugVerbosity::AllScopes,
+            "DefToBuffer",
+            term_arg().then(target()).map_with_context(|(operand, target), context| {
+                let bytes = match operand {
+                    AmlValue::Buffer(ref bytes) => bytes.lock().clone(),
+                    AmlValue::String(ref string) => {
+                        let mut bytes = string.as_bytes().to_vec();
+                        bytes.push(0x00);
+                        bytes
+                    }
+                    _ => {
+                        let bytes = try_with_context!(context, operand.as_integer(context)).to_le_bytes();
+                        bytes[0..(context.integer_width() as usize / 8)].to_vec()
+                    }
+                };
+                let result = AmlValue::Buffer(Arc::new(spinning_top::Spinlock::new(bytes)));
+
+                try_with_context!(context, context.store(target, result.clone()));
+                (Ok(result), context)
+            }),
+        ))
+        .map(|((), result)| Ok(result))
+}
+
+fn def_to_decimal_string<'a, 'c>() -> impl Parser<'a, 'c, AmlValue>
+where
+    'c: 'a,
+{
+    /*
+     * DefToDecimalString := 0x97 Operand Target
+     * Operand := TermArg => ComputationalData
+     *
+     * Buffers are converted to a comma-separated list of the decimal values of each byte.
+     */
+    opcode(opcode::DEF_TO_DECIMAL_STRING_OP)
+        .then(comment_scope(
+            DebugVerbosity::AllScopes,
+            "DefToDecimalString",
+            term_arg().then(target()).map_with_context(|(operand, target), context| {
+                let string = match operand {
+                    AmlValue::String(string) => string,
+                    AmlValue::Buffer(ref bytes) => {
+                        bytes.lock().iter().map(|byte| byte.to_string()).collect::<Vec<String>>().join(",")
+                    }
+                    _ => try_with_context!(context, operand.as_integer(context)).to_string(),
+                };
+                let result = AmlValue::String(string);
+
+                try_with_context!(context, context.store(target, result.clone()));
+                (Ok(result), context)
+            }),
+        ))
+        .map(|((), result)| Ok(result))
+}
+
+fn def_to_hex_string<'a, 'c>() -> impl Parser<'a, 'c, AmlValue>
+where
+    'c: 'a,
+{
+    /*
+     * DefToHexString := 0x98 Operand Target
+     * Operand := TermArg => ComputationalData
+     *
+     * Integers are converted to 16 hexadecimal digits (or 8, if integers are 32 bits wide), and buffers to a
+     * comma-separated list of `0x`-prefixed hexadecimal values of each byte, as ACPICA does.
+     */
+    opcode(opcode::DEF_TO_HEX_STRING_OP)
+        .then(comment_scope(
+            DebugVerbosity::AllScopes,
+            "DefToHexString",
+            term_arg().then(target()).map_with_context(|(operand, target), context| {
+                let string = match operand {
+                    AmlValue::String(string) => string,
+                    AmlValue::Buffer(ref bytes) => bytes
+                        .lock()
+                        .iter()
+                        .map(|byte| alloc::format!("0x{:02X}", byte))
+                        .collect::<Vec<String>>()
+                        .join(","),
+                    _ => alloc::format!(
+                        "{:0width$X}",
+                        context.truncate_integer(try_with_context!(context, operand.as_integer(context))),
+                        width = context.integer_width() as usize / 4
+                    ),
+                };
+                let result = AmlValue::String(string);
+
+                try_with_context!(context, context.store(target, result.clone()));
+                (Ok(result), context)
+            }),
+        ))
+        .map(|((), result)| Ok(result))
+}
+
 fn def_to_integer<'a, 'c>() -> impl Parser<'a, 'c, AmlValue>
 where
     'c: 'a,
@@ -662,6 +1647,105 @@ where
         })
 }
 
+fn def_to_string<'a, 'c>() -> impl Parser<'a, 'c, AmlValue>
+where
+    'c: 'a,
+{
+    /*
+     * DefToString := 0x9c TermArg LengthArg Target
+     * LengthArg := TermArg => Integer
+     *
+     * Bytes are copied out of the buffer until a null byte, `LengthArg` bytes, or the end of the buffer is
+     * reached. A `LengthArg` of `Ones` means there is no maximum length.
+     */
+    opcode(opcode::DEF_TO_STRING_OP)
+        .then(comment_scope(
+            DebugVerbosity::AllScopes,
+            "DefToString",
+            term_arg().then(term_arg()).then(target()).map_with_context(|((source, length), target), context| {
+                let source = try_with_context!(context, source.as_buffer(context));
+                let length = try_with_context!(context, length.as_integer(context));
+
+                let source = source.lock();
+                let max_length: usize = length.try_into().unwrap_or(usize::MAX);
+                let max_length = max_length.min(source.len());
+                let string_length =
+                    source[..max_length].iter().position(|&byte| byte == 0x00).unwrap_or(max_length);
+                /*
+                 * Like string constants, strings produced from buffers can only contain ASCII characters.
+                 */
+                if !source[..string_length].is_ascii() {
+                    return (
+                        Err(Propagate::Err(AmlError::IncompatibleValueConversion {
+                            current: AmlType::Buffer,
+                            target: AmlType::String,
+                        })),
+                        context,
+                    );
+                }
+                let string = String::from(str::from_utf8(&source[..string_length]).unwrap());
+                drop(source);
+                let result = AmlValue::String(string);
+
+                try_with_context!(context, context.store(target, result.clone()));
+                (Ok(result), context)
+            }),
+        ))
+        .map(|((), result)| Ok(result))
+}
+
+fn def_wait<'a, 'c>() -> impl Parser<'a, 'c, AmlValue>
+where
+    'c: 'a,
+{
+    /*
+     * DefWait := ExtOpPrefix 0x25 EventObject Operand
+     * EventObject := SuperName
+     * Operand := TermArg => Integer
+     *
+     * This evaluates to `True` if the wait for the event timed out, and `False` if the event was signalled.
+     */
+    ext_opcode(opcode::EXT_DEF_WAIT_OP)
+        .then(comment_scope(
+            DebugVerbosity::AllScopes,
+            "DefWait",
+            super_name().then(term_arg()).map_with_context(|(event, timeout), context| {
+                let event = try_with_context!(context, context.resolve_event(event));
+                // Timeouts of `0xffff` and above mean to wait forever
+                let timeout = try_with_context!(context, timeout.as_integer(context)).min(0xffff) as u16;
+                let signalled = context.handler.wait_for_event(&event, timeout);
+                (Ok(AmlValue::Boolean(!signalled)), context)
+            }),
+        ))
+        .map(|((), result)| Ok(result))
+}
+
+fn def_xor<'a, 'c>() -> impl Parser<'a, 'c, AmlValue>
+where
+    'c: 'a,
+{
+    /*
+     * DefXor := 0x7f Operand Operand Target
+     * Operand := TermArg => Integer
+     */
+    opcode(opcode::DEF_XOR_OP)
+        .then(comment_scope(
+            DebugVerbosity::AllScopes,
+            "DefXor",
+            term_arg().then(term_arg()).then(target()).map_with_context(
+                |((left_arg, right_arg), target), context| {
+                    let left = try_with_context!(context, left_arg.as_integer(context));
+                    let right = try_with_context!(context, right_arg.as_integer(context));
+                    let result = AmlValue::Integer(left ^ right);
+
+                    try_with_context!(context, context.store(target, result.clone()));
+                    (Ok(result), context)
+                },
+            ),
+        ))
+        .map(|((), result)| Ok(result))
+}
+
 fn method_invocation<'a, 'c>() -> impl Parser<'a, 'c, AmlValue>
 where
     'c: 'a,
@@ -687,12 +1771,14 @@ where
                  * `None` if the path is not a method and so doesn't have arguments, or `Some(the number of
                  * arguments to parse)` if it's a method.
                  */
-                let num_args = if let AmlValue::Method { flags, .. } =
-                    try_with_context!(context, context.namespace.get(handle))
-                {
-                    Some(flags.arg_count())
-                } else {
-                    None
+                let num_args = match try_with_context!(context, context.namespace.get(handle)) {
+                    AmlValue::Method { flags, .. } => Some(flags.arg_count()),
+                    /*
+                     * If the method is defined by a table that hasn't been loaded yet, its `External` tells us how
+                     * many arguments it takes. Methods have an `ObjectType` of `8`.
+                     */
+                    AmlValue::External { object_type: 8, arg_count } => Some(*arg_count),
+                    _ => None,
                 };
                 (Ok((full_path, num_args)), context)
             })
@@ -709,3 +1795,1135 @@ where
             }),
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_utils::*, value::Args};
+    use alloc::vec;
+
+    #[test]
+    fn test_def_store() {
+        let mut context = make_test_context();
+
+        /*
+         * Name(FOO, Zero)
+         * Method(BAR, 1) {
+         *     Store(0x2a, FOO)
+         *     Store(Store(Arg0, Local1), Local0)
+         *     Return(Local0)
+         * }
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x08, b'F', b'O', b'O', b'_', 0x00, 0x14, 0x14, b'B', b'A', b'R', b'_', 0x01, 0x70, 0x0a, 0x2a,
+                b'F', b'O', b'O', b'_', 0x70, 0x70, 0x68, 0x61, 0x60, 0xa4, 0x60,
+            ]),
+            Ok(())
+        );
+        assert!(crudely_cmp_values(
+            &context
+                .invoke_method(
+                    &AmlName::from_str("\\BAR").unwrap(),
+                    Args::from_list(vec![AmlValue::Integer(7)]).unwrap()
+                )
+                .unwrap(),
+            &AmlValue::Integer(7)
+        ));
+        assert!(crudely_cmp_values(
+            context.namespace.get_by_path(&AmlName::from_str("\\FOO").unwrap()).unwrap(),
+            &AmlValue::Integer(0x2a)
+        ));
+    }
+
+    #[test]
+    fn test_store_into_args() {
+        let mut context = make_test_context();
+
+        /*
+         * Method(ARGS, 1) {
+         *     Store(0x2a, Arg0)
+         *     Return(Arg0)
+         * }
+         */
+        assert_eq!(
+            context.parse_table(&[0x14, 0x0c, b'A', b'R', b'G', b'S', 0x01, 0x70, 0x0a, 0x2a, 0x68, 0xa4, 0x68,]),
+            Ok(())
+        );
+        assert!(crudely_cmp_values(
+            &context
+                .invoke_method(
+                    &AmlName::from_str("\\ARGS").unwrap(),
+                    Args::from_list(vec![AmlValue::Integer(7)]).unwrap()
+                )
+                .unwrap(),
+            &AmlValue::Integer(0x2a)
+        ));
+    }
+
+    #[test]
+    fn test_store_through_references() {
+        let mut context = make_test_context();
+
+        /*
+         * Name(BUF, Buffer() { 0x10, 0x20, 0x30 })
+         * Name(FOO, Zero)
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x08, b'B', b'U', b'F', b'_', 0x11, 0x06, 0x0a, 0x03, 0x10, 0x20, 0x30, 0x08, b'F', b'O', b'O',
+                b'_', 0x00,
+            ]),
+            Ok(())
+        );
+
+        // Store(0x55, Index(BUF, One)) and Store(0x66, DerefOf(Index(BUF, 2)))
+        check_ok_value!(
+            expression_opcode().parse(&[0x70, 0x0a, 0x55, 0x88, b'B', b'U', b'F', b'_', 0x01, 0x00], &mut context),
+            AmlValue::Integer(0x55),
+            &[]
+        );
+        check_ok_value!(
+            expression_opcode()
+                .parse(&[0x70, 0x0a, 0x66, 0x83, 0x88, b'B', b'U', b'F', b'_', 0x0a, 0x02, 0x00], &mut context),
+            AmlValue::Integer(0x66),
+            &[]
+        );
+        assert!(crudely_cmp_values(
+            context.namespace.get_by_path(&AmlName::from_str("\\BUF").unwrap()).unwrap(),
+            &AmlValue::buffer(vec![0x10, 0x55, 0x66])
+        ));
+
+        // Store(0x2a, DerefOf("FOO")) stores into the named object
+        check_ok_value!(
+            expression_opcode().parse(&[0x70, 0x0a, 0x2a, 0x83, 0x0d, b'F', b'O', b'O', 0x00], &mut context),
+            AmlValue::Integer(0x2a),
+            &[]
+        );
+        assert!(crudely_cmp_values(
+            context.namespace.get_by_path(&AmlName::from_str("\\FOO").unwrap()).unwrap(),
+            &AmlValue::Integer(0x2a)
+        ));
+
+        // Store(One, RefOf(FOO)) stores into the named object
+        check_ok_value!(
+            expression_opcode().parse(&[0x70, 0x01, 0x71, b'F', b'O', b'O', b'_'], &mut context),
+            AmlValue::Integer(1),
+            &[]
+        );
+        assert!(crudely_cmp_values(
+            context.namespace.get_by_path(&AmlName::from_str("\\FOO").unwrap()).unwrap(),
+            &AmlValue::Integer(1)
+        ));
+
+        /*
+         * Method(SET, 1) { Store(0x33, DerefOf(Arg0)) }
+         * SET(RefOf(FOO))
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x14, 0x0b, b'S', b'E', b'T', b'_', 0x01, 0x70, 0x0a, 0x33, 0x83, 0x68, b'S', b'E', b'T', b'_',
+                0x71, b'F', b'O', b'O', b'_',
+            ]),
+            Ok(())
+        );
+        assert!(crudely_cmp_values(
+            context.namespace.get_by_path(&AmlName::from_str("\\FOO").unwrap()).unwrap(),
+            &AmlValue::Integer(0x33)
+        ));
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let mut context = make_test_context();
+
+        /*
+         * Name(QUO, Zero)
+         * Name(REM, Zero)
+         */
+        assert_eq!(
+            context.parse_table(&[0x08, b'Q', b'U', b'O', b'_', 0x00, 0x08, b'R', b'E', b'M', b'_', 0x00]),
+            Ok(())
+        );
+
+        // Add(Ones, 2)
+        check_ok_value!(
+            expression_opcode().parse(&[0x72, 0xff, 0x0a, 0x02, 0x00], &mut context),
+            AmlValue::Integer(1),
+            &[]
+        );
+        // Subtract(5, 7)
+        check_ok_value!(
+            expression_opcode().parse(&[0x74, 0x0a, 0x05, 0x0a, 0x07, 0x00], &mut context),
+            AmlValue::Integer(u64::max_value() - 1),
+            &[]
+        );
+        // Multiply(0x100000000, 0x100000001, QUO)
+        check_ok_value!(
+            expression_opcode().parse(
+                &[
+                    0x77, 0x0e, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x0e, 0x01, 0x00, 0x00, 0x00,
+                    0x01, 0x00, 0x00, 0x00, b'Q', b'U', b'O', b'_'
+                ],
+                &mut context
+            ),
+            AmlValue::Integer(0x100000000),
+            &[]
+        );
+        assert!(crudely_cmp_values(
+            context.namespace.get_by_path(&AmlName::from_str("\\QUO").unwrap()).unwrap(),
+            &AmlValue::Integer(0x100000000)
+        ));
+        // Divide(9, 4, REM, QUO)
+        check_ok_value!(
+            expression_opcode().parse(
+                &[0x78, 0x0a, 0x09, 0x0a, 0x04, b'R', b'E', b'M', b'_', b'Q', b'U', b'O', b'_'],
+                &mut context
+            ),
+            AmlValue::Integer(2),
+            &[]
+        );
+        assert!(crudely_cmp_values(
+            context.namespace.get_by_path(&AmlName::from_str("\\QUO").unwrap()).unwrap(),
+            &AmlValue::Integer(2)
+        ));
+        assert!(crudely_cmp_values(
+            context.namespace.get_by_path(&AmlName::from_str("\\REM").unwrap()).unwrap(),
+            &AmlValue::Integer(1)
+        ));
+        // Divide(9, 4)
+        check_ok_value!(
+            expression_opcode().parse(&[0x78, 0x0a, 0x09, 0x0a, 0x04, 0x00, 0x00, 0xa3], &mut context),
+            AmlValue::Integer(2),
+            &[0xa3]
+        );
+        // Mod(9, 4)
+        check_ok_value!(
+            expression_opcode().parse(&[0x85, 0x0a, 0x09, 0x0a, 0x04, 0x00], &mut context),
+            AmlValue::Integer(1),
+            &[]
+        );
+        // Divide(9, Zero)
+        check_err!(
+            expression_opcode().parse(&[0x78, 0x0a, 0x09, 0x00, 0x00, 0x00], &mut context),
+            AmlError::DivideByZero,
+            &[0x78, 0x0a, 0x09, 0x00, 0x00, 0x00]
+        );
+        // Mod(9, Zero)
+        check_err!(
+            expression_opcode().parse(&[0x85, 0x0a, 0x09, 0x00, 0x00], &mut context),
+            AmlError::DivideByZero,
+            &[0x85, 0x0a, 0x09, 0x00, 0x00]
+        );
+    }
+
+    #[test]
+    fn test_increment_and_decrement() {
+        let mut context = make_test_context();
+
+        /*
+         * Name(CNT, Zero)
+         * Method(FOO) {
+         *     Store(3, Local0)
+         *     Store(Zero, Local1)
+         *     While (Local0) {
+         *         Decrement(Local0)
+         *         Increment(Local1)
+         *     }
+         *     Return(Local1)
+         * }
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x08, b'C', b'N', b'T', b'_', 0x00, 0x14, 0x16, b'F', b'O', b'O', b'_', 0x00, 0x70, 0x0a, 0x03,
+                0x60, 0x70, 0x00, 0x61, 0xa2, 0x06, 0x60, 0x76, 0x60, 0x75, 0x61, 0xa4, 0x61,
+            ]),
+            Ok(())
+        );
+        assert!(crudely_cmp_values(
+            &context.invoke_method(&AmlName::from_str("\\FOO").unwrap(), Args::EMPTY).unwrap(),
+            &AmlValue::Integer(3)
+        ));
+
+        // Decrement(CNT) wraps around to Ones, and Increment(CNT) wraps back to Zero
+        check_ok_value!(
+            expression_opcode().parse(&[0x76, b'C', b'N', b'T', b'_'], &mut context),
+            AmlValue::Integer(u64::max_value()),
+            &[]
+        );
+        check_ok_value!(
+            expression_opcode().parse(&[0x75, b'C', b'N', b'T', b'_'], &mut context),
+            AmlValue::Integer(0),
+            &[]
+        );
+        assert!(crudely_cmp_values(
+            context.namespace.get_by_path(&AmlName::from_str("\\CNT").unwrap()).unwrap(),
+            &AmlValue::Integer(0)
+        ));
+    }
+
+    #[test]
+    fn test_shifts_and_bitwise_ops() {
+        let mut context = make_test_context();
+
+        // Name(RES, Zero)
+        assert_eq!(context.parse_table(&[0x08, b'R', b'E', b'S', b'_', 0x00]), Ok(()));
+
+        // ShiftLeft(0x0f, 4)
+        check_ok_value!(
+            expression_opcode().parse(&[0x79, 0x0a, 0x0f, 0x0a, 0x04, 0x00], &mut context),
+            AmlValue::Integer(0xf0),
+            &[]
+        );
+        // ShiftRight(0xf0, 4, RES)
+        check_ok_value!(
+            expression_opcode().parse(&[0x7a, 0x0a, 0xf0, 0x0a, 0x04, b'R', b'E', b'S', b'_'], &mut context),
+            AmlValue::Integer(0x0f),
+            &[]
+        );
+        assert!(crudely_cmp_values(
+            context.namespace.get_by_path(&AmlName::from_str("\\RES").unwrap()).unwrap(),
+            &AmlValue::Integer(0x0f)
+        ));
+        // ShiftLeft(Ones, 64) and ShiftRight(Ones, 0x100)
+        check_ok_value!(
+            expression_opcode().parse(&[0x79, 0xff, 0x0a, 0x40, 0x00], &mut context),
+            AmlValue::Integer(0),
+            &[]
+        );
+        check_ok_value!(
+            expression_opcode().parse(&[0x7a, 0xff, 0x0b, 0x00, 0x01, 0x00], &mut context),
+            AmlValue::Integer(0),
+            &[]
+        );
+
+        // And(0x0c, 0x0a)
+        check_ok_value!(
+            expression_opcode().parse(&[0x7b, 0x0a, 0x0c, 0x0a, 0x0a, 0x00], &mut context),
+            AmlValue::Integer(0x08),
+            &[]
+        );
+        // Nand(0x0c, 0x0a)
+        check_ok_value!(
+            expression_opcode().parse(&[0x7c, 0x0a, 0x0c, 0x0a, 0x0a, 0x00], &mut context),
+            AmlValue::Integer(!0x08),
+            &[]
+        );
+        // Or(0x0c, 0x0a, RES)
+        check_ok_value!(
+            expression_opcode().parse(&[0x7d, 0x0a, 0x0c, 0x0a, 0x0a, b'R', b'E', b'S', b'_'], &mut context),
+            AmlValue::Integer(0x0e),
+            &[]
+        );
+        assert!(crudely_cmp_values(
+            context.namespace.get_by_path(&AmlName::from_str("\\RES").unwrap()).unwrap(),
+            &AmlValue::Integer(0x0e)
+        ));
+        // Nor(0x0c, 0x0a)
+        check_ok_value!(
+            expression_opcode().parse(&[0x7e, 0x0a, 0x0c, 0x0a, 0x0a, 0x00], &mut context),
+            AmlValue::Integer(!0x0e),
+            &[]
+        );
+        // Xor(0x0c, 0x0a)
+        check_ok_value!(
+            expression_opcode().parse(&[0x7f, 0x0a, 0x0c, 0x0a, 0x0a, 0x00], &mut context),
+            AmlValue::Integer(0x06),
+            &[]
+        );
+        // Not(Zero) and Not(0x0f, RES)
+        check_ok_value!(
+            expression_opcode().parse(&[0x80, 0x00, 0x00], &mut context),
+            AmlValue::Integer(u64::max_value()),
+            &[]
+        );
+        check_ok_value!(
+            expression_opcode().parse(&[0x80, 0x0a, 0x0f, b'R', b'E', b'S', b'_'], &mut context),
+            AmlValue::Integer(!0x0f),
+            &[]
+        );
+        assert!(crudely_cmp_values(
+            context.namespace.get_by_path(&AmlName::from_str("\\RES").unwrap()).unwrap(),
+            &AmlValue::Integer(!0x0f)
+        ));
+    }
+
+    #[test]
+    fn test_logical_ops() {
+        let mut context = make_test_context();
+
+        // LAnd(One, Zero) and LAnd(One, 7)
+        check_ok_value!(
+            expression_opcode().parse(&[0x90, 0x01, 0x00], &mut context),
+            AmlValue::Boolean(false),
+            &[]
+        );
+        check_ok_value!(
+            expression_opcode().parse(&[0x90, 0x01, 0x0a, 0x07], &mut context),
+            AmlValue::Boolean(true),
+            &[]
+        );
+        // LOr(Zero, Zero)
+        check_ok_value!(
+            expression_opcode().parse(&[0x91, 0x00, 0x00], &mut context),
+            AmlValue::Boolean(false),
+            &[]
+        );
+        // LNot(Zero) and LNot(LEqual(4, 4))
+        check_ok_value!(expression_opcode().parse(&[0x92, 0x00], &mut context), AmlValue::Boolean(true), &[]);
+        check_ok_value!(
+            expression_opcode().parse(&[0x92, 0x93, 0x0a, 0x04, 0x0a, 0x04], &mut context),
+            AmlValue::Boolean(false),
+            &[]
+        );
+        // LGreaterEqual(4, 4)
+        check_ok_value!(
+            expression_opcode().parse(&[0x92, 0x95, 0x0a, 0x04, 0x0a, 0x04], &mut context),
+            AmlValue::Boolean(true),
+            &[]
+        );
+
+        /*
+         * Operands that are fields are read, rather than rejected.
+         *
+         * Name(BUF, Buffer() { 0x00, 0x01 })
+         * CreateByteField(BUF, Zero, BYT0)
+         * CreateByteField(BUF, One, BYT1)
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x08, b'B', b'U', b'F', b'_', 0x11, 0x05, 0x0a, 0x02, 0x00, 0x01, 0x8c, b'B', b'U', b'F', b'_',
+                0x00, b'B', b'Y', b'T', b'0', 0x8c, b'B', b'U', b'F', b'_', 0x01, b'B', b'Y', b'T', b'1',
+            ]),
+            Ok(())
+        );
+        // LAnd(BYT1, One), LOr(BYT0, BYT0), and LNot(BYT0)
+        check_ok_value!(
+            expression_opcode().parse(&[0x90, b'B', b'Y', b'T', b'1', 0x01], &mut context),
+            AmlValue::Boolean(true),
+            &[]
+        );
+        check_ok_value!(
+            expression_opcode().parse(&[0x91, b'B', b'Y', b'T', b'0', b'B', b'Y', b'T', b'0'], &mut context),
+            AmlValue::Boolean(false),
+            &[]
+        );
+        check_ok_value!(
+            expression_opcode().parse(&[0x92, b'B', b'Y', b'T', b'0'], &mut context),
+            AmlValue::Boolean(true),
+            &[]
+        );
+        // LLess("ab", "b") and LGreater(Buffer() { 1, 2 }, Buffer() { 1 })
+        check_ok_value!(
+            expression_opcode().parse(&[0x95, 0x0d, b'a', b'b', 0x00, 0x0d, b'b', 0x00], &mut context),
+            AmlValue::Boolean(true),
+            &[]
+        );
+        check_ok_value!(
+            expression_opcode()
+                .parse(&[0x94, 0x11, 0x05, 0x0a, 0x02, 0x01, 0x02, 0x11, 0x04, 0x0a, 0x01, 0x01], &mut context),
+            AmlValue::Boolean(true),
+            &[]
+        );
+
+        /*
+         * Method(FOO, 1) {
+         *     Store(Arg0, Local0)
+         *     If (LGreater(Local0, 5)) { Return(One) }
+         *     Return(Zero)
+         * }
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x14, 0x13, b'F', b'O', b'O', b'_', 0x01, 0x70, 0x68, 0x60, 0xa0, 0x07, 0x94, 0x60, 0x0a, 0x05,
+                0xa4, 0x01, 0xa4, 0x00,
+            ]),
+            Ok(())
+        );
+        let mut invoke = |arg| {
+            context
+                .invoke_method(
+                    &AmlName::from_str("\\FOO").unwrap(),
+                    Args::from_list(vec![AmlValue::Integer(arg)]).unwrap(),
+                )
+                .unwrap()
+        };
+        assert!(crudely_cmp_values(&invoke(9), &AmlValue::Integer(1)));
+        assert!(crudely_cmp_values(&invoke(5), &AmlValue::Integer(0)));
+    }
+
+    #[test]
+    fn test_store_into_package_elements() {
+        let mut context = make_test_context();
+
+        /*
+         * Name(PKG, Package() { One, 2 })
+         * Store(5, Index(PKG, Zero))
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x08, b'P', b'K', b'G', b'_', 0x12, 0x05, 0x02, 0x01, 0x0a, 0x02, 0x70, 0x0a, 0x05, 0x88, b'P',
+                b'K', b'G', b'_', 0x00, 0x00,
+            ]),
+            Ok(())
+        );
+        assert!(crudely_cmp_values(
+            context.namespace.get_by_path(&AmlName::from_str("\\PKG").unwrap()).unwrap(),
+            &AmlValue::Package(vec![AmlValue::Integer(5), AmlValue::Integer(2)])
+        ));
+
+        // DerefOf(Index(PKG, Zero)) sees the stored element
+        check_ok_value!(
+            expression_opcode().parse(&[0x83, 0x88, b'P', b'K', b'G', b'_', 0x00, 0x00], &mut context),
+            AmlValue::Integer(5),
+            &[]
+        );
+    }
+
+    #[test]
+    fn test_mid_multibyte_strings() {
+        let mut context = make_test_context();
+
+        /*
+         * Method(MID, 2) {
+         *     Return(Mid(Arg0, One, Arg1))
+         * }
+         */
+        assert_eq!(
+            context.parse_table(&[0x14, 0x0c, b'M', b'I', b'D', b'_', 0x02, 0xa4, 0x9e, 0x68, 0x01, 0x69, 0x00]),
+            Ok(())
+        );
+        let mut mid = |string: &str, length| {
+            context.invoke_method(
+                &AmlName::from_str("\\MID").unwrap(),
+                Args::from_list(vec![AmlValue::String(String::from(string)), AmlValue::Integer(length)]).unwrap(),
+            )
+        };
+
+        // Indices and lengths are in bytes, so "é" is two bytes long, and can't be split
+        assert!(crudely_cmp_values(&mid("aéb", 2).unwrap(), &AmlValue::String(String::from("é"))));
+        assert!(crudely_cmp_values(&mid("aéb", 3).unwrap(), &AmlValue::String(String::from("éb"))));
+        assert!(matches!(mid("aéb", 1), Err(AmlError::TypeCannotBeSliced(AmlType::String))));
+    }
+
+    #[test]
+    fn test_index_and_deref_of() {
+        let mut context = make_test_context();
+
+        /*
+         * Name(PKG, Package() { 7, "hi", Package() { One } })
+         * Name(BUF, Buffer() { 0x10, 0x20, 0x30 })
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x08, b'P', b'K', b'G', b'_', 0x12, 0x0c, 0x03, 0x0a, 0x07, 0x0d, b'h', b'i', 0x00, 0x12, 0x03,
+                0x01, 0x01, 0x08, b'B', b'U', b'F', b'_', 0x11, 0x06, 0x0a, 0x03, 0x10, 0x20, 0x30,
+            ]),
+            Ok(())
+        );
+
+        // DerefOf(Index(PKG, Zero)) and DerefOf(Index(PKG, One))
+        check_ok_value!(
+            expression_opcode().parse(&[0x83, 0x88, b'P', b'K', b'G', b'_', 0x00, 0x00], &mut context),
+            AmlValue::Integer(7),
+            &[]
+        );
+        check_ok_value!(
+            expression_opcode().parse(&[0x83, 0x88, b'P', b'K', b'G', b'_', 0x01, 0x00], &mut context),
+            AmlValue::String(String::from("hi")),
+            &[]
+        );
+        // Index(PKG, 2) and DerefOf(Index(PKG, 2))
+        let package = context.namespace.get_handle(&AmlName::from_str("\\PKG").unwrap()).unwrap();
+        check_ok_value!(
+            expression_opcode().parse(&[0x88, b'P', b'K', b'G', b'_', 0x0a, 0x02, 0x00], &mut context),
+            AmlValue::PackageElement { package, index: 2 },
+            &[]
+        );
+        check_ok_value!(
+            expression_opcode().parse(&[0x83, 0x88, b'P', b'K', b'G', b'_', 0x0a, 0x02, 0x00], &mut context),
+            AmlValue::Package(vec![AmlValue::Integer(1)]),
+            &[]
+        );
+        // DerefOf(Index("hi", One))
+        check_ok_value!(
+            expression_opcode().parse(&[0x83, 0x88, 0x0d, b'h', b'i', 0x00, 0x01, 0x00], &mut context),
+            AmlValue::Integer(b'i' as u64),
+            &[]
+        );
+        // DerefOf(Index(BUF, 2)), and DerefOf("PKG")
+        check_ok_value!(
+            expression_opcode().parse(&[0x83, 0x88, b'B', b'U', b'F', b'_', 0x0a, 0x02, 0x00], &mut context),
+            AmlValue::Integer(0x30),
+            &[]
+        );
+        check_ok_value!(
+            expression_opcode().parse(&[0x83, 0x0d, b'B', b'U', b'F', 0x00], &mut context),
+            AmlValue::Buffer(Arc::new(spinning_top::Spinlock::new(vec![0x10, 0x20, 0x30]))),
+            &[]
+        );
+
+        // Index(PKG, 3) and Index(BUF, 3) are past the end
+        check_err!(
+            expression_opcode().parse(&[0x88, b'P', b'K', b'G', b'_', 0x0a, 0x03, 0x00], &mut context),
+            AmlError::IndexOutOfBounds,
+            &[0x88, b'P', b'K', b'G', b'_', 0x0a, 0x03, 0x00]
+        );
+        check_err!(
+            expression_opcode().parse(&[0x88, b'B', b'U', b'F', b'_', 0x0a, 0x03, 0x00], &mut context),
+            AmlError::IndexOutOfBounds,
+            &[0x88, b'B', b'U', b'F', b'_', 0x0a, 0x03, 0x00]
+        );
+        // Index(5, Zero) and DerefOf(5)
+        check_err!(
+            expression_opcode().parse(&[0x88, 0x0a, 0x05, 0x00, 0x00], &mut context),
+            AmlError::TypeCannotBeIndexed(AmlType::Integer),
+            &[0x88, 0x0a, 0x05, 0x00, 0x00]
+        );
+        check_err!(
+            expression_opcode().parse(&[0x83, 0x0a, 0x05], &mut context),
+            AmlError::TypeCannotBeDereferenced(AmlType::Integer),
+            &[0x83, 0x0a, 0x05]
+        );
+    }
+
+    #[test]
+    fn test_ref_of_and_size_of() {
+        let mut context = make_test_context();
+
+        /*
+         * Name(PKG, Package() { One, 2 })
+         * Name(BUF, Buffer() { 0x10, 0x20, 0x30 })
+         * Name(STR, "hello")
+         * Name(INT, 5)
+         * Method(FOO) {
+         *     If (CondRefOf(BUF, Local0)) { Return(SizeOf(Local0)) }
+         *     Return(Ones)
+         * }
+         * Method(BAR) {
+         *     If (CondRefOf(NOPE, Local0)) { Return(One) }
+         *     Return(Zero)
+         * }
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x08, b'P', b'K', b'G', b'_', 0x12, 0x05, 0x02, 0x01, 0x0a, 0x02, 0x08, b'B', b'U', b'F', b'_',
+                0x11, 0x06, 0x0a, 0x03, 0x10, 0x20, 0x30, 0x08, b'S', b'T', b'R', b'_', 0x0d, b'h', b'e', b'l',
+                b'l', b'o', 0x00, 0x08, b'I', b'N', b'T', b'_', 0x0a, 0x05, 0x14, 0x14, b'F', b'O', b'O', b'_',
+                0x00, 0xa0, 0x0b, 0x5b, 0x12, b'B', b'U', b'F', b'_', 0x60, 0xa4, 0x87, 0x60, 0xa4, 0xff, 0x14,
+                0x13, b'B', b'A', b'R', b'_', 0x00, 0xa0, 0x0a, 0x5b, 0x12, b'N', b'O', b'P', b'E', 0x60, 0xa4,
+                0x01, 0xa4, 0x00,
+            ]),
+            Ok(())
+        );
+
+        // DerefOf(RefOf(INT))
+        check_ok_value!(
+            expression_opcode().parse(&[0x83, 0x71, b'I', b'N', b'T', b'_'], &mut context),
+            AmlValue::Integer(5),
+            &[]
+        );
+        // CondRefOf(STR) and CondRefOf(NOPE)
+        check_ok_value!(
+            expression_opcode().parse(&[0x5b, 0x12, b'S', b'T', b'R', b'_', 0x00], &mut context),
+            AmlValue::Boolean(true),
+            &[]
+        );
+        check_ok_value!(
+            expression_opcode().parse(&[0x5b, 0x12, b'N', b'O', b'P', b'E', 0x00], &mut context),
+            AmlValue::Boolean(false),
+            &[]
+        );
+
+        // SizeOf(PKG), SizeOf(BUF), SizeOf(STR), and SizeOf(INT)
+        check_ok_value!(
+            expression_opcode().parse(&[0x87, b'P', b'K', b'G', b'_'], &mut context),
+            AmlValue::Integer(2),
+            &[]
+        );
+        check_ok_value!(
+            expression_opcode().parse(&[0x87, b'B', b'U', b'F', b'_'], &mut context),
+            AmlValue::Integer(3),
+            &[]
+        );
+        check_ok_value!(
+            expression_opcode().parse(&[0x87, b'S', b'T', b'R', b'_'], &mut context),
+            AmlValue::Integer(5),
+            &[]
+        );
+        check_err!(
+            expression_opcode().parse(&[0x87, b'I', b'N', b'T', b'_'], &mut context),
+            AmlError::TypeCannotBeSized(AmlType::Integer),
+            &[0x87, b'I', b'N', b'T', b'_']
+        );
+
+        let mut invoke = |name| context.invoke_method(&AmlName::from_str(name).unwrap(), Args::EMPTY).unwrap();
+        assert!(crudely_cmp_values(&invoke("\\FOO"), &AmlValue::Integer(3)));
+        assert!(crudely_cmp_values(&invoke("\\BAR"), &AmlValue::Integer(0)));
+    }
+
+    #[test]
+    fn test_type_conversions() {
+        let mut context = make_test_context();
+
+        // ToInteger("0x1f"), ToInteger("123"), and ToInteger(Buffer() { 1, 2, 3, 4, 5, 6, 7, 8, 9 })
+        check_ok_value!(
+            expression_opcode().parse(&[0x99, 0x0d, b'0', b'x', b'1', b'f', 0x00, 0x00], &mut context),
+            AmlValue::Integer(0x1f),
+            &[]
+        );
+        check_ok_value!(
+            expression_opcode().parse(&[0x99, 0x0d, b'1', b'2', b'3', 0x00, 0x00], &mut context),
+            AmlValue::Integer(123),
+            &[]
+        );
+        check_ok_value!(
+            expression_opcode().parse(
+                &[0x99, 0x11, 0x0c, 0x0a, 0x09, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x00],
+                &mut context
+            ),
+            AmlValue::Integer(0x0807060504030201),
+            &[]
+        );
+
+        // ToBuffer("ab"), ToBuffer(0x0102), and ToBuffer(Buffer() { 7 })
+        check_ok_value!(
+            expression_opcode().parse(&[0x96, 0x0d, b'a', b'b', 0x00, 0x00], &mut context),
+            AmlValue::Buffer(Arc::new(spinning_top::Spinlock::new(vec![b'a', b'b', 0x00]))),
+            &[]
+        );
+        check_ok_value!(
+            expression_opcode().parse(&[0x96, 0x0b, 0x02, 0x01, 0x00], &mut context),
+            AmlValue::Buffer(Arc::new(spinning_top::Spinlock::new(vec![0x02, 0x01, 0, 0, 0, 0, 0, 0]))),
+            &[]
+        );
+        check_ok_value!(
+            expression_opcode().parse(&[0x96, 0x11, 0x04, 0x0a, 0x01, 0x07, 0x00], &mut context),
+            AmlValue::Buffer(Arc::new(spinning_top::Spinlock::new(vec![0x07]))),
+            &[]
+        );
+
+        // ToDecimalString(1234) and ToDecimalString(Buffer() { 1, 0x20 })
+        check_ok_value!(
+            expression_opcode().parse(&[0x97, 0x0b, 0xd2, 0x04, 0x00], &mut context),
+            AmlValue::String(String::from("1234")),
+            &[]
+        );
+        check_ok_value!(
+            expression_opcode().parse(&[0x97, 0x11, 0x05, 0x0a, 0x02, 0x01, 0x20, 0x00], &mut context),
+            AmlValue::String(String::from("1,32")),
+            &[]
+        );
+
+        // ToHexString(0xabc) and ToHexString(Buffer() { 1, 0xab })
+        check_ok_value!(
+            expression_opcode().parse(&[0x98, 0x0b, 0xbc, 0x0a, 0x00], &mut context),
+            AmlValue::String(String::from("0000000000000ABC")),
+            &[]
+        );
+        check_ok_value!(
+            expression_opcode().parse(&[0x98, 0x11, 0x05, 0x0a, 0x02, 0x01, 0xab, 0x00], &mut context),
+            AmlValue::String(String::from("0x01,0xAB")),
+            &[]
+        );
+
+        // ToString(Buffer() { "abc", 0, "d" }, Ones), ToString(Buffer() { "abc" }, 2)
+        check_ok_value!(
+            expression_opcode()
+                .parse(&[0x9c, 0x11, 0x08, 0x0a, 0x05, b'a', b'b', b'c', 0x00, b'd', 0xff, 0x00], &mut context),
+            AmlValue::String(String::from("abc")),
+            &[]
+        );
+        check_ok_value!(
+            expression_opcode()
+                .parse(&[0x9c, 0x11, 0x06, 0x0a, 0x03, b'a', b'b', b'c', 0x0a, 0x02, 0x00], &mut context),
+            AmlValue::String(String::from("ab")),
+            &[]
+        );
+        // ToString(Buffer() { 0xc3, 0xa9 }, Ones) isn't ASCII, even though it's valid UTF-8
+        check_err!(
+            expression_opcode().parse(&[0x9c, 0x11, 0x05, 0x0a, 0x02, 0xc3, 0xa9, 0xff, 0x00], &mut context),
+            AmlError::IncompatibleValueConversion { current: AmlType::Buffer, target: AmlType::String },
+            &[0x9c, 0x11, 0x05, 0x0a, 0x02, 0xc3, 0xa9, 0xff, 0x00]
+        );
+
+        // ToBCD(1234), FromBCD(0x1234), and FromBCD(0x1a)
+        check_ok_value!(
+            expression_opcode().parse(&[0x5b, 0x29, 0x0b, 0xd2, 0x04, 0x00], &mut context),
+            AmlValue::Integer(0x1234),
+            &[]
+        );
+        check_ok_value!(
+            expression_opcode().parse(&[0x5b, 0x28, 0x0b, 0x34, 0x12, 0x00], &mut context),
+            AmlValue::Integer(1234),
+            &[]
+        );
+        check_err!(
+            expression_opcode().parse(&[0x5b, 0x28, 0x0a, 0x1a, 0x00], &mut context),
+            AmlError::InvalidBcdConversion(0x1a),
+            &[0x5b, 0x28, 0x0a, 0x1a, 0x00]
+        );
+        // ToBCD(Ones) doesn't fit
+        check_err!(
+            expression_opcode().parse(&[0x5b, 0x29, 0xff, 0x00], &mut context),
+            AmlError::InvalidBcdConversion(0xffff_ffff_ffff_ffff),
+            &[0x5b, 0x29, 0xff, 0x00]
+        );
+
+        // With 32-bit integers, ToBuffer(0x0102) produces 4 bytes, and ToHexString(0xabc) and ToHexString(Ones)
+        // produce 8 hex digits
+        context.interpreter_revision = 1;
+        check_ok_value!(
+            expression_opcode().parse(&[0x96, 0x0b, 0x02, 0x01, 0x00], &mut context),
+            AmlValue::Buffer(Arc::new(spinning_top::Spinlock::new(vec![0x02, 0x01, 0, 0]))),
+            &[]
+        );
+        check_ok_value!(
+            expression_opcode().parse(&[0x98, 0x0b, 0xbc, 0x0a, 0x00], &mut context),
+            AmlValue::String(String::from("00000ABC")),
+            &[]
+        );
+        check_ok_value!(
+            expression_opcode().parse(&[0x98, 0xff, 0x00], &mut context),
+            AmlValue::String(String::from("FFFFFFFF")),
+            &[]
+        );
+    }
+
+    #[test]
+    fn test_concat_and_mid() {
+        let mut context = make_test_context();
+
+        // Concatenate("abc", "de")
+        check_ok_value!(
+            expression_opcode()
+                .parse(&[0x73, 0x0d, b'a', b'b', b'c', 0x00, 0x0d, b'd', b'e', 0x00, 0x00], &mut context),
+            AmlValue::String(String::from("abcde")),
+            &[]
+        );
+        // Concatenate(Buffer() { 1, 2 }, Buffer() { 3 })
+        check_ok_value!(
+            expression_opcode().parse(
+                &[0x73, 0x11, 0x05, 0x0a, 0x02, 0x01, 0x02, 0x11, 0x04, 0x0a, 0x01, 0x03, 0x00],
+                &mut context
+            ),
+            AmlValue::Buffer(Arc::new(spinning_top::Spinlock::new(vec![0x01, 0x02, 0x03]))),
+            &[]
+        );
+        // Concatenate(0x0102, One)
+        check_ok_value!(
+            expression_opcode().parse(&[0x73, 0x0b, 0x02, 0x01, 0x01, 0x00], &mut context),
+            AmlValue::Buffer(Arc::new(spinning_top::Spinlock::new(vec![
+                0x02, 0x01, 0, 0, 0, 0, 0, 0, 0x01, 0, 0, 0, 0, 0, 0, 0
+            ]))),
+            &[]
+        );
+
+        // Mid("abcdef", 2, 3), Mid("abcdef", 4, Ones), and Mid("abcdef", 10, 2)
+        check_ok_value!(
+            expression_opcode().parse(
+                &[0x9e, 0x0d, b'a', b'b', b'c', b'd', b'e', b'f', 0x00, 0x0a, 0x02, 0x0a, 0x03, 0x00],
+                &mut context
+            ),
+            AmlValue::String(String::from("cde")),
+            &[]
+        );
+        check_ok_value!(
+            expression_opcode().parse(
+                &[0x9e, 0x0d, b'a', b'b', b'c', b'd', b'e', b'f', 0x00, 0x0a, 0x04, 0xff, 0x00],
+                &mut context
+            ),
+            AmlValue::String(String::from("ef")),
+            &[]
+        );
+        check_ok_value!(
+            expression_opcode().parse(
+                &[0x9e, 0x0d, b'a', b'b', b'c', b'd', b'e', b'f', 0x00, 0x0a, 0x0a, 0x0a, 0x02, 0x00],
+                &mut context
+            ),
+            AmlValue::String(String::new()),
+            &[]
+        );
+        // Mid(Buffer() { 1, 2, 3, 4 }, One, 2) and Mid(Buffer() { 1, 2, 3, 4 }, Ones, Ones)
+        check_ok_value!(
+            expression_opcode().parse(
+                &[0x9e, 0x11, 0x07, 0x0a, 0x04, 0x01, 0x02, 0x03, 0x04, 0x01, 0x0a, 0x02, 0x00],
+                &mut context
+            ),
+            AmlValue::Buffer(Arc::new(spinning_top::Spinlock::new(vec![0x02, 0x03]))),
+            &[]
+        );
+        check_ok_value!(
+            expression_opcode()
+                .parse(&[0x9e, 0x11, 0x07, 0x0a, 0x04, 0x01, 0x02, 0x03, 0x04, 0xff, 0xff, 0x00], &mut context),
+            AmlValue::Buffer(Arc::new(spinning_top::Spinlock::new(vec![]))),
+            &[]
+        );
+    }
+
+    #[test]
+    fn test_def_match() {
+        let mut context = make_test_context();
+
+        /*
+         * A `_PSS`-style table of core frequencies, in MHz.
+         *
+         * Name(FREQ, Package() { 3000, 2400, 1800, 1200, 800, Package() { Zero } })
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x08, b'F', b'R', b'E', b'Q', 0x12, 0x15, 0x06, 0x0b, 0xb8, 0x0b, 0x0b, 0x60, 0x09, 0x0b, 0x08,
+                0x07, 0x0b, 0xb0, 0x04, 0x0b, 0x20, 0x03, 0x12, 0x03, 0x01, 0x00,
+            ]),
+            Ok(())
+        );
+
+        // Match(FREQ, MLE, 2000, MTR, Zero, Zero)
+        check_ok_value!(
+            expression_opcode()
+                .parse(&[0x89, b'F', b'R', b'E', b'Q', 0x02, 0x0b, 0xd0, 0x07, 0x00, 0x00, 0x00], &mut context),
+            AmlValue::Integer(2),
+            &[]
+        );
+        // Match(FREQ, MLT, 2400, MGT, 1000, 3)
+        check_ok_value!(
+            expression_opcode().parse(
+                &[0x89, b'F', b'R', b'E', b'Q', 0x03, 0x0b, 0x60, 0x09, 0x05, 0x0b, 0xe8, 0x03, 0x0a, 0x03],
+                &mut context
+            ),
+            AmlValue::Integer(3),
+            &[]
+        );
+        // Match(FREQ, MEQ, 1000, MTR, Zero, Zero) doesn't match anything
+        check_ok_value!(
+            expression_opcode()
+                .parse(&[0x89, b'F', b'R', b'E', b'Q', 0x01, 0x0b, 0xe8, 0x03, 0x00, 0x00, 0x00], &mut context),
+            AmlValue::Integer(u64::max_value()),
+            &[]
+        );
+        // Match(FREQ, MTR, Zero, MTR, Zero, 5) matches the package, even though it can't be compared with Zero
+        check_ok_value!(
+            expression_opcode()
+                .parse(&[0x89, b'F', b'R', b'E', b'Q', 0x00, 0x00, 0x00, 0x00, 0x0a, 0x05], &mut context),
+            AmlValue::Integer(5),
+            &[]
+        );
+        // Match(FREQ, 6, Zero, MTR, Zero, Zero) uses an invalid match opcode
+        check_err!(
+            expression_opcode().parse(&[0x89, b'F', b'R', b'E', b'Q', 0x06, 0x00, 0x00, 0x00, 0x00], &mut context),
+            AmlError::InvalidMatchOpcode(6),
+            &[0x89, b'F', b'R', b'E', b'Q', 0x06, 0x00, 0x00, 0x00, 0x00]
+        );
+
+        // With 32-bit integers, Match(FREQ, MEQ, 1000, MTR, Zero, Zero) evaluates to a 32-bit Ones
+        context.interpreter_revision = 1;
+        check_ok_value!(
+            expression_opcode()
+                .parse(&[0x89, b'F', b'R', b'E', b'Q', 0x01, 0x0b, 0xe8, 0x03, 0x00, 0x00, 0x00], &mut context),
+            AmlValue::Integer(0xffff_ffff),
+            &[]
+        );
+    }
+
+    #[test]
+    fn test_def_timer() {
+        let (mut context, hardware) = make_recording_test_context();
+        hardware.lock().timer = 1000;
+
+        check_ok_value!(
+            expression_opcode().parse(&[0x5b, 0x33, 0xff], &mut context),
+            AmlValue::Integer(1000),
+            &[0xff]
+        );
+        check_ok_value!(expression_opcode().parse(&[0x5b, 0x33], &mut context), AmlValue::Integer(1010), &[]);
+
+        /*
+         * Method(WAIT, 0) {
+         *     Local0 = Timer
+         *     Local1 = Zero
+         *     While ((Timer - Local0) < 50) {
+         *         Local1++
+         *     }
+         *     Return (Local1)
+         * }
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x14, 0x1b, b'W', b'A', b'I', b'T', 0x00, 0x70, 0x5b, 0x33, 0x60, 0x70, 0x00, 0x61, 0xa2, 0x0b,
+                0x95, 0x74, 0x5b, 0x33, 0x60, 0x00, 0x0a, 0x32, 0x75, 0x61, 0xa4, 0x61,
+            ]),
+            Ok(())
+        );
+        assert!(crudely_cmp_values(
+            &context.invoke_method(&AmlName::from_str("\\WAIT").unwrap(), Args::default()).unwrap(),
+            &AmlValue::Integer(4)
+        ));
+    }
+
+    #[test]
+    fn test_copy_object_and_object_type() {
+        let mut context = make_test_context();
+
+        /*
+         * Name(INT, 5)
+         * Name(STR, "AB")
+         * Name(BUF, Buffer(2) { 1, 2 })
+         * Name(PKG, Package(1) { 1 })
+         * Device(DEV) { }
+         * Method(MTH, 0) { }
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x08, b'I', b'N', b'T', b'_', 0x0a, 0x05, 0x08, b'S', b'T', b'R', b'_', 0x0d, b'A', b'B', 0x00,
+                0x08, b'B', b'U', b'F', b'_', 0x11, 0x05, 0x0a, 0x02, 0x01, 0x02, 0x08, b'P', b'K', b'G', b'_',
+                0x12, 0x03, 0x01, 0x01, 0x5b, 0x82, 0x05, b'D', b'E', b'V', b'_', 0x14, 0x06, b'M', b'T', b'H',
+                b'_', 0x00,
+            ]),
+            Ok(())
+        );
+
+        let object_type = |context: &mut AmlContext, bytes: &[u8]| match expression_opcode().parse(bytes, context)
+        {
+            Ok((_, _, AmlValue::Integer(object_type))) => object_type,
+            _ => panic!("Failed to parse ObjectType"),
+        };
+        assert_eq!(object_type(&mut context, &[0x8e, b'I', b'N', b'T', b'_']), 1);
+        assert_eq!(object_type(&mut context, &[0x8e, b'S', b'T', b'R', b'_']), 2);
+        assert_eq!(object_type(&mut context, &[0x8e, b'B', b'U', b'F', b'_']), 3);
+        assert_eq!(object_type(&mut context, &[0x8e, b'P', b'K', b'G', b'_']), 4);
+        assert_eq!(object_type(&mut context, &[0x8e, b'D', b'E', b'V', b'_']), 6);
+        assert_eq!(object_type(&mut context, &[0x8e, b'M', b'T', b'H', b'_']), 8);
+        assert_eq!(object_type(&mut context, &[0x8e, 0x5b, 0x31]), 16);
+        // ObjectType(RefOf(BUF)) and ObjectType(Index(PKG, 0))
+        assert_eq!(object_type(&mut context, &[0x8e, 0x71, b'B', b'U', b'F', b'_']), 3);
+        assert_eq!(object_type(&mut context, &[0x8e, 0x88, b'P', b'K', b'G', b'_', 0x00, 0x00]), 1);
+
+        // CopyObject(BUF, INT) replaces the integer with a copy of the buffer, instead of converting it
+        check_ok_value!(
+            expression_opcode().parse(&[0x9d, b'B', b'U', b'F', b'_', b'I', b'N', b'T', b'_'], &mut context),
+            AmlValue::buffer(vec![0x01, 0x02]),
+            &[]
+        );
+        if let AmlValue::Buffer(bytes) =
+            context.namespace.get_by_path(&AmlName::from_str("\\BUF").unwrap()).unwrap()
+        {
+            bytes.lock()[0] = 0xff;
+        }
+        assert!(crudely_cmp_values(
+            context.namespace.get_by_path(&AmlName::from_str("\\INT").unwrap()).unwrap(),
+            &AmlValue::buffer(vec![0x01, 0x02])
+        ));
+    }
+
+    #[test]
+    fn test_find_set_bits() {
+        let mut context = make_test_context();
+
+        // FindSetLeftBit(Zero, ) and FindSetRightBit(Zero, )
+        check_ok_value!(expression_opcode().parse(&[0x81, 0x00, 0x00], &mut context), AmlValue::Integer(0), &[]);
+        check_ok_value!(expression_opcode().parse(&[0x82, 0x00, 0x00], &mut context), AmlValue::Integer(0), &[]);
+        // FindSetLeftBit(0x10, ) and FindSetRightBit(0x10, )
+        check_ok_value!(
+            expression_opcode().parse(&[0x81, 0x0a, 0x10, 0x00], &mut context),
+            AmlValue::Integer(5),
+            &[]
+        );
+        check_ok_value!(
+            expression_opcode().parse(&[0x82, 0x0a, 0x10, 0x00], &mut context),
+            AmlValue::Integer(5),
+            &[]
+        );
+        // FindSetLeftBit(0x0c, ) and FindSetRightBit(0x0c, )
+        check_ok_value!(
+            expression_opcode().parse(&[0x81, 0x0a, 0x0c, 0x00], &mut context),
+            AmlValue::Integer(4),
+            &[]
+        );
+        check_ok_value!(
+            expression_opcode().parse(&[0x82, 0x0a, 0x0c, 0x00], &mut context),
+            AmlValue::Integer(3),
+            &[]
+        );
+        // FindSetLeftBit(Ones, ) and FindSetRightBit(0x8000000000000000, )
+        check_ok_value!(expression_opcode().parse(&[0x81, 0xff, 0x00], &mut context), AmlValue::Integer(64), &[]);
+        check_ok_value!(
+            expression_opcode()
+                .parse(&[0x82, 0x0e, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x80, 0x00], &mut context),
+            AmlValue::Integer(64),
+            &[]
+        );
+        // FindSetLeftBit(0x80000000, )
+        check_ok_value!(
+            expression_opcode().parse(&[0x81, 0x0c, 0x00, 0x00, 0x00, 0x80, 0x00], &mut context),
+            AmlValue::Integer(32),
+            &[]
+        );
+
+        // With 32-bit integers, only the low 32 bits are searched
+        context.interpreter_revision = 1;
+        check_ok_value!(expression_opcode().parse(&[0x81, 0xff, 0x00], &mut context), AmlValue::Integer(32), &[]);
+        check_ok_value!(
+            expression_opcode()
+                .parse(&[0x82, 0x0e, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x80, 0x00], &mut context),
+            AmlValue::Integer(0),
+            &[]
+        );
+        check_ok_value!(
+            expression_opcode()
+                .parse(&[0x82, 0x0e, 0x00, 0x00, 0x00, 0x80, 0x01, 0x00, 0x00, 0x00, 0x00], &mut context),
+            AmlValue::Integer(32),
+            &[]
+        );
+    }
+
+    #[test]
+    fn test_integer_width() {
+        let mut context = make_test_context();
+
+        // Subtract(Zero, One, )
+        check_ok_value!(
+            expression_opcode().parse(&[0x74, 0x00, 0x01, 0x00], &mut context),
+            AmlValue::Integer(u64::MAX),
+            &[]
+        );
+
+        // With 32-bit integers, results are truncated to the low 32 bits
+        context.interpreter_revision = 1;
+        check_ok_value!(
+            expression_opcode().parse(&[0x74, 0x00, 0x01, 0x00], &mut context),
+            AmlValue::Integer(0xffff_ffff),
+            &[]
+        );
+        // Add(0xffffffff, One, )
+        check_ok_value!(
+            expression_opcode().parse(&[0x72, 0x0c, 0xff, 0xff, 0xff, 0xff, 0x01, 0x00], &mut context),
+            AmlValue::Integer(0),
+            &[]
+        );
+        // Add(Ones, Zero, )
+        check_ok_value!(
+            expression_opcode().parse(&[0x72, 0xff, 0x00, 0x00], &mut context),
+            AmlValue::Integer(0xffff_ffff),
+            &[]
+        );
+        // Multiply(0x10000, 0x10000, )
+        check_ok_value!(
+            expression_opcode()
+                .parse(&[0x77, 0x0c, 0x00, 0x00, 0x01, 0x00, 0x0c, 0x00, 0x00, 0x01, 0x00, 0x00], &mut context),
+            AmlValue::Integer(0),
+            &[]
+        );
+        // ShiftLeft(0x80000000, One, )
+        check_ok_value!(
+            expression_opcode().parse(&[0x79, 0x0c, 0x00, 0x00, 0x00, 0x80, 0x01, 0x00], &mut context),
+            AmlValue::Integer(0),
+            &[]
+        );
+        // Not(Zero, )
+        check_ok_value!(
+            expression_opcode().parse(&[0x80, 0x00, 0x00], &mut context),
+            AmlValue::Integer(0xffff_ffff),
+            &[]
+        );
+    }
+}
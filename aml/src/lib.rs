@@ -36,7 +36,7 @@
 //! combinator patterns to express the parse.
 
 #![no_std]
-#![feature(decl_macro, type_ascription, box_syntax, bool_to_option)]
+#![feature(decl_macro)]
 
 extern crate alloc;
 
@@ -61,19 +61,25 @@ pub mod value;
 
 pub use crate::{namespace::*, value::AmlValue};
 
-use alloc::{boxed::Box, string::ToString};
-use core::mem;
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::{fmt, mem, ops::Range};
 use log::{error, warn};
 use misc::{ArgNum, LocalNum};
 use name_object::Target;
 use parser::{Parser, Propagate};
 use pkg_length::PkgLength;
 use term_object::term_list;
-use value::{AmlType, Args};
+use value::{AmlType, Args, FieldAccessAttrib, GsbAttribute, RegionSpace, SmbusProtocol, StatusObject};
 
 /// AML has a `RevisionOp` operator that returns the "AML interpreter revision". It's not clear
-/// what this is actually used for, but this is ours.
-pub const AML_INTERPRETER_REVISION: u64 = 0;
+/// what this is actually used for, but this is ours. We support the 64-bit integers introduced in ACPI 2.0, so report
+/// a revision of `2`. This is the default value of [`AmlContext::interpreter_revision`].
+pub const AML_INTERPRETER_REVISION: u64 = 2;
 
 /// Describes how much debug information the parser should emit. Set the "maximum" expected verbosity in
 /// the context's `debug_verbosity` - everything will be printed that is less or equal in 'verbosity'.
@@ -109,6 +115,10 @@ impl MethodContext {
     }
 }
 
+/// The maximum number of control method invocations that can be nested inside each other. This stops AML that
+/// recurses forever (or just very deeply) from overflowing the stack.
+const MAX_METHOD_DEPTH: usize = 32;
+
 pub struct AmlContext {
     /// The `Handler` passed from the library user. This is stored as a boxed trait object simply to avoid having
     /// to add a lifetime and type parameter to `AmlContext`, as they would massively complicate the parser types.
@@ -116,6 +126,11 @@ pub struct AmlContext {
 
     pub namespace: Namespace,
     method_context: Option<MethodContext>,
+    /// How many control method invocations are currently in progress. This is limited to `MAX_METHOD_DEPTH`.
+    method_depth: usize,
+    /// The value that `RevisionOp` evaluates to. Some firmware branches on this, so it can be changed before any
+    /// tables are parsed if a different revision needs to be reported.
+    pub interpreter_revision: u64,
 
     /*
      * These track the state of the context while it's parsing an AML table.
@@ -123,6 +138,11 @@ pub struct AmlContext {
     current_scope: AmlName,
     scope_indent: usize,
     debug_verbosity: DebugVerbosity,
+    /// The addresses covered by the table being parsed, and the address of the innermost term in it that failed to
+    /// parse. Parsers hand back the input they were given when they fail, so the position of an error is lost by
+    /// the time it reaches `parse_table`, and has to be recorded as it happens instead.
+    table_bounds: Range<usize>,
+    failed_term: Option<usize>,
 }
 
 impl AmlContext {
@@ -133,41 +153,117 @@ impl AmlContext {
             handler,
             namespace: Namespace::new(),
             method_context: None,
+            method_depth: 0,
+            interpreter_revision: AML_INTERPRETER_REVISION,
 
             current_scope: AmlName::root(),
             scope_indent: 0,
             debug_verbosity,
+            table_bounds: 0..0,
+            failed_term: None,
         };
 
         context.add_predefined_objects();
         context
     }
 
-    pub fn parse_table(&mut self, stream: &[u8]) -> Result<(), AmlError> {
+    /// Parse a table of AML, such as the DSDT or an SSDT, into the namespace. `stream` should not include the
+    /// table's header. If parsing fails, the returned [`ParseError`] records the offset into `stream` of the term
+    /// that failed and the scope being parsed, to make broken tables easier to diagnose.
+    ///
+    /// This should be called for the DSDT first, and then for each SSDT. Each table is parsed into the same
+    /// namespace, so later tables can add objects to scopes created by earlier ones, define objects that earlier
+    /// tables declared with `External`, and redefine objects defined by earlier tables.
+    pub fn parse_table(&mut self, stream: &[u8]) -> Result<(), ParseError> {
         if stream.len() == 0 {
-            return Err(AmlError::UnexpectedEndOfStream);
+            return Err(ParseError {
+                offset: Some(0),
+                scope: self.current_scope.clone(),
+                error: AmlError::UnexpectedEndOfStream,
+            });
         }
 
+        self.namespace.begin_table();
+        self.table_bounds = (stream.as_ptr() as usize)..(stream.as_ptr() as usize + stream.len());
+        self.failed_term = None;
+
         let table_length = PkgLength::from_raw_length(stream, stream.len() as u32).unwrap();
         match term_object::term_list(table_length).parse(stream, self) {
             Ok(_) => Ok(()),
-            Err((_, _, Propagate::Err(err))) => {
-                error!("Failed to parse AML stream. Err = {:?}", err);
-                Err(err)
+            Err((_, context, Propagate::Err(err))) => {
+                let offset = context.failed_term.map(|address| address - context.table_bounds.start);
+                match offset {
+                    Some(offset) => error!(
+                        "Failed to parse AML stream at offset {:#x} (in scope {}): {}. Bytes at offset: {:x?}",
+                        offset,
+                        context.current_scope,
+                        err,
+                        &stream[offset..usize::min(offset + 8, stream.len())]
+                    ),
+                    None => error!("Failed to parse AML stream (in scope {}): {}", context.current_scope, err),
+                }
+                Err(ParseError { offset, scope: context.current_scope.clone(), error: err })
             }
-            Err((_, _, other)) => {
+            Err((_, context, other)) => {
                 error!("AML table evaluated to unexpected result: {:?}", other);
-                Err(AmlError::MalformedStream)
+                Err(ParseError {
+                    offset: None,
+                    scope: context.current_scope.clone(),
+                    error: AmlError::MalformedStream,
+                })
             }
         }
     }
 
-    // TODO: docs
+    /// Get the value of the object at `path` in the namespace, following any aliases. Relative paths are resolved
+    /// against the root of the namespace. Returns `None` if there is no such object.
+    pub fn lookup(&self, path: &AmlName) -> Option<&AmlValue> {
+        let path = path.resolve(&AmlName::root()).ok()?;
+        self.namespace.get_by_path(&path).ok()
+    }
+
+    /// Like [`AmlContext::lookup`], but gets a mutable reference to the value.
+    pub fn lookup_mut(&mut self, path: &AmlName) -> Option<&mut AmlValue> {
+        let path = path.resolve(&AmlName::root()).ok()?;
+        self.namespace.get_by_path_mut(&path).ok()
+    }
+
+    /// Read the field at `path`, which can be either a `Field` or an `IndexField`, using the handler to access the
+    /// region it's in. Relative paths are resolved against the root of the namespace. The value is read as an
+    /// `AmlValue::Integer` if it fits in one, or as an `AmlValue::Buffer` if not.
+    pub fn read_field(&mut self, path: &AmlName) -> Result<AmlValue, AmlError> {
+        let field = self.namespace.get_by_path(&path.resolve(&AmlName::root())?)?.clone();
+        match field {
+            AmlValue::IndexField { .. } => field.read_index_field(self),
+            _ => field.read_field(self),
+        }
+    }
+
+    /// Write `value` to the field at `path`, which can be either a `Field` or an `IndexField`. Bits of the field's
+    /// access units that aren't part of the field are written according to the field's update rule.
+    pub fn write_field(&mut self, path: &AmlName, value: AmlValue) -> Result<(), AmlError> {
+        let mut field = self.namespace.get_by_path(&path.resolve(&AmlName::root())?)?.clone();
+        match field {
+            AmlValue::IndexField { .. } => field.write_index_field(value, self),
+            _ => field.write_field(value, self),
+        }
+    }
+
+    /// Invoke the control method at `path`, passing it `args`, and return the value it returns. Each invocation gets
+    /// its own set of arguments and locals, so methods that invoke other methods (or themselves) resume correctly
+    /// when the inner invocation returns. Methods that don't explicitly return a value return `0`.
+    ///
+    /// If the object at `path` is not a method, its value is returned directly. This matches how AML allows objects
+    /// like `_STA` to be either a method or a plain value.
     pub fn invoke_method(&mut self, path: &AmlName, args: Args) -> Result<AmlValue, AmlError> {
         use value::MethodCode;
 
         match self.namespace.get_by_path(path)?.clone() {
             AmlValue::Method { flags, code } => {
+                if self.method_depth >= MAX_METHOD_DEPTH {
+                    return Err(AmlError::MethodNestingTooDeep);
+                }
+
                 /*
                  * First, set up the state we expect to enter the method with, but clearing local
                  * variables to "null" and setting the arguments. Save the current method state and scope, so if we're
@@ -180,6 +276,7 @@ impl AmlContext {
                  * Create a namespace level to store local objects created by the invocation.
                  */
                 self.namespace.add_level(path.clone(), LevelType::MethodLocals)?;
+                self.method_depth += 1;
 
                 let return_value = match code {
                     MethodCode::Aml(ref code) => {
@@ -213,17 +310,32 @@ impl AmlContext {
                  */
                 // TODO: this should also remove objects created by the method outside the method's scope, if they
                 // weren't statically created. This is harder.
-                self.namespace.remove_level(path.clone())?;
+                let removed_level = self.namespace.remove_level(path.clone());
 
                 /*
                  * Restore the old state.
                  */
                 self.method_context = old_context;
                 self.current_scope = old_scope;
+                self.method_depth -= 1;
+
+                /*
+                 * If the method invoked itself, the innermost invocation has already removed the level.
+                 */
+                match removed_level {
+                    Ok(()) | Err(AmlError::LevelDoesNotExist(_)) => (),
+                    Err(err) => return Err(err),
+                }
 
                 return_value
             }
 
+            /*
+             * An `External` is only a placeholder for an object that another table should have defined. If it
+             * hasn't been replaced, the method doesn't exist, even though the name does.
+             */
+            AmlValue::External { .. } => Err(AmlError::ValueDoesNotExist(path.clone())),
+
             /*
              * AML can encode methods that don't require any computation simply as the value that would otherwise be
              * returned (e.g. a `_STA` object simply being an `AmlValue::Integer`, instead of a method that just
@@ -233,10 +345,24 @@ impl AmlContext {
         }
     }
 
+    /// Invoke the control method at `path`, resolved relative to `scope` in the same way as names in AML are: `^`
+    /// prefixes move up from `scope`, and absolute paths ignore it. Unlike names in AML, search rules are not applied,
+    /// so objects like a device's `_STA` aren't found in its parents. A relative `scope` is resolved against the
+    /// root of the namespace. Otherwise, this behaves like [`invoke_method`](AmlContext::invoke_method).
+    pub fn invoke_method_relative(
+        &mut self,
+        scope: &AmlName,
+        path: &AmlName,
+        args: Args,
+    ) -> Result<AmlValue, AmlError> {
+        let scope = scope.resolve(&AmlName::root())?;
+        let path = path.resolve(&scope)?;
+        self.invoke_method(&path, args)
+    }
+
     // TODO: docs
     pub fn initialize_objects(&mut self) -> Result<(), AmlError> {
         use name_object::NameSeg;
-        use value::StatusObject;
 
         /*
          * If `\_SB._INI` exists, we unconditionally execute it at the beginning of device initialization.
@@ -258,12 +384,7 @@ impl AmlContext {
          */
         self.namespace.clone().traverse(|path, level: &NamespaceLevel| match level.typ {
             LevelType::Device => {
-                let status = if level.values.contains_key(&NameSeg::from_str("_STA").unwrap()) {
-                    self.invoke_method(&AmlName::from_str("_STA").unwrap().resolve(&path)?, Args::default())?
-                        .as_status()?
-                } else {
-                    StatusObject::default()
-                };
+                let status = self.device_status(&path)?;
 
                 /*
                  * If the device is present and has an `_INI` method, invoke it.
@@ -291,16 +412,115 @@ impl AmlContext {
         Ok(())
     }
 
-    pub(crate) fn read_target(&self, target: &Target) -> Result<&AmlValue, AmlError> {
+    /// Get the status of the device at `path` by evaluating its `_STA` object. Relative paths are resolved against
+    /// the root of the namespace. Devices that don't have a `_STA` object are assumed to be present, enabled, and
+    /// functioning (§6.3.7 of the ACPI spec), so `StatusObject::default()` is returned for them.
+    pub fn device_status(&mut self, path: &AmlName) -> Result<StatusObject, AmlError> {
+        let path = path.resolve(&AmlName::root())?;
+        // Make sure the device itself exists, so we don't report missing devices as present
+        self.namespace.get_by_path(&path)?;
+
+        let sta_path = AmlName::from_str("_STA").unwrap().resolve(&path)?;
+        match self.namespace.get_by_path(&sta_path) {
+            Ok(_) => {
+                // `_STA` can return anything that converts to an integer, not just an `Integer` itself
+                let status = self.invoke_method(&sta_path, Args::default())?.as_integer(self)?;
+                AmlValue::Integer(status).as_status()
+            }
+            Err(AmlError::ValueDoesNotExist(_)) => Ok(StatusObject::default()),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Find every device whose hardware ID (`_HID`) or one of whose compatible IDs (`_CID`) is `id`, such as
+    /// `PNP0A03` for PCI host bridges. IDs can be encoded as strings or as compressed EISA IDs, and can be returned
+    /// from control methods. Returns the absolute paths of the matching devices, in order of their paths. Devices
+    /// whose IDs can't be evaluated are logged and skipped, so a single broken device doesn't hide the others.
+    pub fn find_devices(&mut self, id: &str) -> Result<Vec<AmlName>, AmlError> {
+        fn matches_id(value: &AmlValue, id: &str) -> bool {
+            match value {
+                // `_CID` can also be a package of IDs
+                AmlValue::Package(ids) => ids.iter().any(|value| matches_id(value, id)),
+                _ => value.as_eisa_id().as_deref() == Some(id),
+            }
+        }
+
+        let devices: Vec<AmlName> = self
+            .namespace
+            .iter()
+            .filter_map(|(path, value)| if let AmlValue::Device = value { Some(path) } else { None })
+            .collect();
+
+        let mut matching = Vec::new();
+        for device in devices {
+            for object in &["_HID", "_CID"] {
+                match self.invoke_method(&AmlName::from_str(object).unwrap().resolve(&device)?, Args::EMPTY) {
+                    Ok(value) if matches_id(&value, id) => {
+                        matching.push(device.clone());
+                        break;
+                    }
+                    Ok(_) | Err(AmlError::ValueDoesNotExist(_)) => (),
+                    Err(err) => {
+                        warn!("Failed to evaluate {} of device {}: {:?}. Skipping it.", object, device, err);
+                        break;
+                    }
+                }
+            }
+        }
+
+        Ok(matching)
+    }
+
+    pub(crate) fn read_target<'a>(&'a self, target: &'a Target) -> Result<&'a AmlValue, AmlError> {
         match target {
-            Target::Null => todo!(),
+            Target::Null | Target::Debug => Err(AmlError::TargetCannotBeRead),
             Target::Name(name) => {
                 let (_, handle) = self.namespace.search(name, &self.current_scope)?;
                 self.namespace.get(handle)
             }
-            Target::Debug => todo!(),
             Target::Arg(arg) => self.current_arg(*arg),
             Target::Local(local) => self.local(*local),
+            Target::Reference(reference) => match reference {
+                AmlValue::Reference(object) => Ok(object),
+                AmlValue::NamedReference(object) => self.namespace.get(*object),
+                AmlValue::PackageElement { package, index } => self.package_element(*package, *index),
+                AmlValue::BufferField { .. } => Ok(reference),
+                AmlValue::String(name) => {
+                    let (_, handle) = self.namespace.search(&AmlName::from_str(name)?, &self.current_scope)?;
+                    self.namespace.get(handle)
+                }
+                _ => Err(AmlError::TypeCannotBeDereferenced(reference.type_of())),
+            },
+        }
+    }
+
+    /// Record that the term starting at `input` failed to parse, if it's part of the table being parsed and no term
+    /// inside it has already failed.
+    pub(crate) fn record_failed_term(&mut self, input: &[u8]) {
+        let address = input.as_ptr() as usize;
+        if self.failed_term.is_none() && self.table_bounds.contains(&address) {
+            self.failed_term = Some(address);
+        }
+    }
+
+    /// Get the element of the named package `package` at `index`, which is what a `PackageElement` refers to.
+    pub(crate) fn package_element(&self, package: AmlHandle, index: usize) -> Result<&AmlValue, AmlError> {
+        match self.namespace.get(package)? {
+            AmlValue::Package(elements) => elements.get(index).ok_or(AmlError::IndexOutOfBounds),
+            object => Err(AmlError::TypeCannotBeIndexed(object.type_of())),
+        }
+    }
+
+    /// Follow `value`, if it's a reference, to the object it refers to. References to references are followed
+    /// until an object that isn't a reference is reached.
+    pub(crate) fn dereference<'a>(&'a self, mut value: &'a AmlValue) -> Result<&'a AmlValue, AmlError> {
+        loop {
+            value = match value {
+                AmlValue::Reference(object) => object,
+                AmlValue::NamedReference(object) => self.namespace.get(*object)?,
+                AmlValue::PackageElement { package, index } => self.package_element(*package, *index)?,
+                _ => return Ok(value),
+            };
         }
     }
 
@@ -323,6 +543,22 @@ impl AmlContext {
             .ok_or(AmlError::InvalidLocalAccess(local))
     }
 
+    /// Make a reference to the object in `target`, as `RefOf` does. Named objects are referred to in place, and
+    /// references are made to copies of other objects.
+    pub(crate) fn reference_to(&self, target: &Target) -> Result<AmlValue, AmlError> {
+        match target {
+            Target::Name(name) => {
+                let (_, handle) = self.namespace.search(name, &self.current_scope)?;
+                Ok(AmlValue::NamedReference(handle))
+            }
+            // A reference to the object referred to by a reference already in place is that reference
+            Target::Reference(reference @ (AmlValue::NamedReference(_) | AmlValue::PackageElement { .. })) => {
+                Ok(reference.clone())
+            }
+            _ => Ok(AmlValue::Reference(Box::new(self.read_target(target)?.clone()))),
+        }
+    }
+
     /// Perform a store into a `Target`, according to the rules specified by §19.3.5.8. This returns a value read
     /// out of the target, if neccessary, as values can be altered during a store in some circumstances.  When
     /// required, this also performs required implicit conversions, otherwise stores are semantically equivalent to
@@ -331,28 +567,11 @@ impl AmlContext {
         match target {
             Target::Name(ref path) => {
                 let (_, handle) = self.namespace.search(path, &self.current_scope)?;
-
-                match self.namespace.get(handle).unwrap().type_of() {
-                    AmlType::FieldUnit => {
-                        let mut field = self.namespace.get(handle).unwrap().clone();
-                        field.write_field(value, self)?;
-                        field.read_field(self)
-                    }
-                    AmlType::BufferField => {
-                        let mut buffer_field = self.namespace.get(handle).unwrap().clone();
-                        buffer_field.write_buffer_field(value.clone(), self)?;
-                        Ok(value)
-                    }
-                    typ => {
-                        *self.namespace.get_mut(handle)? = value.as_type(typ, self)?;
-                        Ok(self.namespace.get(handle)?.clone())
-                    }
-                }
+                self.store_into_object(handle, value)
             }
-
             Target::Debug => {
-                // TODO
-                unimplemented!()
+                self.handler.debug_output(&value);
+                Ok(value)
             }
 
             Target::Arg(arg_num) => {
@@ -383,16 +602,93 @@ impl AmlContext {
                 Ok(value)
             }
 
+            Target::Reference(reference) => match reference {
+                AmlValue::BufferField { .. } => {
+                    let mut buffer_field = reference;
+                    buffer_field.write_buffer_field(value.clone(), self)?;
+                    Ok(value)
+                }
+                AmlValue::String(ref name) => self.store(Target::Name(AmlName::from_str(name)?), value),
+                AmlValue::PackageElement { package, index } => {
+                    // Stores into package elements replace the element, without any implicit conversion
+                    match self.namespace.get_mut(package)? {
+                        AmlValue::Package(elements) => {
+                            *elements.get_mut(index).ok_or(AmlError::IndexOutOfBounds)? = value.clone()
+                        }
+                        object => return Err(AmlError::TypeCannotBeIndexed(object.type_of())),
+                    }
+                    Ok(value)
+                }
+                AmlValue::NamedReference(object) => self.store_into_object(object, value),
+                // Other references refer to a copy of the object, so stores through them can't be made
+                AmlValue::Reference(_) => Err(AmlError::TargetCannotBeWritten),
+                _ => Err(AmlError::TypeCannotBeDereferenced(reference.type_of())),
+            },
+
             Target::Null => Ok(value),
         }
     }
 
+    /// Store `value` into the named object `handle`, applying implicit conversion to the type of the object.
+    fn store_into_object(&mut self, handle: AmlHandle, value: AmlValue) -> Result<AmlValue, AmlError> {
+        match self.namespace.get(handle)?.type_of() {
+            AmlType::FieldUnit => {
+                let mut field = self.namespace.get(handle).unwrap().clone();
+                if let AmlValue::IndexField { .. } = field {
+                    field.write_index_field(value, self)?;
+                    field.read_index_field(self)
+                } else if field.is_serial_bus_field(self)? {
+                    /*
+                     * Writes to fields in serial bus regions are transactions with a device, and the
+                     * result of the store is the buffer produced by the transaction, instead of the new
+                     * value of the field.
+                     */
+                    field.serial_bus_transaction(Some(value), self)
+                } else {
+                    field.write_field(value, self)?;
+                    field.read_field(self)
+                }
+            }
+            AmlType::BufferField => {
+                let mut buffer_field = self.namespace.get(handle).unwrap().clone();
+                buffer_field.write_buffer_field(value.clone(), self)?;
+                Ok(value)
+            }
+            typ => {
+                *self.namespace.get_mut(handle)? = value.as_type(typ, self)?;
+                Ok(self.namespace.get(handle)?.clone())
+            }
+        }
+    }
+
+    /// Perform a `CopyObject` into a `Target`. This replaces the object in the target with `value`, without the
+    /// implicit conversions performed by [`store`](AmlContext::store). Copies into field units and buffer fields
+    /// still write to the field, as they can't be replaced.
+    pub(crate) fn copy_object(&mut self, target: Target, value: AmlValue) -> Result<AmlValue, AmlError> {
+        match target {
+            Target::Name(ref path) => {
+                let (_, handle) = self.namespace.search(path, &self.current_scope)?;
+
+                match self.namespace.get(handle)?.type_of() {
+                    AmlType::FieldUnit | AmlType::BufferField => self.store(target, value),
+                    _ => {
+                        *self.namespace.get_mut(handle)? = value.clone();
+                        Ok(value)
+                    }
+                }
+            }
+
+            // Stores into other targets are already copies
+            _ => self.store(target, value),
+        }
+    }
+
     /// Read from an operation-region, performing only standard-sized reads (supported powers-of-2 only. If a field
-    /// is not one of these sizes, it may need to be masked, or multiple reads may need to be performed).
+    /// is not one of these sizes, it may need to be masked, or multiple reads may need to be performed). `offset`
+    /// is the offset into the region in bytes, and `length` is the size of the read in bits.
     pub(crate) fn read_region(&self, region_handle: AmlHandle, offset: u64, length: u64) -> Result<u64, AmlError> {
         use bit_field::BitField;
         use core::convert::TryInto;
-        use value::RegionSpace;
 
         let (region_space, region_base, region_length, parent_device) = {
             if let AmlValue::OpRegion { region, offset, length, parent_device } =
@@ -470,11 +766,46 @@ impl AmlContext {
                 }
             }
 
-            // TODO
-            _ => unimplemented!(),
+            RegionSpace::EmbeddedControl => {
+                /*
+                 * The embedded controller can only be accessed a byte at a time, so larger reads are made up of
+                 * reads of consecutive bytes, least significant first.
+                 */
+                if length > 64 {
+                    return Err(AmlError::FieldInvalidAccessSize);
+                }
+
+                let mut value = 0;
+                for i in 0..(length / 8) {
+                    let address: u8 =
+                        (region_base + offset + i).try_into().map_err(|_| AmlError::FieldInvalidAddress)?;
+                    value.set_bits(
+                        (i as usize * 8)..((i as usize + 1) * 8),
+                        self.handler.read_ec_u8(address)? as u64,
+                    );
+                }
+                Ok(value)
+            }
+
+            RegionSpace::SystemCmos => {
+                let index = (region_base + offset).try_into().map_err(|_| AmlError::FieldInvalidAddress)?;
+                match length {
+                    8 => Ok(self.handler.read_cmos_u8(index)? as u64),
+                    _ => Err(AmlError::FieldInvalidAccessSize),
+                }
+            }
+
+            RegionSpace::OemDefined(space) => match length {
+                8 | 16 | 32 | 64 => self.handler.read_oem_region(*space, region_base + offset, length as u8),
+                _ => Err(AmlError::FieldInvalidAccessSize),
+            },
+
+            space => Err(AmlError::UnsupportedRegionSpace(*space)),
         }
     }
 
+    /// Write to an operation-region, performing only standard-sized writes. Like `read_region`, `offset` is the
+    /// offset into the region in bytes, and `length` is the size of the write in bits.
     pub(crate) fn write_region(
         &mut self,
         region_handle: AmlHandle,
@@ -484,7 +815,6 @@ impl AmlContext {
     ) -> Result<(), AmlError> {
         use bit_field::BitField;
         use core::convert::TryInto;
-        use value::RegionSpace;
 
         let (region_space, region_base, region_length, parent_device) = {
             if let AmlValue::OpRegion { region, offset, length, parent_device } =
@@ -562,11 +892,165 @@ impl AmlContext {
                 }
             }
 
-            // TODO
-            _ => unimplemented!(),
+            RegionSpace::EmbeddedControl => {
+                if length > 64 {
+                    return Err(AmlError::FieldInvalidAccessSize);
+                }
+
+                for i in 0..(length / 8) {
+                    let address: u8 =
+                        (region_base + offset + i).try_into().map_err(|_| AmlError::FieldInvalidAddress)?;
+                    self.handler
+                        .write_ec_u8(address, value.get_bits((i as usize * 8)..((i as usize + 1) * 8)) as u8)?;
+                }
+                Ok(())
+            }
+
+            RegionSpace::SystemCmos => {
+                let index = (region_base + offset).try_into().map_err(|_| AmlError::FieldInvalidAddress)?;
+                match length {
+                    8 => self.handler.write_cmos_u8(index, value as u8),
+                    _ => Err(AmlError::FieldInvalidAccessSize),
+                }
+            }
+
+            RegionSpace::OemDefined(space) => match length {
+                8 | 16 | 32 | 64 => {
+                    self.handler.write_oem_region(*space, region_base + offset, length as u8, value)
+                }
+                _ => Err(AmlError::FieldInvalidAccessSize),
+            },
+
+            space => Err(AmlError::UnsupportedRegionSpace(*space)),
         }
     }
 
+    /// Perform a transaction on a serial bus operation-region (see [`RegionSpace::is_serial_bus`]). `offset` is
+    /// the offset of the field into the region in bytes, which selects the command sent to the device. The
+    /// transaction writes `write_data` to the device, or reads from it if it's `None`. Returns the transaction
+    /// buffer, in the layout defined by the spec for the region space.
+    pub(crate) fn serial_bus_transaction(
+        &self,
+        region_handle: AmlHandle,
+        offset: u64,
+        access_attrib: Option<FieldAccessAttrib>,
+        connection: Option<&[u8]>,
+        write_data: Option<&[u8]>,
+    ) -> Result<Vec<u8>, AmlError> {
+        use core::convert::TryInto;
+
+        let (region_space, region_base) = {
+            if let AmlValue::OpRegion { region, offset, .. } = self.namespace.get(region_handle)? {
+                (region, offset)
+            } else {
+                return Err(AmlError::FieldRegionIsNotOpRegion);
+            }
+        };
+
+        match region_space {
+            RegionSpace::SMBus => {
+                /*
+                 * The address of an SMBus field is made up of the address of the device, in the upper byte, and
+                 * the command, in the lower byte. Transactions use a 34-byte buffer: a status byte, a length
+                 * byte, and up to 32 bytes of data.
+                 */
+                let protocol = access_attrib
+                    .and_then(|access_attrib| {
+                        SmbusProtocol::from_access_attrib(access_attrib, write_data.is_some())
+                    })
+                    .ok_or(AmlError::InvalidSmbusProtocol)?;
+                let address: u16 = (region_base + offset).try_into().map_err(|_| AmlError::FieldInvalidAddress)?;
+
+                let mut buffer = vec![0; 34];
+                if let Some(data) = write_data {
+                    let length = usize::min(data.len(), buffer.len());
+                    buffer[..length].copy_from_slice(&data[..length]);
+                }
+
+                self.handler.smbus_transaction(protocol, (address >> 8) as u8, address as u8, &mut buffer)?;
+                Ok(buffer)
+            }
+
+            RegionSpace::GenericSerialBus => {
+                /*
+                 * The buffer of a `GenericSerialBus` transaction is a status byte and a length byte, followed by
+                 * the data, the size of which depends on the protocol used.
+                 */
+                let protocol = access_attrib.ok_or(AmlError::FieldInvalidAccessSize)?;
+                let connection = connection.ok_or(AmlError::FieldMissingConnection)?;
+                let command = (region_base + offset).try_into().map_err(|_| AmlError::FieldInvalidAddress)?;
+
+                let mut buffer = vec![0; 2 + protocol.gsb_data_length()];
+                if let Some(data) = write_data {
+                    let length = usize::min(data.len(), buffer.len());
+                    buffer[..length].copy_from_slice(&data[..length]);
+                }
+
+                let attribute = GsbAttribute { protocol, write: write_data.is_some() };
+                self.handler.gsb_transaction(connection, attribute, command, &mut buffer)?;
+                Ok(buffer)
+            }
+
+            RegionSpace::IPMI => {
+                /*
+                 * The address of an IPMI field is made up of the network function, in the upper byte, and the
+                 * command, in the lower byte. Transactions use a 66-byte buffer: a status byte, a length byte,
+                 * and up to 64 bytes of data. The request is the data of the buffer written to the field, and the
+                 * handler fills in the buffer of the response.
+                 */
+                let address: u16 = (region_base + offset).try_into().map_err(|_| AmlError::FieldInvalidAddress)?;
+                let (network_function, command) = ((address >> 8) as u8, address as u8);
+
+                let request = match write_data {
+                    Some(data) if data.len() >= 2 => {
+                        let length = usize::min(data[1] as usize, usize::min(data.len() - 2, 64));
+                        &data[2..(2 + length)]
+                    }
+                    _ => &[],
+                };
+                let mut response = vec![0; 66];
+                self.handler.ipmi_transaction(network_function, command, request, &mut response)?;
+                Ok(response)
+            }
+
+            space => Err(AmlError::UnsupportedRegionSpace(*space)),
+        }
+    }
+
+    /// Read the pins of a `GeneralPurposeIo` connection covered by a field. Each bit of the field is a pin, so
+    /// `offset` is the index of the first pin into the connection's pin list, and `length` is the number of pins.
+    pub(crate) fn read_gpio(&self, connection: Option<&[u8]>, offset: u64, length: u64) -> Result<u64, AmlError> {
+        use core::convert::TryInto;
+
+        let connection = connection.ok_or(AmlError::FieldMissingConnection)?;
+        if length > 64 {
+            return Err(AmlError::FieldInvalidAccessSize);
+        }
+        let pin_offset = offset.try_into().map_err(|_| AmlError::FieldInvalidAddress)?;
+
+        self.handler.read_gpio(connection, pin_offset, length as u16)
+    }
+
+    /// Write to the pins of a `GeneralPurposeIo` connection covered by a field. Like `read_gpio`, `offset` and
+    /// `length` are the index of the first pin and the number of pins.
+    pub(crate) fn write_gpio(
+        &self,
+        connection: Option<&[u8]>,
+        offset: u64,
+        length: u64,
+        value: u64,
+    ) -> Result<(), AmlError> {
+        use core::convert::TryInto;
+
+        let connection = connection.ok_or(AmlError::FieldMissingConnection)?;
+        if length > 64 {
+            return Err(AmlError::FieldInvalidAccessSize);
+        }
+        let pin_offset = offset.try_into().map_err(|_| AmlError::FieldInvalidAddress)?;
+
+        self.handler.write_gpio(connection, pin_offset, length as u16, value)
+    }
+
     fn add_predefined_objects(&mut self) {
         /*
          * These are the scopes predefined by the spec. Some tables will try to access them without defining them
@@ -659,10 +1143,66 @@ impl AmlContext {
          * useless and deprecated (this is mirrored in newer specs, which claim `2` means "ACPI 2 or greater").
          */
         self.namespace.add_value(AmlName::from_str("\\_REV").unwrap(), AmlValue::Integer(2)).unwrap();
+
+        /*
+         * `\_GL` is the Global Lock, which synchronizes access to hardware that's shared between the OS and the
+         * firmware. It's acquired and released like any other mutex, so the `Handler` must recognise it by its path.
+         */
+        self.namespace.add_value(AmlName::from_str("\\_GL").unwrap(), AmlValue::Mutex { sync_level: 0 }).unwrap();
+    }
+
+    /// Resolve the `MutexObject` of a `DefAcquire` or `DefRelease` to the absolute path of the mutex.
+    pub(crate) fn resolve_mutex(&self, mutex: Target) -> Result<AmlName, AmlError> {
+        let path = match mutex {
+            Target::Name(ref name) => self.namespace.search(name, &self.current_scope)?.0,
+            _ => return Err(AmlError::NotAMutex),
+        };
+
+        match self.namespace.get_by_path(&path)? {
+            AmlValue::Mutex { .. } => Ok(path),
+            _ => Err(AmlError::NotAMutex),
+        }
+    }
+
+    /// Resolve the `EventObject` of a `DefSignal`, `DefWait`, or `DefReset` to the absolute path of the event.
+    pub(crate) fn resolve_event(&self, event: Target) -> Result<AmlName, AmlError> {
+        let path = match event {
+            Target::Name(ref name) => self.namespace.search(name, &self.current_scope)?.0,
+            _ => return Err(AmlError::NotAnEvent),
+        };
+
+        match self.namespace.get_by_path(&path)? {
+            AmlValue::Event => Ok(path),
+            _ => Err(AmlError::NotAnEvent),
+        }
+    }
+
+    /// The width of integers, in bits. Integers were extended from 32 to 64 bits in ACPI 2.0, so this is `32` if
+    /// `interpreter_revision` is set to a revision before that, and `64` otherwise.
+    pub(crate) fn integer_width(&self) -> u32 {
+        if self.interpreter_revision < 2 {
+            32
+        } else {
+            64
+        }
+    }
+
+    /// Truncate `value` to the width of integers (see [`AmlContext::integer_width`]). This should be applied to
+    /// the results of operations that can set bits above the width of integers.
+    pub(crate) fn truncate_integer(&self, value: u64) -> u64 {
+        if self.integer_width() == 32 {
+            value & 0xffff_ffff
+        } else {
+            value
+        }
     }
 }
 
 /// Trait type used by [`AmlContext`] to handle reading and writing to various types of memory in the system.
+///
+/// Methods for accessing region spaces that not every system has (e.g. the embedded controller) have default
+/// implementations that return [`AmlError::UnsupportedRegionSpace`], so handlers only need to implement the ones
+/// their platform supports.
 pub trait Handler: Send + Sync {
     fn read_u8(&self, address: usize) -> u8;
     fn read_u16(&self, address: usize) -> u16;
@@ -690,11 +1230,250 @@ pub trait Handler: Send + Sync {
     fn write_pci_u16(&self, segment: u16, bus: u8, device: u8, function: u8, offset: u16, value: u16);
     fn write_pci_u32(&self, segment: u16, bus: u8, device: u8, function: u8, offset: u16, value: u32);
 
+    /// Read the byte at `address` in the address space of the embedded controller, using the EC's command and data
+    /// registers. This is used to access fields in `EmbeddedControl` operation regions, which are always accessed a
+    /// byte at a time.
+    fn read_ec_u8(&self, _address: u8) -> Result<u8, AmlError> {
+        Err(AmlError::UnsupportedRegionSpace(RegionSpace::EmbeddedControl))
+    }
+
+    /// Write to the byte at `address` in the address space of the embedded controller.
+    fn write_ec_u8(&self, _address: u8, _value: u8) -> Result<(), AmlError> {
+        Err(AmlError::UnsupportedRegionSpace(RegionSpace::EmbeddedControl))
+    }
+
+    /// Read the byte at `index` in the CMOS (e.g. the RTC's registers and NVRAM), which is usually accessed through
+    /// an index/data pair of IO ports. This is used to access fields in `SystemCmos` operation regions.
+    fn read_cmos_u8(&self, _index: u8) -> Result<u8, AmlError> {
+        Err(AmlError::UnsupportedRegionSpace(RegionSpace::SystemCmos))
+    }
+
+    /// Write to the byte at `index` in the CMOS.
+    fn write_cmos_u8(&self, _index: u8, _value: u8) -> Result<(), AmlError> {
+        Err(AmlError::UnsupportedRegionSpace(RegionSpace::SystemCmos))
+    }
+
+    /// Read a `width`-bit value at `offset` in the OEM-defined region space `space` (`0x80..=0xff`). This is used
+    /// to access fields in operation regions with vendor-specific region spaces. `width` is one of 8, 16, 32, or
+    /// 64.
+    fn read_oem_region(&self, space: u8, _offset: u64, _width: u8) -> Result<u64, AmlError> {
+        Err(AmlError::UnsupportedRegionSpace(RegionSpace::OemDefined(space)))
+    }
+
+    /// Write a `width`-bit value at `offset` in the OEM-defined region space `space`.
+    fn write_oem_region(&self, space: u8, _offset: u64, _width: u8, _value: u64) -> Result<(), AmlError> {
+        Err(AmlError::UnsupportedRegionSpace(RegionSpace::OemDefined(space)))
+    }
+
+    /// Perform a transaction with a device on a `GenericSerialBus` (e.g. I2C, SPI, or UART). `connection` is the
+    /// resource descriptor of the connection to the device, `attribute` describes the protocol and direction of
+    /// the transaction, and `command` is the command (or register) it targets. `buffer` is the transaction buffer:
+    /// a status byte, a length byte, and then the data. It contains the data to write for writes, and the handler
+    /// should fill in the status, length, and any data read.
+    fn gsb_transaction(
+        &self,
+        _connection: &[u8],
+        _attribute: GsbAttribute,
+        _command: u8,
+        _buffer: &mut [u8],
+    ) -> Result<(), AmlError> {
+        Err(AmlError::UnsupportedRegionSpace(RegionSpace::GenericSerialBus))
+    }
+
+    /// Perform a transaction with the device at `address` on an SMBus, using `protocol`. `command` is the command
+    /// (or register) it targets. `buffer` is the 34-byte transaction buffer: a status byte, a length byte, and up
+    /// to 32 bytes of data. It contains the data to write for writes, and the handler should fill in the status,
+    /// length, and any data read. Buffers longer than 34 bytes written to SMBus fields are truncated, so only
+    /// their first 34 bytes reach the handler.
+    fn smbus_transaction(
+        &self,
+        _protocol: SmbusProtocol,
+        _address: u8,
+        _command: u8,
+        _buffer: &mut [u8],
+    ) -> Result<(), AmlError> {
+        Err(AmlError::UnsupportedRegionSpace(RegionSpace::SMBus))
+    }
+
+    /// Send an IPMI request to the BMC, and wait for its response. `request` is the data of the request, and
+    /// `response` is the 66-byte buffer the response should be written into: a status byte, a length byte, and up
+    /// to 64 bytes of data.
+    fn ipmi_transaction(
+        &self,
+        _network_function: u8,
+        _command: u8,
+        _request: &[u8],
+        _response: &mut [u8],
+    ) -> Result<(), AmlError> {
+        Err(AmlError::UnsupportedRegionSpace(RegionSpace::IPMI))
+    }
+
+    /// Read `bit_count` pins of a GPIO connection, starting at `pin_offset` into the pin list of `connection`, which
+    /// is the resource descriptor of the connection. The first pin is returned in the least significant bit of the
+    /// result.
+    fn read_gpio(&self, _connection: &[u8], _pin_offset: u16, _bit_count: u16) -> Result<u64, AmlError> {
+        Err(AmlError::UnsupportedRegionSpace(RegionSpace::GeneralPurposeIo))
+    }
+
+    /// Write `bit_count` pins of a GPIO connection, starting at `pin_offset`. Like `read_gpio`, the first pin is in
+    /// the least significant bit of `value`.
+    fn write_gpio(
+        &self,
+        _connection: &[u8],
+        _pin_offset: u16,
+        _bit_count: u16,
+        _value: u64,
+    ) -> Result<(), AmlError> {
+        Err(AmlError::UnsupportedRegionSpace(RegionSpace::GeneralPurposeIo))
+    }
+
+    /// Stall for at least the given number of **microseconds**. An implementation should not relinquish control of
+    /// the processor during the stall, and for this reason, firmwares should not stall for periods of more than
+    /// 100 microseconds. A firmware's timing requirements can't be met without a real timer, so the default
+    /// implementation panics.
+    fn stall(&self, microseconds: u64) {
+        panic!("AML stalled for {} microseconds, but the Handler doesn't support stalling", microseconds);
+    }
+
+    /// Sleep for at least the given number of **milliseconds**. An implementation may round to the closest sleep
+    /// time supported, and should relinquish the processor. The default implementation panics.
+    fn sleep(&self, milliseconds: u64) {
+        panic!("AML slept for {} milliseconds, but the Handler doesn't support sleeping", milliseconds);
+    }
+
+    /// Get the value of a monotonically-increasing timer, in units of **100 nanoseconds**. This is used to
+    /// implement `DefTimer`, which firmware uses to measure how long it has been waiting for something. The
+    /// timer's starting value doesn't matter. Firmware waiting for the timer to advance would wait forever if it
+    /// didn't, so the default implementation panics.
+    fn monotonic_timer(&self) -> u64 {
+        panic!("AML read the timer, but the Handler doesn't provide one");
+    }
+
+    /// Find the ACPI table with the given signature, OEM ID, and OEM Table ID, and return its physical address and
+    /// length. This is used to create `DataTableRegion`s, which are operation regions that cover an entire table.
+    /// The default implementation can't find any tables.
+    fn find_table(&self, _signature: &str, _oem_id: &str, _oem_table_id: &str) -> Option<(u64, u64)> {
+        None
+    }
+
+    /// Called when AML notifies the OS of an event on a device, processor, or thermal zone using `DefNotify`.
+    /// `object` is the path to the object, and `value` identifies the event (e.g. `0x80` for a battery status
+    /// change). The default implementation ignores the notification.
+    fn handle_notify(&self, _object: &AmlName, _value: u64) {}
+
+    /// Called when AML stores a value into the `Debug` object, which firmware uses to emit diagnostic output
+    /// while executing methods. The default implementation ignores the value.
+    fn debug_output(&self, _value: &AmlValue) {}
+
+    /// Acquire the mutex at the given path, waiting for at most `timeout` milliseconds (a timeout of `0xffff` means
+    /// to wait forever). Returns `true` if the mutex was acquired, or `false` if the wait timed out. This is also
+    /// used to acquire the Global Lock, which has the path `\_GL`. The default implementation always acquires the
+    /// mutex immediately, which is only correct if AML is never executed concurrently.
+    fn acquire_mutex(&self, _mutex: &AmlName, _timeout: u16) -> bool {
+        true
+    }
+
+    /// Release the mutex at the given path, which has previously been acquired with
+    /// [`acquire_mutex`](Handler::acquire_mutex). The default implementation does nothing.
+    fn release_mutex(&self, _mutex: &AmlName) {}
+
+    /// Signal the event at the given path, releasing one wait on it by [`wait_for_event`](Handler::wait_for_event).
+    /// The default implementation does nothing.
+    fn signal_event(&self, _event: &AmlName) {}
+
+    /// Wait for the event at the given path to be signalled, waiting for at most `timeout` milliseconds (a timeout
+    /// of `0xffff` means to wait forever). Returns `true` if the event was signalled, or `false` if the wait timed
+    /// out. The default implementation always returns `true` immediately, which is only correct if AML is never
+    /// executed concurrently.
+    fn wait_for_event(&self, _event: &AmlName, _timeout: u16) -> bool {
+        true
+    }
+
+    /// Reset the event at the given path, discarding any signals that haven't been waited for. The default
+    /// implementation does nothing.
+    fn reset_event(&self, _event: &AmlName) {}
+
+    /// Called when AML encounters a `DefFatal` op, which indicates that the firmware has detected an unrecoverable
+    /// error. If this returns, execution of the AML is aborted with [`AmlError::FatalError`]. The default
+    /// implementation panics.
     fn handle_fatal_error(&self, fatal_type: u8, fatal_code: u32, fatal_arg: u64) {
         panic!("Fatal error while executing AML (encountered DefFatal op). fatal_type = {:?}, fatal_code = {:?}, fatal_arg = {:?}", fatal_type, fatal_code, fatal_arg);
     }
 }
 
+/// Parse a table of AML into a fresh namespace, without access to any hardware. `stream` should not include the
+/// table's header. Code that runs while the table is loaded reads zero from memory, IO ports, and PCI configuration
+/// space, and its writes to them are discarded. This is mainly useful for fuzzing and for inspecting tables
+/// offline: malformed input produces an `Err`, never a panic.
+pub fn parse_aml(stream: &[u8]) -> Result<Namespace, AmlError> {
+    let mut context = AmlContext::new(Box::new(NullHandler::default()), DebugVerbosity::None);
+    context.parse_table(stream).map_err(|err| err.error)?;
+    Ok(context.namespace)
+}
+
+/// The `Handler` used by [`parse_aml`], which has no hardware behind it.
+#[derive(Default)]
+struct NullHandler {
+    timer: core::sync::atomic::AtomicU64,
+}
+
+impl Handler for NullHandler {
+    fn read_u8(&self, _address: usize) -> u8 {
+        0
+    }
+    fn read_u16(&self, _address: usize) -> u16 {
+        0
+    }
+    fn read_u32(&self, _address: usize) -> u32 {
+        0
+    }
+    fn read_u64(&self, _address: usize) -> u64 {
+        0
+    }
+
+    fn write_u8(&mut self, _address: usize, _value: u8) {}
+    fn write_u16(&mut self, _address: usize, _value: u16) {}
+    fn write_u32(&mut self, _address: usize, _value: u32) {}
+    fn write_u64(&mut self, _address: usize, _value: u64) {}
+
+    fn read_io_u8(&self, _port: u16) -> u8 {
+        0
+    }
+    fn read_io_u16(&self, _port: u16) -> u16 {
+        0
+    }
+    fn read_io_u32(&self, _port: u16) -> u32 {
+        0
+    }
+
+    fn write_io_u8(&self, _port: u16, _value: u8) {}
+    fn write_io_u16(&self, _port: u16, _value: u16) {}
+    fn write_io_u32(&self, _port: u16, _value: u32) {}
+
+    fn read_pci_u8(&self, _segment: u16, _bus: u8, _device: u8, _function: u8, _offset: u16) -> u8 {
+        0
+    }
+    fn read_pci_u16(&self, _segment: u16, _bus: u8, _device: u8, _function: u8, _offset: u16) -> u16 {
+        0
+    }
+    fn read_pci_u32(&self, _segment: u16, _bus: u8, _device: u8, _function: u8, _offset: u16) -> u32 {
+        0
+    }
+    fn write_pci_u8(&self, _segment: u16, _bus: u8, _device: u8, _function: u8, _offset: u16, _value: u8) {}
+    fn write_pci_u16(&self, _segment: u16, _bus: u8, _device: u8, _function: u8, _offset: u16, _value: u16) {}
+    fn write_pci_u32(&self, _segment: u16, _bus: u8, _device: u8, _function: u8, _offset: u16, _value: u32) {}
+
+    fn stall(&self, _microseconds: u64) {}
+    fn sleep(&self, _milliseconds: u64) {}
+    fn monotonic_timer(&self) -> u64 {
+        // Advance the timer on every read, so AML waiting for time to pass can't wait forever
+        self.timer.fetch_add(10, core::sync::atomic::Ordering::Relaxed)
+    }
+
+    // `DefFatal` is reported as `AmlError::FatalError` instead
+    fn handle_fatal_error(&self, _fatal_type: u8, _fatal_code: u32, _fatal_arg: u64) {}
+}
+
 /// Used when an [`AmlContext`] encounters an error.
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub enum AmlError {
@@ -705,16 +1484,18 @@ pub enum AmlError {
     UnexpectedByte(u8),
     /// Produced when the stream evaluates to something other than nothing or an error.
     MalformedStream,
-    InvalidNameSeg,
+    /// Produced when a `NameSeg` is expected in the stream, but the bytes there aren't valid name characters.
+    InvalidNameSeg([u8; 4]),
     InvalidPkgLength,
     InvalidFieldFlags,
     UnterminatedStringConstant,
     InvalidStringConstant,
     InvalidRegionSpace(u8),
-    /// Produced when a `DefPackage` contains a different number of elements to the package's length.
+    /// Produced when a `DefPackage` contains more elements than the package's length.
     MalformedPackage,
-    /// Produced when a `DefBuffer` contains more bytes that its size.
-    MalformedBuffer,
+    /// Produced when an object in a `TermList` is parsed without consuming any of the stream, which would otherwise
+    /// cause the list to be parsed forever.
+    NoProgress,
     /// Emitted by a parser when it's clear that the stream doesn't encode the object parsed by
     /// that parser (e.g. the wrong opcode starts the stream). This is handled specially by some
     /// parsers such as `or` and `choice!`.
@@ -726,6 +1507,9 @@ pub enum AmlError {
      * Errors produced manipulating AML names.
      */
     EmptyNamesAreInvalid,
+    /// Produced when a string can't be converted to an `AmlName`, because one of its segments is too long or
+    /// contains invalid characters.
+    InvalidNameString,
     /// Produced when trying to normalize a path that does not point to a valid level of the
     /// namespace. E.g. `\_SB.^^PCI0` goes above the root of the namespace. The contained value is the name that
     /// normalization was attempted upon.
@@ -756,10 +1540,19 @@ pub enum AmlError {
     InvalidLocalAccess(LocalNum),
     /// Tried to invoke a method with too many arguments.
     TooManyArgs,
+    /// Produced when control method invocations are nested too deeply, such as when a method invokes itself
+    /// without ever returning.
+    MethodNestingTooDeep,
     /// A `DefBreak` operation was performed outside of a `DefWhile` or `DefSwitch`.
     BreakInInvalidPosition,
     /// A `DefContinue` operation was performed outside of a `DefWhile`.
     ContinueInInvalidPosition,
+    /// Produced when the object of a `DefNotify` isn't a `Device`, `Processor`, or `ThermalZone`.
+    InvalidNotifyObject,
+    /// Produced when the object of a `DefAcquire` or `DefRelease` isn't a `Mutex`.
+    NotAMutex,
+    /// Produced when the object of a `DefSignal`, `DefWait`, or `DefReset` isn't an `Event`.
+    NotAnEvent,
 
     /*
      * Errors produced parsing the PCI routing tables (_PRT objects).
@@ -777,6 +1570,7 @@ pub enum AmlError {
     ReservedResourceType,
     ResourceDescriptorTooShort,
     ResourceDescriptorTooLong,
+    InvalidResourceDescriptorChecksum,
     UnexpectedResourceType,
 
     /*
@@ -787,16 +1581,175 @@ pub enum AmlError {
         target: AmlType,
     },
     InvalidStatusObject,
-    InvalidShiftLeft,
-    InvalidShiftRight,
+    /// Produced when a `DefDivide` or `DefMod` has a divisor of zero.
+    DivideByZero,
     FieldRegionIsNotOpRegion,
+    /// Produced when a field is defined that extends past the end of the operation region it is in.
+    FieldOutOfRegionBounds,
+    /// Produced when a `DefDataRegion` refers to a table that the [`Handler`] can't find.
+    DataRegionTableNotFound {
+        signature: String,
+        oem_id: String,
+        oem_table_id: String,
+    },
     FieldInvalidAddress,
     FieldInvalidAccessSize,
+    /// Produced when a field in a region that needs a connection to a device (e.g. a `GeneralPurposeIo` region)
+    /// is accessed, but no `Connection` precedes it in its field list.
+    FieldMissingConnection,
+    /// Produced when a field in an `SMBus` region is accessed, but its access attribute is missing or isn't one of
+    /// the SMBus protocols.
+    InvalidSmbusProtocol,
     TypeCannotBeCompared(AmlType),
     /// Produced when the `Mid` operator is applied to a value of a type other than `Buffer` or `String`.
     TypeCannotBeSliced(AmlType),
     TypeCannotBeWrittenToBufferField(AmlType),
     BufferFieldIndexesOutOfBounds,
+    /// Produced when the `Index` operator is applied to a value of a type other than `Buffer`, `Package`, or
+    /// `String`.
+    TypeCannotBeIndexed(AmlType),
+    /// Produced when `Index` is used to refer to an element past the end of a `Buffer`, `Package`, or `String`.
+    IndexOutOfBounds,
+    /// Produced when a `DefBuffer` or `DefVarPackage` is too large to be allocated.
+    ObjectTooLarge,
+    /// Produced when the Null target or the Debug object is used as the source of an operation.
+    TargetCannotBeRead,
+    /// Produced when a store is made through a reference to a copy of an object, which would have no effect.
+    TargetCannotBeWritten,
+    /// Produced when a field is accessed in an operation region whose space we don't support accessing.
+    UnsupportedRegionSpace(RegionSpace),
+    /// Produced when `DerefOf` is applied to a value that isn't a reference or the name of an object.
+    TypeCannotBeDereferenced(AmlType),
+    /// Produced when `ToBCD` is applied to an integer with more than 16 decimal digits, or `FromBCD` is applied to
+    /// an integer that contains a nibble that isn't a decimal digit.
+    InvalidBcdConversion(u64),
+    /// Produced when a `DefMatch` uses a match opcode that isn't defined.
+    InvalidMatchOpcode(u8),
+    /// Produced when `SizeOf` is applied to a value of a type other than `Buffer`, `Package`, or `String`.
+    TypeCannotBeSized(AmlType),
+}
+
+/// Returned by [`AmlContext::parse_table`] when a table can't be parsed. As well as the error itself, this records
+/// where in the table it occurred.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ParseError {
+    /// The offset into the table of the innermost term that failed to parse, or `None` if the error wasn't in a
+    /// term. Errors in control methods executed while parsing the table are reported at the term that invoked them.
+    pub offset: Option<usize>,
+    /// The scope that was being parsed when the error occurred.
+    pub scope: AmlName,
+    pub error: AmlError,
+}
+
+impl fmt::Display for AmlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AmlError::UnexpectedEndOfStream => write!(f, "unexpected end of AML stream"),
+            AmlError::UnexpectedByte(byte) => write!(f, "unexpected byte {:#04x}", byte),
+            AmlError::MalformedStream => write!(f, "AML stream evaluated to an unexpected result"),
+            AmlError::InvalidNameSeg(seg) => write!(f, "invalid NameSeg {:02x?}", seg),
+            AmlError::InvalidPkgLength => write!(f, "invalid PkgLength"),
+            AmlError::InvalidFieldFlags => write!(f, "invalid field flags"),
+            AmlError::UnterminatedStringConstant => write!(f, "string constant is not null-terminated"),
+            AmlError::InvalidStringConstant => write!(f, "string constant contains non-ASCII characters"),
+            AmlError::InvalidRegionSpace(space) => write!(f, "invalid region space {:#04x}", space),
+            AmlError::MalformedPackage => write!(f, "package contains more elements than its length"),
+            AmlError::NoProgress => write!(f, "object in term list did not consume any of the stream"),
+            AmlError::WrongParser => write!(f, "stream does not encode the object being parsed"),
+            AmlError::FatalError => write!(f, "AML raised a fatal error"),
+
+            AmlError::EmptyNamesAreInvalid => write!(f, "empty names are invalid"),
+            AmlError::InvalidNameString => write!(f, "string is not a valid name"),
+            AmlError::InvalidNormalizedName(name) => write!(f, "{} does not normalize to a valid path", name),
+            AmlError::RootHasNoParent => write!(f, "the root of the namespace has no parent"),
+
+            AmlError::LevelDoesNotExist(name) => write!(f, "namespace level {} does not exist", name),
+            AmlError::ValueDoesNotExist(name) => write!(f, "object {} does not exist", name),
+            AmlError::NameCollision(name) => write!(f, "object {} already exists", name),
+            AmlError::TriedToRemoveRootNamespace => write!(f, "tried to remove the root of the namespace"),
+
+            AmlError::NotExecutingControlMethod => write!(f, "not executing a control method"),
+            AmlError::InvalidArgAccess(arg) => write!(f, "invalid access of Arg{}", arg),
+            AmlError::InvalidLocalAccess(local) => write!(f, "invalid access of Local{}", local),
+            AmlError::TooManyArgs => write!(f, "too many arguments passed to control method"),
+            AmlError::MethodNestingTooDeep => write!(f, "control method invocations are nested too deeply"),
+            AmlError::BreakInInvalidPosition => write!(f, "Break outside of While"),
+            AmlError::ContinueInInvalidPosition => write!(f, "Continue outside of While"),
+            AmlError::InvalidNotifyObject => write!(f, "Notify object is not a Device, Processor, or ThermalZone"),
+            AmlError::NotAMutex => write!(f, "object is not a Mutex"),
+            AmlError::NotAnEvent => write!(f, "object is not an Event"),
+
+            AmlError::PrtInvalidAddress => write!(f, "_PRT entry has invalid address"),
+            AmlError::PrtInvalidPin => write!(f, "_PRT entry has invalid pin"),
+            AmlError::PrtInvalidSource => write!(f, "_PRT entry has invalid source"),
+            AmlError::PrtInvalidGsi => write!(f, "_PRT entry has invalid GSI"),
+            AmlError::PrtNoEntry => write!(f, "_PRT has no entry for the requested address and pin"),
+
+            AmlError::ReservedResourceType => write!(f, "reserved resource descriptor type"),
+            AmlError::ResourceDescriptorTooShort => write!(f, "resource descriptor is too short"),
+            AmlError::ResourceDescriptorTooLong => write!(f, "resource descriptor is too long"),
+            AmlError::InvalidResourceDescriptorChecksum => {
+                write!(f, "resource descriptor has an invalid checksum")
+            }
+            AmlError::UnexpectedResourceType => write!(f, "unexpected resource descriptor type"),
+
+            AmlError::IncompatibleValueConversion { current, target } => {
+                write!(f, "can't convert value of type {:?} to {:?}", current, target)
+            }
+            AmlError::InvalidStatusObject => write!(f, "invalid _STA object"),
+            AmlError::DivideByZero => write!(f, "division by zero"),
+            AmlError::FieldRegionIsNotOpRegion => write!(f, "field's region is not an OpRegion"),
+            AmlError::FieldOutOfRegionBounds => write!(f, "field extends past the end of its OpRegion"),
+            AmlError::DataRegionTableNotFound { signature, oem_id, oem_table_id } => write!(
+                f,
+                "no table found for DataRegion (signature {:?}, OEM id {:?}, OEM table id {:?})",
+                signature, oem_id, oem_table_id
+            ),
+            AmlError::FieldInvalidAddress => write!(f, "field has an invalid address"),
+            AmlError::FieldInvalidAccessSize => write!(f, "field has an invalid access size"),
+            AmlError::FieldMissingConnection => write!(f, "field is not preceded by a connection"),
+            AmlError::InvalidSmbusProtocol => {
+                write!(f, "field doesn't have an SMBus protocol as its access attribute")
+            }
+            AmlError::TypeCannotBeCompared(typ) => write!(f, "values of type {:?} can't be compared", typ),
+            AmlError::TypeCannotBeSliced(typ) => write!(f, "values of type {:?} can't be sliced", typ),
+            AmlError::TypeCannotBeWrittenToBufferField(typ) => {
+                write!(f, "values of type {:?} can't be written to a BufferField", typ)
+            }
+            AmlError::BufferFieldIndexesOutOfBounds => write!(f, "BufferField extends past the end of its buffer"),
+            AmlError::TypeCannotBeIndexed(typ) => write!(f, "values of type {:?} can't be indexed", typ),
+            AmlError::IndexOutOfBounds => write!(f, "index out of bounds"),
+            AmlError::ObjectTooLarge => write!(f, "object is too large to allocate"),
+            AmlError::TargetCannotBeRead => write!(f, "target cannot be read from"),
+            AmlError::TargetCannotBeWritten => write!(f, "target cannot be written to"),
+            AmlError::UnsupportedRegionSpace(space) => {
+                write!(f, "accesses to {:?} regions are not supported", space)
+            }
+            AmlError::TypeCannotBeDereferenced(typ) => write!(f, "values of type {:?} can't be dereferenced", typ),
+            AmlError::InvalidBcdConversion(value) => {
+                write!(f, "{:#x} can't be converted to or from binary-coded decimal", value)
+            }
+            AmlError::InvalidMatchOpcode(op) => write!(f, "invalid Match opcode {:#04x}", op),
+            AmlError::TypeCannotBeSized(typ) => write!(f, "values of type {:?} have no size", typ),
+        }
+    }
+}
+
+impl core::error::Error for AmlError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.offset {
+            Some(offset) => write!(f, "{} at offset {:#x} (in scope {})", self.error, offset, self.scope),
+            None => write!(f, "{} (in scope {})", self.error, self.scope),
+        }
+    }
+}
+
+impl core::error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        Some(&self.error)
+    }
 }
 
 #[cfg(test)]
@@ -809,4 +1762,473 @@ mod tests {
         fn test_send_sync<T: Send + Sync>() {}
         test_send_sync::<AmlContext>();
     }
+
+    #[test]
+    fn test_parse_aml() {
+        // Name(FOO, 7)
+        let namespace = parse_aml(&[0x08, b'F', b'O', b'O', b'_', 0x0a, 0x07]).unwrap();
+        assert!(crate::test_utils::crudely_cmp_values(
+            namespace.get_by_path(&AmlName::from_str("\\FOO").unwrap()).unwrap(),
+            &AmlValue::Integer(7)
+        ));
+
+        // Name(\, One), and Fatal(0x01, 0x12345678, 5)
+        assert_eq!(parse_aml(&[0x08, b'\\', 0x00, 0x01]).map(|_| ()), Err(AmlError::RootHasNoParent));
+        assert_eq!(
+            parse_aml(&[0x5b, 0x32, 0x01, 0x78, 0x56, 0x34, 0x12, 0x0a, 0x05]).map(|_| ()),
+            Err(AmlError::FatalError)
+        );
+    }
+
+    #[test]
+    fn test_error_display() {
+        use alloc::string::ToString;
+
+        assert_eq!(AmlError::UnexpectedByte(0x5b).to_string(), "unexpected byte 0x5b");
+        assert_eq!(
+            AmlError::ValueDoesNotExist(AmlName::from_str("\\_SB.PCI0").unwrap()).to_string(),
+            "object \\_SB_.PCI0 does not exist"
+        );
+        assert_eq!(
+            AmlError::IncompatibleValueConversion { current: AmlType::Package, target: AmlType::Integer }
+                .to_string(),
+            "can't convert value of type Package to Integer"
+        );
+        assert_eq!(
+            ParseError {
+                offset: Some(0x1c),
+                scope: AmlName::from_str("\\_SB").unwrap(),
+                error: AmlError::MalformedStream
+            }
+            .to_string(),
+            "AML stream evaluated to an unexpected result at offset 0x1c (in scope \\_SB_)"
+        );
+    }
+
+    #[test]
+    fn test_lookup() {
+        let mut context = crate::test_utils::make_test_context();
+
+        /*
+         * Name(FOOO, 7)
+         * Scope(\\_SB) { Name(BAR, 3) }
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x08, b'F', b'O', b'O', b'O', 0x0a, 0x07, 0x10, 0x0d, b'\\', b'_', b'S', b'B', b'_', 0x08, b'B',
+                b'A', b'R', b'_', 0x0a, 0x03,
+            ]),
+            Ok(())
+        );
+
+        assert!(matches!(context.lookup(&AmlName::from_str("\\FOOO").unwrap()), Some(AmlValue::Integer(7))));
+        assert!(matches!(context.lookup(&AmlName::from_str("FOOO").unwrap()), Some(AmlValue::Integer(7))));
+        assert!(matches!(context.lookup(&AmlName::from_str("\\_SB.BAR").unwrap()), Some(AmlValue::Integer(3))));
+        assert!(matches!(context.lookup(&AmlName::from_str("_SB.BAR_").unwrap()), Some(AmlValue::Integer(3))));
+        assert!(context.lookup(&AmlName::from_str("\\_SB.BAZ").unwrap()).is_none());
+        assert!(context.lookup(&AmlName::from_str("\\BAR").unwrap()).is_none());
+
+        *context.lookup_mut(&AmlName::from_str("\\_SB.BAR").unwrap()).unwrap() = AmlValue::Integer(4);
+        assert!(matches!(context.lookup(&AmlName::from_str("\\_SB.BAR").unwrap()), Some(AmlValue::Integer(4))));
+    }
+
+    #[test]
+    fn test_find_devices() {
+        let mut context = crate::test_utils::make_test_context();
+
+        /*
+         * Device(\\_SB.PCI0) { Name(_HID, EisaId("PNP0A03")) }
+         * Device(\\_SB.PCI1) {
+         *     Name(_HID, EisaId("PNP0A08"))
+         *     Name(_CID, EisaId("PNP0A03"))
+         * }
+         * Device(\\_SB.ACAD) {
+         *     Method(_HID, 0) { Return("ACPI0003") }
+         * }
+         * Device(\\_SB.DEV0) { }
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x5b, 0x82, 0x15, b'\\', 0x2e, b'_', b'S', b'B', b'_', b'P', b'C', b'I', b'0', 0x08, b'_', b'H',
+                b'I', b'D', 0x0c, 0x41, 0xd0, 0x0a, 0x03, 0x5b, 0x82, 0x1f, b'\\', 0x2e, b'_', b'S', b'B', b'_',
+                b'P', b'C', b'I', b'1', 0x08, b'_', b'H', b'I', b'D', 0x0c, 0x41, 0xd0, 0x0a, 0x08, 0x08, b'_',
+                b'C', b'I', b'D', 0x0c, 0x41, 0xd0, 0x0a, 0x03, 0x5b, 0x82, 0x1d, b'\\', 0x2e, b'_', b'S', b'B',
+                b'_', b'A', b'C', b'A', b'D', 0x14, 0x11, b'_', b'H', b'I', b'D', 0x00, 0xa4, 0x0d, b'A', b'C',
+                b'P', b'I', b'0', b'0', b'0', b'3', 0x00, 0x5b, 0x82, 0x0b, b'\\', 0x2e, b'_', b'S', b'B', b'_',
+                b'D', b'E', b'V', b'0',
+            ]),
+            Ok(())
+        );
+
+        assert_eq!(
+            context.find_devices("PNP0A03"),
+            Ok(alloc::vec![AmlName::from_str("\\_SB.PCI0").unwrap(), AmlName::from_str("\\_SB.PCI1").unwrap()])
+        );
+        assert_eq!(context.find_devices("PNP0A08"), Ok(alloc::vec![AmlName::from_str("\\_SB.PCI1").unwrap()]));
+        assert_eq!(context.find_devices("ACPI0003"), Ok(alloc::vec![AmlName::from_str("\\_SB.ACAD").unwrap()]));
+        assert_eq!(context.find_devices("PNP0C0A"), Ok(alloc::vec![]));
+
+        /*
+         * Devices whose IDs fail to evaluate are skipped.
+         *
+         * Device(\_SB.BAD0) {
+         *     Method(_HID, 0) { Return(DerefOf(Zero)) }
+         * }
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x5b, 0x82, 0x15, b'\\', 0x2e, b'_', b'S', b'B', b'_', b'B', b'A', b'D', b'0', 0x14, 0x09, b'_',
+                b'H', b'I', b'D', 0x00, 0xa4, 0x83, 0x00,
+            ]),
+            Ok(())
+        );
+        assert!(context.invoke_method(&AmlName::from_str("\\_SB.BAD0._HID").unwrap(), Args::EMPTY).is_err());
+        assert_eq!(
+            context.find_devices("PNP0A03"),
+            Ok(alloc::vec![AmlName::from_str("\\_SB.PCI0").unwrap(), AmlName::from_str("\\_SB.PCI1").unwrap()])
+        );
+    }
+
+    #[test]
+    fn test_invoke_method() {
+        let mut context = crate::test_utils::make_test_context();
+
+        /*
+         * Method(FACT, 1) {
+         *     Local0 = 1
+         *     While (Arg0) {
+         *         Local0 *= Arg0
+         *         Arg0--
+         *     }
+         *     Return (Local0)
+         * }
+         * Method(CALL, 1) {
+         *     Local0 = 5
+         *     Return (FACT(Arg0) + Local0)
+         * }
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x14, 0x14, b'F', b'A', b'C', b'T', 0x01, 0x70, 0x01, 0x60, 0xa2, 0x08, 0x68, 0x77, 0x60, 0x68,
+                0x60, 0x76, 0x68, 0xa4, 0x60, 0x14, 0x13, b'C', b'A', b'L', b'L', 0x01, 0x70, 0x0a, 0x05, 0x60,
+                0xa4, 0x72, b'F', b'A', b'C', b'T', 0x68, 0x60, 0x00,
+            ]),
+            Ok(())
+        );
+
+        let fact = AmlName::from_str("\\FACT").unwrap();
+        assert_eq!(
+            context
+                .invoke_method(&fact, Args::from_list(alloc::vec![AmlValue::Integer(5)]).unwrap())
+                .unwrap()
+                .as_integer(&context),
+            Ok(120)
+        );
+        assert_eq!(
+            context
+                .invoke_method(&fact, Args::from_list(alloc::vec![AmlValue::Integer(0)]).unwrap())
+                .unwrap()
+                .as_integer(&context),
+            Ok(1)
+        );
+
+        // `CALL`'s `Local0` must survive the invocation of `FACT`, which uses its own `Local0`
+        assert_eq!(
+            context
+                .invoke_method(
+                    &AmlName::from_str("\\CALL").unwrap(),
+                    Args::from_list(alloc::vec![AmlValue::Integer(4)]).unwrap()
+                )
+                .unwrap()
+                .as_integer(&context),
+            Ok(29)
+        );
+    }
+
+    #[test]
+    fn test_invoke_method_relative() {
+        let mut context = crate::test_utils::make_test_context();
+
+        /*
+         * Device(DEV0) {
+         *     Name(_STA, 0x0f)
+         *     Device(DEV1) {
+         *         Method(_STA, 0) { Return (0x0b) }
+         *     }
+         * }
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x5b, 0x82, 0x1d, b'D', b'E', b'V', b'0', 0x08, b'_', b'S', b'T', b'A', 0x0a, 0x0f, 0x5b, 0x82,
+                0x0f, b'D', b'E', b'V', b'1', 0x14, 0x09, b'_', b'S', b'T', b'A', 0x00, 0xa4, 0x0a, 0x0b,
+            ]),
+            Ok(())
+        );
+
+        let invoke = |context: &mut AmlContext, scope: &str, path: &str| {
+            context.invoke_method_relative(
+                &AmlName::from_str(scope).unwrap(),
+                &AmlName::from_str(path).unwrap(),
+                Args::default(),
+            )
+        };
+        assert!(matches!(invoke(&mut context, "\\DEV0", "_STA"), Ok(AmlValue::Integer(0x0f))));
+        assert!(matches!(invoke(&mut context, "DEV0", "_STA"), Ok(AmlValue::Integer(0x0f))));
+        assert!(matches!(invoke(&mut context, "\\DEV0.DEV1", "_STA"), Ok(AmlValue::Integer(0x0b))));
+        assert!(matches!(invoke(&mut context, "\\DEV0", "DEV1._STA"), Ok(AmlValue::Integer(0x0b))));
+        assert!(matches!(invoke(&mut context, "\\DEV0.DEV1", "^_STA"), Ok(AmlValue::Integer(0x0f))));
+        assert!(matches!(invoke(&mut context, "\\DEV0.DEV1", "\\DEV0._STA"), Ok(AmlValue::Integer(0x0f))));
+        assert_eq!(
+            invoke(&mut context, "\\", "_STA").err(),
+            Some(AmlError::ValueDoesNotExist(AmlName::from_str("\\_STA").unwrap()))
+        );
+    }
+
+    #[test]
+    fn test_search_rules() {
+        let mut context = crate::test_utils::make_test_context();
+
+        /*
+         * Scope(\_SB) {
+         *     Name(FOO, 0x2a)
+         *     Device(PCI0) {
+         *         Name(BAR, One)
+         *         Device(GFX0) {
+         *             Method(TST, 0) { Return (FOO) }
+         *             Method(TST2, 0) { Return (PCI0.BAR) }
+         *         }
+         *     }
+         * }
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x10, 0x3e, b'\\', b'_', b'S', b'B', b'_', 0x08, b'F', b'O', b'O', b'_', 0x0a, 0x2a, 0x5b, 0x82,
+                0x2f, b'P', b'C', b'I', b'0', 0x08, b'B', b'A', b'R', b'_', 0x01, 0x5b, 0x82, 0x22, b'G', b'F',
+                b'X', b'0', 0x14, 0x0b, b'T', b'S', b'T', b'_', 0x00, 0xa4, b'F', b'O', b'O', b'_', 0x14, 0x10,
+                b'T', b'S', b'T', b'2', 0x00, 0xa4, 0x2e, b'P', b'C', b'I', b'0', b'B', b'A', b'R', b'_',
+            ]),
+            Ok(())
+        );
+
+        // A single segment is searched for in each scope up to the root
+        assert!(matches!(
+            context.invoke_method(&AmlName::from_str("\\_SB.PCI0.GFX0.TST").unwrap(), Args::default()),
+            Ok(AmlValue::Integer(0x2a))
+        ));
+        // But names with more than one segment are only resolved against the current scope
+        assert!(matches!(
+            context.invoke_method(&AmlName::from_str("\\_SB.PCI0.GFX0.TST2").unwrap(), Args::default()),
+            Err(AmlError::ValueDoesNotExist(_) | AmlError::LevelDoesNotExist(_))
+        ));
+    }
+
+    #[test]
+    fn test_device_status() {
+        let mut context = crate::test_utils::make_test_context();
+
+        /*
+         * Device(DEV0) { }
+         * Device(DEV1) { Name(_STA, 0x0d) }
+         * Device(DEV2) {
+         *     Method(_STA, 0) { Return (0x00) }
+         * }
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x5b, 0x82, 0x05, b'D', b'E', b'V', b'0', 0x5b, 0x82, 0x0c, b'D', b'E', b'V', b'1', 0x08, b'_',
+                b'S', b'T', b'A', 0x0a, 0x0d, 0x5b, 0x82, 0x0e, b'D', b'E', b'V', b'2', 0x14, 0x08, b'_', b'S',
+                b'T', b'A', 0x00, 0xa4, 0x00,
+            ]),
+            Ok(())
+        );
+
+        assert_eq!(context.device_status(&AmlName::from_str("\\DEV0").unwrap()), Ok(StatusObject::default()));
+        assert_eq!(
+            context.device_status(&AmlName::from_str("DEV1").unwrap()),
+            Ok(StatusObject {
+                present: true,
+                enabled: false,
+                show_in_ui: true,
+                functional: true,
+                battery_present: false
+            })
+        );
+        assert_eq!(
+            context.device_status(&AmlName::from_str("\\DEV2").unwrap()),
+            Ok(StatusObject {
+                present: false,
+                enabled: false,
+                show_in_ui: false,
+                functional: false,
+                battery_present: false
+            })
+        );
+        assert!(matches!(
+            context.device_status(&AmlName::from_str("\\DEV3").unwrap()),
+            Err(AmlError::ValueDoesNotExist(_))
+        ));
+
+        /*
+         * Device(DEV4) {
+         *     Method(_STA, 0) { Return (Buffer() { 0x1f }) }
+         * }
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x5b, 0x82, 0x12, b'D', b'E', b'V', b'4', 0x14, 0x0c, b'_', b'S', b'T', b'A', 0x00, 0xa4, 0x11,
+                0x04, 0x0a, 0x01, 0x1f,
+            ]),
+            Ok(())
+        );
+        assert_eq!(context.device_status(&AmlName::from_str("\\DEV4").unwrap()), Ok(StatusObject::default()));
+    }
+
+    #[test]
+    fn test_multiple_tables() {
+        let mut context = crate::test_utils::make_test_context();
+
+        /*
+         * DSDT:
+         * External(\_SB.PCI0.FOO, MethodObj, 0)
+         * Device(\_SB.PCI0) {
+         *     Name(_STA, 0x0f)
+         * }
+         * Name(BAR, One)
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x15, b'\\', 0x2f, 0x03, b'_', b'S', b'B', b'_', b'P', b'C', b'I', b'0', b'F', b'O', b'O', b'_',
+                0x08, 0x00, 0x5b, 0x82, 0x12, b'\\', 0x2e, b'_', b'S', b'B', b'_', b'P', b'C', b'I', b'0', 0x08,
+                b'_', b'S', b'T', b'A', 0x0a, 0x0f, 0x08, b'B', b'A', b'R', b'_', 0x01,
+            ]),
+            Ok(())
+        );
+
+        /*
+         * SSDT:
+         * Scope(\_SB.PCI0) {
+         *     Method(FOO, 0) { Return (0x2a) }
+         *     Name(_STA, 0x0b)
+         * }
+         * Name(BAR, 0x07)
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x10, 0x1c, b'\\', 0x2e, b'_', b'S', b'B', b'_', b'P', b'C', b'I', b'0', 0x14, 0x09, b'F', b'O',
+                b'O', b'_', 0x00, 0xa4, 0x0a, 0x2a, 0x08, b'_', b'S', b'T', b'A', 0x0a, 0x0b, 0x08, b'B', b'A',
+                b'R', b'_', 0x0a, 0x07,
+            ]),
+            Ok(())
+        );
+
+        assert_eq!(
+            context
+                .invoke_method(&AmlName::from_str("\\_SB.PCI0.FOO").unwrap(), Args::default())
+                .unwrap()
+                .as_integer(&context),
+            Ok(0x2a)
+        );
+        assert_eq!(
+            context.device_status(&AmlName::from_str("\\_SB.PCI0").unwrap()).map(|status| status.show_in_ui),
+            Ok(false)
+        );
+        assert_eq!(context.lookup(&AmlName::from_str("\\BAR").unwrap()).unwrap().as_integer(&context), Ok(7));
+
+        // Objects can't be redefined within a single table
+        assert!(matches!(
+            context.parse_table(&[0x08, b'B', b'A', b'Z', b'_', 0x01, 0x08, b'B', b'A', b'Z', b'_', 0x00]),
+            Err(ParseError { error: AmlError::NameCollision(_), .. })
+        ));
+        assert_eq!(context.lookup(&AmlName::from_str("\\BAZ").unwrap()).unwrap().as_integer(&context), Ok(1));
+
+        // Unless redefinitions are allowed, in which case the last definition wins
+        context.namespace.allow_redefinition = true;
+        assert_eq!(
+            context.parse_table(&[0x08, b'Q', b'U', b'X', b'_', 0x01, 0x08, b'Q', b'U', b'X', b'_', 0x00]),
+            Ok(())
+        );
+        assert_eq!(context.lookup(&AmlName::from_str("\\QUX").unwrap()).unwrap().as_integer(&context), Ok(0));
+    }
+
+    #[test]
+    fn test_malformed_tables() {
+        let parse =
+            |stream: &[u8]| crate::test_utils::make_test_context().parse_table(stream).map_err(|err| err.error);
+
+        // Name(\, One)
+        assert_eq!(parse(&[0x08, b'\\', 0x00, 0x01]), Err(AmlError::RootHasNoParent));
+        // Buffer(Ones) { }
+        assert_eq!(parse(&[0x11, 0x02, 0xff]), Err(AmlError::ObjectTooLarge));
+        // VarPackage(Ones) { }
+        assert_eq!(parse(&[0x13, 0x02, 0xff]), Err(AmlError::ObjectTooLarge));
+        // CreateByteField(Buffer(4) { }, Ones, FOO)
+        assert_eq!(
+            parse(&[0x8c, 0x11, 0x03, 0x0a, 0x04, 0xff, b'F', b'O', b'O', b'_']),
+            Err(AmlError::BufferFieldIndexesOutOfBounds)
+        );
+        /*
+         * Name(BUF, Buffer(4) { })
+         * CreateField(BUF, 0x1c, 0x10, FLD)
+         * FLD = 0xffff
+         */
+        assert_eq!(
+            parse(&[
+                0x08, b'B', b'U', b'F', b'_', 0x11, 0x03, 0x0a, 0x04, 0x5b, 0x13, b'B', b'U', b'F', b'_', 0x0a,
+                0x1c, 0x0a, 0x10, b'F', b'L', b'D', b'_', 0x70, 0x0b, 0xff, 0xff, b'F', b'L', b'D', b'_',
+            ]),
+            Err(AmlError::BufferFieldIndexesOutOfBounds)
+        );
+        // Scope(\_SB) { Name(FOO, One) }, but the scope's PkgLength ends before the end of the name
+        assert_eq!(
+            parse(&[0x10, 0x0b, b'\\', b'_', b'S', b'B', b'_', 0x08, b'F', b'O', b'O', b'_', 0x01]),
+            Err(AmlError::UnexpectedEndOfStream)
+        );
+        // Increment(Debug)
+        assert_eq!(parse(&[0x75, 0x5b, 0x31]), Err(AmlError::TargetCannotBeRead));
+        // Debug = One
+        assert_eq!(parse(&[0x70, 0x01, 0x5b, 0x31]), Ok(()));
+
+        // Name(FOO, One) and Scope(\_SB) { Name(\, One) } fail at the second `Name`, inside the scope
+        assert_eq!(
+            crate::test_utils::make_test_context().parse_table(&[
+                0x08, b'F', b'O', b'O', b'_', 0x01, 0x10, 0x0a, b'\\', b'_', b'S', b'B', b'_', 0x08, b'\\', 0x00,
+                0x01,
+            ]),
+            Err(ParseError {
+                offset: Some(13),
+                scope: AmlName::from_str("\\_SB").unwrap(),
+                error: AmlError::RootHasNoParent
+            })
+        );
+
+        // Method(RECU, 0) { RECU() }
+        let mut context = crate::test_utils::make_test_context();
+        assert_eq!(
+            context.parse_table(&[0x14, 0x0a, b'R', b'E', b'C', b'U', 0x00, b'R', b'E', b'C', b'U']),
+            Ok(())
+        );
+        assert!(matches!(
+            context.invoke_method(&AmlName::from_str("\\RECU").unwrap(), Args::default()),
+            Err(AmlError::MethodNestingTooDeep)
+        ));
+    }
+
+    #[test]
+    fn test_debug_output() {
+        let (mut context, hardware) = crate::test_utils::make_recording_test_context();
+
+        /*
+         * Store(0x2a, Debug)
+         * Store("AB", Debug)
+         */
+        assert_eq!(
+            context.parse_table(&[0x70, 0x0a, 0x2a, 0x5b, 0x31, 0x70, 0x0d, b'A', b'B', 0x00, 0x5b, 0x31]),
+            Ok(())
+        );
+
+        let hardware = hardware.lock();
+        assert_eq!(hardware.debug_output.len(), 2);
+        assert!(crate::test_utils::crudely_cmp_values(&hardware.debug_output[0], &AmlValue::Integer(0x2a)));
+        assert!(crate::test_utils::crudely_cmp_values(&hardware.debug_output[1], &AmlValue::string("AB")));
+    }
 }
@@ -81,3 +81,45 @@ where
         arg_parser(6, opcode::ARG6_OP)
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_utils::*, AmlError};
+
+    #[test]
+    fn test_local_obj() {
+        let mut context = make_test_context();
+
+        check_ok!(local_obj().parse(&[0x60], &mut context), 0, &[]);
+        check_ok!(local_obj().parse(&[0x67, 0x60], &mut context), 7, &[0x60]);
+        check_err!(local_obj().parse(&[0x68], &mut context), AmlError::WrongParser, &[0x68]);
+        check_err!(local_obj().parse(&[], &mut context), AmlError::UnexpectedEndOfStream, &[]);
+    }
+
+    #[test]
+    fn test_arg_obj() {
+        let mut context = make_test_context();
+
+        check_ok!(arg_obj().parse(&[0x68], &mut context), 0, &[]);
+        check_ok!(arg_obj().parse(&[0x6e, 0x68], &mut context), 6, &[0x68]);
+        check_err!(arg_obj().parse(&[0x6f], &mut context), AmlError::WrongParser, &[0x6f]);
+        check_err!(arg_obj().parse(&[0x67], &mut context), AmlError::WrongParser, &[0x67]);
+    }
+
+    #[test]
+    fn test_args_and_locals_outside_method() {
+        let mut context = make_test_context();
+
+        check_err!(
+            crate::term_object::term_arg().parse(&[0x68], &mut context),
+            AmlError::NotExecutingControlMethod,
+            &[0x68]
+        );
+        check_err!(
+            crate::term_object::term_arg().parse(&[0x60], &mut context),
+            AmlError::NotExecutingControlMethod,
+            &[0x60]
+        );
+    }
+}
@@ -1,10 +1,12 @@
 use crate::{
+    expression::reference_type_opcode,
     misc::{arg_obj, debug_obj, local_obj, ArgNum, LocalNum},
     namespace::{AmlName, NameComponent},
     opcode::{opcode, DUAL_NAME_PREFIX, MULTI_NAME_PREFIX, NULL_NAME, PREFIX_CHAR, ROOT_CHAR},
-    parser::{choice, comment_scope, consume, n_of, take, take_while, Parser, Propagate},
+    parser::{choice, comment_scope, n_of, take, take_n, take_while, Parser, Propagate},
     AmlContext,
     AmlError,
+    AmlValue,
     DebugVerbosity,
 };
 use alloc::vec::Vec;
@@ -18,6 +20,10 @@ pub enum Target {
     Debug,
     Arg(ArgNum),
     Local(LocalNum),
+    /// The object referred to by a reference, produced by a `ReferenceTypeOpcode` (`RefOf`, `DerefOf`, or
+    /// `Index`). The contained value is the reference itself: an `ObjectReference`, a `BufferField` produced by
+    /// indexing into a buffer, or a `String` containing the name of the object.
+    Reference(AmlValue),
 }
 
 pub fn target<'a, 'c>() -> impl Parser<'a, 'c, Target>
@@ -41,12 +47,11 @@ where
 {
     /*
      * SuperName := SimpleName | DebugObj | ReferenceTypeOpcode
-     * TODO: this doesn't cover ReferenceTypeOpcode yet
      */
     comment_scope(
         DebugVerbosity::AllScopes,
         "SuperName",
-        choice!(debug_obj().map(|()| Ok(Target::Debug)), simple_name()),
+        choice!(debug_obj().map(|()| Ok(Target::Debug)), reference_type_opcode(), simple_name()),
     )
 }
 
@@ -176,7 +181,7 @@ impl NameSeg {
     pub(crate) fn from_str(string: &str) -> Result<NameSeg, AmlError> {
         // Each NameSeg can only have four chars, and must have at least one
         if string.len() < 1 || string.len() > 4 {
-            return Err(AmlError::InvalidNameSeg);
+            return Err(AmlError::InvalidNameString);
         }
 
         // We pre-fill the array with '_', so it will already be correct if the length is < 4
@@ -185,14 +190,14 @@ impl NameSeg {
 
         // Manually do the first one, because we have to check it's a LeadNameChar
         if !is_lead_name_char(bytes[0]) {
-            return Err(AmlError::InvalidNameSeg);
+            return Err(AmlError::InvalidNameString);
         }
         seg[0] = bytes[0];
 
         // Copy the rest of the chars, checking that they're NameChars
         for i in 1..bytes.len() {
             if !is_name_char(bytes[i]) {
-                return Err(AmlError::InvalidNameSeg);
+                return Err(AmlError::InvalidNameString);
             }
             seg[i] = bytes[i];
         }
@@ -224,14 +229,19 @@ where
 {
     /*
      * NameSeg := <LeadNameChar NameChar NameChar NameChar>
+     *
+     * All four bytes are checked before any of them are consumed, so an invalid segment leaves the stream where it
+     * started.
      */
-    // TODO: can we write this better?
     move |input, context: &'c mut AmlContext| {
-        let (input, context, char_1) = consume(is_lead_name_char).parse(input, context)?;
-        let (input, context, char_2) = consume(is_name_char).parse(input, context)?;
-        let (input, context, char_3) = consume(is_name_char).parse(input, context)?;
-        let (input, context, char_4) = consume(is_name_char).parse(input, context)?;
-        Ok((input, context, NameSeg([char_1, char_2, char_3, char_4])))
+        let (new_input, context, bytes) = take_n(4).parse(input, context)?;
+        let seg = [bytes[0], bytes[1], bytes[2], bytes[3]];
+
+        if !is_lead_name_char(seg[0]) || !seg[1..].iter().all(|&byte| is_name_char(byte)) {
+            return Err((input, context, Propagate::Err(AmlError::InvalidNameSeg(seg))));
+        }
+
+        Ok((new_input, context, NameSeg(seg)))
     }
 }
 
@@ -268,9 +278,24 @@ mod tests {
         );
         check_err!(
             name_seg().parse(&[0xff, b'E', b'A', b'7'], &mut context),
-            AmlError::UnexpectedByte(0xff),
+            AmlError::InvalidNameSeg([0xff, b'E', b'A', b'7']),
             &[0xff, b'E', b'A', b'7']
         );
+        check_err!(
+            name_seg().parse(&[b'3', b'E', b'A', b'7'], &mut context),
+            AmlError::InvalidNameSeg([b'3', b'E', b'A', b'7']),
+            &[b'3', b'E', b'A', b'7']
+        );
+        check_err!(
+            name_seg().parse(&[b'_', b'S', b'B', b'a', b'_'], &mut context),
+            AmlError::InvalidNameSeg([b'_', b'S', b'B', b'a']),
+            &[b'_', b'S', b'B', b'a', b'_']
+        );
+        check_err!(
+            name_seg().parse(&[b'_', b'S', b'B'], &mut context),
+            AmlError::UnexpectedEndOfStream,
+            &[b'_', b'S', b'B']
+        );
         check_err!(name_seg().parse(&[], &mut context), AmlError::UnexpectedEndOfStream, &[]);
     }
 
@@ -290,6 +315,37 @@ mod tests {
             ],
             &[]
         );
+        check_err!(
+            name_path().parse(&[0x2f, 0x03, b'A', b'B', b'C', b'D', b'E', b'_', b'F', b'G'], &mut context),
+            AmlError::UnexpectedEndOfStream,
+            &[0x2f, 0x03, b'A', b'B', b'C', b'D', b'E', b'_', b'F', b'G']
+        );
+        check_ok!(
+            name_path().parse(
+                &[0x2f, 0x03, b'A', b'B', b'C', b'D', b'E', b'_', b'F', b'G', b'_', b'X', b'Y', b'Z', 0xff],
+                &mut context
+            ),
+            alloc::vec![
+                NameComponent::Segment(NameSeg([b'A', b'B', b'C', b'D'])),
+                NameComponent::Segment(NameSeg([b'E', b'_', b'F', b'G'])),
+                NameComponent::Segment(NameSeg([b'_', b'X', b'Y', b'Z']))
+            ],
+            &[0xff]
+        );
+    }
+
+    #[test]
+    fn test_multi_name_string() {
+        let mut context = crate::test_utils::make_test_context();
+
+        check_ok!(
+            name_string().parse(
+                &[b'\\', 0x2f, 0x03, b'_', b'S', b'B', b'_', b'P', b'C', b'I', b'0', b'G', b'F', b'X', b'0'],
+                &mut context
+            ),
+            AmlName::from_str("\\_SB.PCI0.GFX0").unwrap(),
+            &[]
+        );
     }
 
     #[test]
@@ -307,4 +363,70 @@ mod tests {
             &[]
         );
     }
+
+    #[test]
+    fn test_prefix_path_resolution() {
+        let mut context = crate::test_utils::make_test_context();
+
+        /*
+         * Scope(\_SB) {
+         *     Device(PCI0) {
+         *         Device(GFX0) {
+         *             Name(^^FOO, 1)
+         *         }
+         *     }
+         * }
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x10, 0x1c, b'\\', b'_', b'S', b'B', b'_', 0x5b, 0x82, 0x14, b'P', b'C', b'I', b'0', 0x5b, 0x82,
+                0x0d, b'G', b'F', b'X', b'0', 0x08, b'^', b'^', b'F', b'O', b'O', b'_', 0x01,
+            ]),
+            Ok(())
+        );
+        assert!(crudely_cmp_values(
+            context.namespace.get_by_path(&AmlName::from_str("\\_SB.FOO").unwrap()).unwrap(),
+            &crate::AmlValue::Integer(1)
+        ));
+        assert_eq!(
+            context.namespace.get_by_path(&AmlName::from_str("\\_SB.PCI0.GFX0.FOO").unwrap()).err(),
+            Some(AmlError::ValueDoesNotExist(AmlName::from_str("\\_SB.PCI0.GFX0.FOO").unwrap()))
+        );
+    }
+
+    #[test]
+    fn test_dual_and_multi_path_resolution() {
+        let mut context = crate::test_utils::make_test_context();
+
+        /*
+         * Scope(\_SB) {
+         *     Device(PCI0) {
+         *         Device(GFX0) { }
+         *     }
+         *     Name(PCI0.BAR, 2)
+         *     Name(PCI0.GFX0.FOO, 1)
+         * }
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x10, 0x30, b'\\', b'_', b'S', b'B', b'_', 0x5b, 0x82, 0x0c, b'P', b'C', b'I', b'0', 0x5b, 0x82,
+                0x05, b'G', b'F', b'X', b'0', 0x08, 0x2e, b'P', b'C', b'I', b'0', b'B', b'A', b'R', b'_', 0x0a,
+                0x02, 0x08, 0x2f, 0x03, b'P', b'C', b'I', b'0', b'G', b'F', b'X', b'0', b'F', b'O', b'O', b'_',
+                0x01,
+            ]),
+            Ok(())
+        );
+        assert!(crudely_cmp_values(
+            context.namespace.get_by_path(&AmlName::from_str("\\_SB.PCI0.BAR").unwrap()).unwrap(),
+            &crate::AmlValue::Integer(2)
+        ));
+        assert!(crudely_cmp_values(
+            context.namespace.get_by_path(&AmlName::from_str("\\_SB.PCI0.GFX0.FOO").unwrap()).unwrap(),
+            &crate::AmlValue::Integer(1)
+        ));
+        assert_eq!(
+            AmlName::from_str("PCI0.GFX0").unwrap().resolve(&AmlName::from_str("\\_SB").unwrap()),
+            AmlName::from_str("\\_SB.PCI0.GFX0")
+        );
+    }
 }
@@ -44,14 +44,22 @@ pub struct NamespaceLevel {
     pub typ: LevelType,
     pub children: BTreeMap<NameSeg, NamespaceLevel>,
     pub values: BTreeMap<NameSeg, AmlHandle>,
+    /// Names in this level that were created by `DefAlias`, mapped to the absolute paths of the objects they refer
+    /// to. These are resolved when they are looked up, so the object they refer to doesn't need to exist when the
+    /// alias is created.
+    pub aliases: BTreeMap<NameSeg, AmlName>,
 }
 
 impl NamespaceLevel {
     pub(crate) fn new(typ: LevelType) -> NamespaceLevel {
-        NamespaceLevel { typ, children: BTreeMap::new(), values: BTreeMap::new() }
+        NamespaceLevel { typ, children: BTreeMap::new(), values: BTreeMap::new(), aliases: BTreeMap::new() }
     }
 }
 
+/// The maximum number of aliases that will be followed when looking up a name. This stops us looping forever on
+/// aliases that (directly or indirectly) refer to themselves.
+const MAX_ALIAS_DEPTH: usize = 16;
+
 #[derive(Clone)]
 pub struct Namespace {
     /// This is a running count of ids, which are never reused. This is incremented every time we
@@ -59,6 +67,16 @@ pub struct Namespace {
     /// risking using the same id for two objects.
     next_handle: AmlHandle,
 
+    /// The first handle given out while parsing the current table. Objects with handles before this one were
+    /// defined by tables that have already been parsed, and so can be redefined by the current table (e.g. an SSDT
+    /// overriding an object in the DSDT).
+    table_start_handle: AmlHandle,
+
+    /// Whether an object can be redefined by the same table that first defined it. This is a firmware bug, so is
+    /// rejected with `AmlError::NameCollision` by default, but some tables rely on redefinitions replacing the
+    /// earlier object. Objects can always be redefined by later tables.
+    pub allow_redefinition: bool,
+
     /// This maps handles to actual values, and is used to access the actual AML values. When removing a value
     /// from the object map, care must be taken to also remove references to its handle in the level data
     /// structure, as invalid handles will cause panics.
@@ -74,6 +92,8 @@ impl Namespace {
     pub fn new() -> Namespace {
         Namespace {
             next_handle: AmlHandle(0),
+            table_start_handle: AmlHandle(0),
+            allow_redefinition: false,
             object_map: BTreeMap::new(),
             root: NamespaceLevel::new(LevelType::Scope),
         }
@@ -127,21 +147,53 @@ impl Namespace {
         }
     }
 
+    /// Mark the start of a new table. Objects added after this can redefine objects added before it, but not each
+    /// other.
+    pub(crate) fn begin_table(&mut self) {
+        self.table_start_handle = self.next_handle;
+    }
+
     /// Add a value to the namespace at the given path, which must be a normalized, absolute AML
     /// name. If you want to add at a path relative to a given scope, use `add_at_resolved_path`
     /// instead.
+    ///
+    /// Adding a value at a path that already holds one is an error (`AmlError::NameCollision`), unless the
+    /// existing value was defined by an earlier table, in which case the new value replaces it.
     pub fn add_value(&mut self, path: AmlName, value: AmlValue) -> Result<AmlHandle, AmlError> {
         assert!(path.is_absolute());
         let path = path.normalize()?;
 
-        let handle = self.next_handle;
-        self.next_handle.increment();
-        self.object_map.insert(handle, value);
+        let (level, last_seg) = self.get_level_for_path(&path)?;
+        if level.aliases.contains_key(&last_seg) {
+            return Err(AmlError::NameCollision(path));
+        }
 
-        let (level, last_seg) = self.get_level_for_path_mut(&path)?;
-        match level.values.insert(last_seg, handle) {
-            None => Ok(handle),
+        match level.values.get(&last_seg).copied() {
+            /*
+             * `External`s are placeholders for objects defined by other tables, so are replaced by the real object
+             * when it's added. We keep the placeholder's handle, so anything that has already looked it up sees the
+             * real object.
+             */
+            Some(handle) if matches!(self.object_map.get(&handle), Some(AmlValue::External { .. })) => {
+                self.object_map.insert(handle, value);
+                Ok(handle)
+            }
+            /*
+             * Later tables can redefine objects defined by earlier ones. `External`s are the only objects that
+             * should be redefined within a table, unless we've been told to allow it.
+             */
+            Some(handle) if handle < self.table_start_handle || self.allow_redefinition => {
+                self.object_map.insert(handle, value);
+                Ok(handle)
+            }
             Some(_) => Err(AmlError::NameCollision(path)),
+            None => {
+                let handle = self.next_handle;
+                self.next_handle.increment();
+                self.object_map.insert(handle, value);
+                self.get_level_for_path_mut(&path)?.0.values.insert(last_seg, handle);
+                Ok(handle)
+            }
         }
     }
 
@@ -157,6 +209,35 @@ impl Namespace {
         self.add_value(path.resolve(scope)?, value)
     }
 
+    /// Add an alias to the namespace at the given path, which refers to the object at `target`. Both paths must be
+    /// normalized, absolute AML names. The target is resolved every time the alias is looked up, so does not need
+    /// to exist yet.
+    pub fn add_alias(&mut self, path: AmlName, target: AmlName) -> Result<(), AmlError> {
+        assert!(path.is_absolute());
+        assert!(target.is_absolute());
+        let path = path.normalize()?;
+        let target = target.normalize()?;
+
+        let (level, last_seg) = self.get_level_for_path_mut(&path)?;
+        if level.values.contains_key(&last_seg) || level.aliases.contains_key(&last_seg) {
+            return Err(AmlError::NameCollision(path));
+        }
+
+        level.aliases.insert(last_seg, target);
+        Ok(())
+    }
+
+    /// Helper method for adding an alias to the namespace, where both the alias and its target are relative to the
+    /// given scope.
+    pub fn add_alias_at_resolved_path(
+        &mut self,
+        path: AmlName,
+        scope: &AmlName,
+        target: AmlName,
+    ) -> Result<(), AmlError> {
+        self.add_alias(path.resolve(scope)?, target.resolve(scope)?)
+    }
+
     pub fn get(&self, handle: AmlHandle) -> Result<&AmlValue, AmlError> {
         Ok(self.object_map.get(&handle).unwrap())
     }
@@ -167,7 +248,29 @@ impl Namespace {
 
     pub fn get_handle(&self, path: &AmlName) -> Result<AmlHandle, AmlError> {
         let (level, last_seg) = self.get_level_for_path(path)?;
-        Ok(*level.values.get(&last_seg).ok_or(AmlError::ValueDoesNotExist(path.clone()))?)
+        if let Some(&handle) = level.values.get(&last_seg) {
+            return Ok(handle);
+        }
+        match level.aliases.get(&last_seg) {
+            Some(target) => Ok(self.resolve_alias(target)?.1),
+            None => Err(AmlError::ValueDoesNotExist(path.clone())),
+        }
+    }
+
+    /// Find the object an alias refers to, following any further aliases. Returns the absolute path and handle of
+    /// the object.
+    fn resolve_alias(&self, target: &AmlName) -> Result<(AmlName, AmlHandle), AmlError> {
+        let mut target = target.clone();
+
+        for _ in 0..MAX_ALIAS_DEPTH {
+            let (level, last_seg) = self.get_level_for_path(&target)?;
+            if let Some(&handle) = level.values.get(&last_seg) {
+                return Ok((target, handle));
+            }
+            target = level.aliases.get(&last_seg).ok_or(AmlError::ValueDoesNotExist(target.clone()))?.clone();
+        }
+
+        Err(AmlError::ValueDoesNotExist(target))
     }
 
     pub fn get_by_path(&self, path: &AmlName) -> Result<&AmlValue, AmlError> {
@@ -200,6 +303,9 @@ impl Namespace {
                         if let Some(&handle) = level.values.get(&last_seg) {
                             return Ok((name, handle));
                         }
+                        if let Some(target) = level.aliases.get(&last_seg) {
+                            return self.resolve_alias(target);
+                        }
                     }
 
                     /*
@@ -234,6 +340,8 @@ impl Namespace {
 
             if let Some(&handle) = level.values.get(&last_seg) {
                 Ok((name, handle))
+            } else if let Some(target) = level.aliases.get(&last_seg) {
+                self.resolve_alias(target)
             } else {
                 Err(AmlError::ValueDoesNotExist(path.clone()))
             }
@@ -266,10 +374,8 @@ impl Namespace {
     }
 
     fn get_level_for_path(&self, path: &AmlName) -> Result<(&NamespaceLevel, NameSeg), AmlError> {
-        assert_ne!(*path, AmlName::root());
-
-        let (last_seg, levels) = path.0[1..].split_last().unwrap();
-        let last_seg = last_seg.as_segment().unwrap();
+        let (last_seg, levels) = path.0[1..].split_last().ok_or(AmlError::RootHasNoParent)?;
+        let last_seg = last_seg.as_segment().map_err(|()| AmlError::InvalidNormalizedName(path.clone()))?;
 
         // TODO: this helps with diagnostics, but requires a heap allocation just in case we need to error.
         let mut traversed_path = AmlName::root();
@@ -279,7 +385,7 @@ impl Namespace {
             traversed_path.0.push(*level);
             current_level = current_level
                 .children
-                .get(&level.as_segment().unwrap())
+                .get(&level.as_segment().map_err(|()| AmlError::InvalidNormalizedName(path.clone()))?)
                 .ok_or(AmlError::LevelDoesNotExist(traversed_path.clone()))?;
         }
 
@@ -287,12 +393,10 @@ impl Namespace {
     }
 
     /// Split an absolute path into a bunch of level segments (used to traverse the level data structure), and a
-    /// last segment to index into that level. This must not be called on `\\`.
+    /// last segment to index into that level. Returns `AmlError::RootHasNoParent` if called on `\\`.
     fn get_level_for_path_mut(&mut self, path: &AmlName) -> Result<(&mut NamespaceLevel, NameSeg), AmlError> {
-        assert_ne!(*path, AmlName::root());
-
-        let (last_seg, levels) = path.0[1..].split_last().unwrap();
-        let last_seg = last_seg.as_segment().unwrap();
+        let (last_seg, levels) = path.0[1..].split_last().ok_or(AmlError::RootHasNoParent)?;
+        let last_seg = last_seg.as_segment().map_err(|()| AmlError::InvalidNormalizedName(path.clone()))?;
 
         // TODO: this helps with diagnostics, but requires a heap allocation just in case we need to error. We can
         // improve this by changing the `levels` interation into an `enumerate()`, and then using the index to
@@ -304,7 +408,7 @@ impl Namespace {
             traversed_path.0.push(*level);
             current_level = current_level
                 .children
-                .get_mut(&level.as_segment().unwrap())
+                .get_mut(&level.as_segment().map_err(|()| AmlError::InvalidNormalizedName(path.clone()))?)
                 .ok_or(AmlError::LevelDoesNotExist(traversed_path.clone()))?;
         }
 
@@ -339,6 +443,50 @@ impl Namespace {
 
         Ok(())
     }
+
+    /// Iterate over every object in the namespace, yielding the absolute path of each object along with its value.
+    /// Objects are yielded in order of their paths, so the order is the same for two namespaces containing the
+    /// same objects. Aliases are not yielded, as they don't hold objects of their own.
+    pub fn iter(&self) -> impl Iterator<Item = (AmlName, &AmlValue)> {
+        let mut objects = Vec::new();
+        let mut levels = alloc::vec![(AmlName::root(), &self.root)];
+
+        while let Some((scope, level)) = levels.pop() {
+            for (name, handle) in level.values.iter() {
+                objects.push((scope.with_segment(*name), self.object_map.get(handle).unwrap()));
+            }
+
+            for (name, child) in level.children.iter() {
+                levels.push((scope.with_segment(*name), child));
+            }
+        }
+
+        objects.sort_by(|(a, _), (b, _)| a.cmp(b));
+        objects.into_iter()
+    }
+
+    /// Iterate over the objects directly inside the level at `scope`, which must be a normalized, absolute AML
+    /// name, yielding the absolute path of each object along with its value. Objects are yielded in order of
+    /// their names. Objects in sub-levels (such as the objects inside a `Device`) are not yielded.
+    pub fn iter_children<'a>(
+        &'a self,
+        scope: &AmlName,
+    ) -> Result<impl Iterator<Item = (AmlName, &'a AmlValue)> + 'a, AmlError> {
+        assert!(scope.is_absolute());
+
+        let level = if *scope == AmlName::root() {
+            &self.root
+        } else {
+            let (parent, last_seg) = self.get_level_for_path(scope)?;
+            parent.children.get(&last_seg).ok_or(AmlError::LevelDoesNotExist(scope.clone()))?
+        };
+
+        let scope = scope.clone();
+        Ok(level
+            .values
+            .iter()
+            .map(move |(name, handle)| (scope.with_segment(*name), self.object_map.get(handle).unwrap())))
+    }
 }
 
 impl fmt::Debug for Namespace {
@@ -365,6 +513,66 @@ impl fmt::Debug for Namespace {
                 )?;
             }
 
+            for (name, target) in level.aliases.iter() {
+                writeln!(
+                    f,
+                    "{:indent$}{}: Alias({})",
+                    "",
+                    name.as_str(),
+                    target,
+                    indent = indent + INDENT_PER_LEVEL
+                )?;
+            }
+
+            for (name, sub_level) in level.children.iter() {
+                print_level(namespace, f, name.as_str(), sub_level, indent + INDENT_PER_LEVEL)?;
+            }
+
+            Ok(())
+        }
+
+        print_level(self, f, "\\", &self.root, 0)
+    }
+}
+
+/// Prints the namespace as an indented tree, with one line for each object and level. This is more compact than the
+/// `Debug` output, and is intended to make it easy to see the structure of the namespace when debugging.
+impl fmt::Display for Namespace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const INDENT_PER_LEVEL: usize = 4;
+
+        fn print_level(
+            namespace: &Namespace,
+            f: &mut fmt::Formatter<'_>,
+            level_name: &str,
+            level: &NamespaceLevel,
+            indent: usize,
+        ) -> fmt::Result {
+            writeln!(f, "{:indent$}{} ({:?}):", "", level_name, level.typ, indent = indent)?;
+
+            for (name, handle) in level.values.iter() {
+                /*
+                 * Objects like `Device`s also have a level of the same name, which holds their contents. We only
+                 * print these objects as levels, to avoid showing them twice.
+                 */
+                if level.children.contains_key(name) {
+                    continue;
+                }
+
+                writeln!(
+                    f,
+                    "{:indent$}{} = {}",
+                    "",
+                    name.as_str(),
+                    namespace.object_map.get(handle).unwrap(),
+                    indent = indent + INDENT_PER_LEVEL
+                )?;
+            }
+
+            for (name, target) in level.aliases.iter() {
+                writeln!(f, "{:indent$}{} -> {}", "", name.as_str(), target, indent = indent + INDENT_PER_LEVEL)?;
+            }
+
             for (name, sub_level) in level.children.iter() {
                 print_level(namespace, f, name.as_str(), sub_level, indent + INDENT_PER_LEVEL)?;
             }
@@ -507,6 +715,13 @@ impl AmlName {
         }
     }
 
+    /// Get the path of the object called `seg` inside the level with this path.
+    fn with_segment(&self, seg: NameSeg) -> AmlName {
+        let mut path = self.clone();
+        path.0.push(NameComponent::Segment(seg));
+        path
+    }
+
     /// Resolve this path against a given scope, making it absolute. If the path is absolute, it is
     /// returned directly. The path is also normalized.
     pub fn resolve(&self, scope: &AmlName) -> Result<AmlName, AmlError> {
@@ -640,6 +855,21 @@ mod tests {
         assert_eq!(AmlName::from_str("\\_SB.PCI0").unwrap().parent().unwrap().parent(), Ok(AmlName::root()));
     }
 
+    #[test]
+    fn test_aml_name_resolve() {
+        let scope = AmlName::from_str("\\_SB.PCI0.GFX0").unwrap();
+
+        assert_eq!(AmlName::from_str("FOO").unwrap().resolve(&scope), AmlName::from_str("\\_SB.PCI0.GFX0.FOO"));
+        assert_eq!(AmlName::from_str("^FOO").unwrap().resolve(&scope), AmlName::from_str("\\_SB.PCI0.FOO"));
+        assert_eq!(AmlName::from_str("^^FOO").unwrap().resolve(&scope), AmlName::from_str("\\_SB.FOO"));
+        assert_eq!(AmlName::from_str("^^^FOO.BAR").unwrap().resolve(&scope), AmlName::from_str("\\FOO.BAR"));
+        assert_eq!(
+            AmlName::from_str("^^^^FOO").unwrap().resolve(&scope),
+            Err(AmlError::InvalidNormalizedName(AmlName::from_str("\\_SB.PCI0.GFX0.^^^^FOO").unwrap()))
+        );
+        assert_eq!(AmlName::from_str("\\_GPE").unwrap().resolve(&scope), AmlName::from_str("\\_GPE"));
+    }
+
     #[test]
     fn test_namespace() {
         let mut namespace = Namespace::new();
@@ -743,4 +973,73 @@ mod tests {
             assert_eq!(last_seg, NameSeg::from_str("FOO").unwrap());
         }
     }
+
+    #[test]
+    fn test_iter() {
+        let mut namespace = Namespace::new();
+
+        assert_eq!(namespace.add_level(AmlName::from_str("\\_SB").unwrap(), LevelType::Scope), Ok(()));
+        assert_eq!(namespace.add_level(AmlName::from_str("\\_SB.PCI0").unwrap(), LevelType::Device), Ok(()));
+        assert_eq!(namespace.add_level(AmlName::from_str("\\FOO").unwrap(), LevelType::Scope), Ok(()));
+
+        assert!(namespace.add_value(AmlName::from_str("\\_SB.PCI0._ADR").unwrap(), AmlValue::Integer(0)).is_ok());
+        assert!(namespace.add_value(AmlName::from_str("\\_SB.PCI0._UID").unwrap(), AmlValue::Integer(1)).is_ok());
+        assert!(namespace.add_value(AmlName::from_str("\\_SB.BAR").unwrap(), AmlValue::Integer(2)).is_ok());
+        assert!(namespace.add_value(AmlName::from_str("\\FOO.BAZ").unwrap(), AmlValue::Integer(3)).is_ok());
+        assert!(namespace.add_value(AmlName::from_str("\\MOO").unwrap(), AmlValue::Boolean(true)).is_ok());
+        assert_eq!(
+            namespace.add_alias(AmlName::from_str("\\ALIA").unwrap(), AmlName::from_str("\\MOO").unwrap()),
+            Ok(())
+        );
+
+        let names: Vec<String> = namespace.iter().map(|(name, _)| name.as_string()).collect();
+        assert_eq!(names, ["\\FOO_.BAZ_", "\\MOO_", "\\_SB_.BAR_", "\\_SB_.PCI0._ADR", "\\_SB_.PCI0._UID"]);
+        assert!(namespace
+            .iter()
+            .all(|(name, value)| crudely_cmp_values(namespace.get_by_path(&name).unwrap(), value)));
+
+        let names: Vec<String> = namespace
+            .iter_children(&AmlName::from_str("\\_SB").unwrap())
+            .unwrap()
+            .map(|(name, _)| name.as_string())
+            .collect();
+        assert_eq!(names, ["\\_SB_.BAR_"]);
+
+        let names: Vec<String> =
+            namespace.iter_children(&AmlName::root()).unwrap().map(|(name, _)| name.as_string()).collect();
+        assert_eq!(names, ["\\MOO_"]);
+
+        assert!(namespace.iter_children(&AmlName::from_str("\\_SB.PCI1").unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_display() {
+        let mut namespace = Namespace::new();
+
+        assert_eq!(namespace.add_level(AmlName::from_str("\\_SB").unwrap(), LevelType::Scope), Ok(()));
+        assert_eq!(namespace.add_level(AmlName::from_str("\\_SB.PCI0").unwrap(), LevelType::Device), Ok(()));
+        assert!(namespace.add_value(AmlName::from_str("\\_SB.PCI0").unwrap(), AmlValue::Device).is_ok());
+        assert!(namespace
+            .add_value(AmlName::from_str("\\_SB.PCI0._HID").unwrap(), AmlValue::Integer(0x030ad041))
+            .is_ok());
+        assert!(namespace
+            .add_value(AmlName::from_str("\\FOO").unwrap(), AmlValue::String(String::from("hello")))
+            .is_ok());
+        assert_eq!(
+            namespace.add_alias(AmlName::from_str("\\BAR").unwrap(), AmlName::from_str("\\FOO").unwrap()),
+            Ok(())
+        );
+
+        assert_eq!(
+            namespace.to_string(),
+            concat!(
+                "\\ (Scope):\n",
+                "    FOO_ = \"hello\"\n",
+                "    BAR_ -> \\FOO_\n",
+                "    _SB_ (Scope):\n",
+                "        PCI0 (Device):\n",
+                "            _HID = 0x30ad041\n",
+            )
+        );
+    }
 }
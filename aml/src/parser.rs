@@ -119,6 +119,18 @@ where
     }
 }
 
+/// Produces the byte `n` bytes into the input, without consuming anything. Fails with `UnexpectedEndOfStream` if
+/// the input isn't long enough.
+pub fn peek_ahead<'a, 'c>(n: u32) -> impl Parser<'a, 'c, u8>
+where
+    'c: 'a,
+{
+    move |input: &'a [u8], context: &'c mut AmlContext| match input.get(n as usize) {
+        Some(&byte) => Ok((input, context, byte)),
+        None => Err((input, context, Propagate::Err(AmlError::UnexpectedEndOfStream))),
+    }
+}
+
 pub fn take_u16<'a, 'c>() -> impl Parser<'a, 'c, u16>
 where
     'c: 'a,
@@ -158,6 +170,35 @@ where
     }
 }
 
+/*
+ * The signed readers reinterpret the two's-complement encoding read by the unsigned ones, so are bounds-checked
+ * in the same way. Nothing in the AML grammar or the resource descriptors we parse is signed yet, so these are only
+ * used by tests for now.
+ */
+#[allow(dead_code)]
+pub fn take_i16<'a, 'c>() -> impl Parser<'a, 'c, i16>
+where
+    'c: 'a,
+{
+    take_u16().map(|value| Ok(value as i16))
+}
+
+#[allow(dead_code)]
+pub fn take_i32<'a, 'c>() -> impl Parser<'a, 'c, i32>
+where
+    'c: 'a,
+{
+    take_u32().map(|value| Ok(value as i32))
+}
+
+#[allow(dead_code)]
+pub fn take_i64<'a, 'c>() -> impl Parser<'a, 'c, i64>
+where
+    'c: 'a,
+{
+    take_u64().map(|value| Ok(value as i64))
+}
+
 pub fn take_n<'a, 'c>(n: u32) -> impl Parser<'a, 'c, &'a [u8]>
 where
     'c: 'a,
@@ -178,8 +219,9 @@ where
 {
     move |input: &'a [u8], context| {
         /*
-         * TODO: fuzzing manages to find PkgLengths that correctly parse during construction, but later crash here.
-         * I would've thought we would pick up all invalid lengths there, so have a look at why this is needed.
+         * `pkg_length` makes sure the end of the structure isn't before the end of the `PkgLength` itself, but
+         * other objects (e.g. the `NameString` of a `DefScope`) can be parsed between the two, and may extend past
+         * the end of the structure in malformed streams, so we still need to check this.
          */
         let bytes_to_take = match (input.len() as u32).checked_sub(length.end_offset) {
             Some(bytes_to_take) => bytes_to_take,
@@ -189,6 +231,27 @@ where
     }
 }
 
+/// Run `parser` on only the part of the stream that is within the structure described by `length`, so that it
+/// can't read past the end of the structure - trying to do so produces `AmlError::UnexpectedEndOfStream`. Any of
+/// the structure that `parser` doesn't consume is left in the returned stream.
+pub fn within_pkglength<'a, 'c, P, R>(length: PkgLength, parser: P) -> impl Parser<'a, 'c, R>
+where
+    'c: 'a,
+    P: Parser<'a, 'c, R>,
+{
+    move |input: &'a [u8], context| {
+        let bytes_to_take = match (input.len() as u32).checked_sub(length.end_offset) {
+            Some(bytes_to_take) => bytes_to_take as usize,
+            None => return Err((input, context, Propagate::Err(AmlError::InvalidPkgLength))),
+        };
+
+        match parser.parse(&input[..bytes_to_take], context) {
+            Ok((remaining, context, result)) => Ok((&input[(bytes_to_take - remaining.len())..], context, result)),
+            Err((_, context, err)) => Err((input, context, err)),
+        }
+    }
+}
+
 pub fn n_of<'a, 'c, P, R>(parser: P, n: usize) -> impl Parser<'a, 'c, Vec<R>>
 where
     'c: 'a,
@@ -235,18 +298,6 @@ where
     }
 }
 
-pub fn consume<'a, 'c, F>(condition: F) -> impl Parser<'a, 'c, u8>
-where
-    'c: 'a,
-    F: Fn(u8) -> bool,
-{
-    move |input: &'a [u8], context: &'c mut AmlContext| match input.first() {
-        Some(&byte) if condition(byte) => Ok((&input[1..], context, byte)),
-        Some(&byte) => Err((input, context, Propagate::Err(AmlError::UnexpectedByte(byte)))),
-        None => Err((input, context, Propagate::Err(AmlError::UnexpectedEndOfStream))),
-    }
-}
-
 pub fn comment_scope<'a, 'c, P, R>(
     verbosity: DebugVerbosity,
     scope_name: &'a str,
@@ -504,6 +555,29 @@ mod tests {
     use super::*;
     use crate::test_utils::*;
 
+    #[test]
+    fn test_take() {
+        let mut context = make_test_context();
+        check_ok!(take().parse(&[0x34, 0x12], &mut context), 0x34, &[0x12]);
+        check_ok!(take().parse(&[0x12], &mut context), 0x12, &[]);
+        check_err!(take().parse(&[], &mut context), AmlError::UnexpectedEndOfStream, &[]);
+        check_ok!(n_of(take(), 3).parse(&[0x01, 0x02, 0x03], &mut context), alloc::vec![0x01, 0x02, 0x03], &[]);
+        check_err!(n_of(take(), 4).parse(&[0x01, 0x02, 0x03], &mut context), AmlError::UnexpectedEndOfStream, &[]);
+    }
+
+    #[test]
+    fn test_peek_ahead() {
+        let mut context = make_test_context();
+        check_ok!(peek_ahead(0).parse(&[0x5b, 0x80], &mut context), 0x5b, &[0x5b, 0x80]);
+        check_ok!(peek_ahead(1).parse(&[0x5b, 0x80], &mut context), 0x80, &[0x5b, 0x80]);
+        check_err!(
+            peek_ahead(2).parse(&[0x5b, 0x80], &mut context),
+            AmlError::UnexpectedEndOfStream,
+            &[0x5b, 0x80]
+        );
+        check_err!(peek_ahead(0).parse(&[], &mut context), AmlError::UnexpectedEndOfStream, &[]);
+    }
+
     #[test]
     fn test_take_n() {
         let mut context = make_test_context();
@@ -523,6 +597,11 @@ mod tests {
 
         check_err!(take_u32().parse(&[0x34, 0x12], &mut context), AmlError::UnexpectedEndOfStream, &[0x34, 0x12]);
         check_ok!(take_u32().parse(&[0x34, 0x12, 0xf4, 0xc3, 0x3e], &mut context), 0xc3f41234, &[0x3e]);
+        check_ok!(
+            take_u32().parse(&[0x78, 0x56, 0x34, 0x12, 0x0c, 0x01], &mut context),
+            0x12345678,
+            &[0x0c, 0x01]
+        );
 
         check_err!(take_u64().parse(&[0x34], &mut context), AmlError::UnexpectedEndOfStream, &[0x34]);
         check_ok!(
@@ -531,4 +610,45 @@ mod tests {
             &[0xff, 0x00]
         );
     }
+
+    #[test]
+    fn test_take_ix() {
+        let mut context = make_test_context();
+        check_err!(take_i16().parse(&[0xfe], &mut context), AmlError::UnexpectedEndOfStream, &[0xfe]);
+        check_ok!(take_i16().parse(&[0xfe, 0xff], &mut context), -2, &[]);
+        check_ok!(take_i16().parse(&[0x00, 0x80, 0x01], &mut context), i16::MIN, &[0x01]);
+        check_ok!(take_i16().parse(&[0x2e, 0xfb], &mut context), -1234, &[]);
+
+        check_err!(take_i32().parse(&[0xff, 0xff], &mut context), AmlError::UnexpectedEndOfStream, &[0xff, 0xff]);
+        check_ok!(take_i32().parse(&[0x78, 0x56, 0x34, 0x12], &mut context), 0x12345678, &[]);
+        check_ok!(take_i32().parse(&[0x88, 0xa9, 0xcb, 0xed], &mut context), -0x12345678, &[]);
+
+        check_err!(take_i64().parse(&[0xff; 7], &mut context), AmlError::UnexpectedEndOfStream, &[0xff; 7]);
+        check_ok!(take_i64().parse(&[0xff; 8], &mut context), -1, &[]);
+        check_ok!(
+            take_i64().parse(&[0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x80], &mut context),
+            i64::MIN,
+            &[]
+        );
+    }
+
+    #[test]
+    fn test_within_pkglength() {
+        let mut context = make_test_context();
+        let stream = [0x01, 0x02, 0x03, 0x04, 0x05];
+        let length = PkgLength::from_raw_length(&stream, 2).unwrap();
+
+        check_ok!(within_pkglength(length, take_u16()).parse(&stream, &mut context), 0x0201, &[0x03, 0x04, 0x05]);
+        check_ok!(within_pkglength(length, take()).parse(&stream, &mut context), 0x01, &[0x02, 0x03, 0x04, 0x05]);
+        check_err!(
+            within_pkglength(length, take_u32()).parse(&stream, &mut context),
+            AmlError::UnexpectedEndOfStream,
+            &[0x01, 0x02, 0x03, 0x04, 0x05]
+        );
+        check_err!(
+            within_pkglength(length, take_u16()).parse(&stream[4..], &mut context),
+            AmlError::InvalidPkgLength,
+            &[0x05]
+        );
+    }
 }
@@ -36,6 +36,14 @@ where
     move |input: &'a [u8], context: &'c mut AmlContext| -> crate::parser::ParseResult<'a, 'c, PkgLength> {
         let (new_input, context, raw_length) = raw_pkg_length().parse(input, context)?;
 
+        /*
+         * The raw length includes the bytes used to encode the `PkgLength` itself, so a length shorter than that
+         * would put the end of the structure before the point we've already parsed up to.
+         */
+        if raw_length < (input.len() - new_input.len()) as u32 {
+            return Err((input, context, Propagate::Err(AmlError::InvalidPkgLength)));
+        }
+
         /*
          * NOTE: we use the original input here, because `raw_length` includes the length of the
          * `PkgLength`.
@@ -122,7 +130,7 @@ mod tests {
     fn test_pkg_length() {
         let mut context = make_test_context();
         check_err!(pkg_length().parse(&[], &mut context), AmlError::UnexpectedEndOfStream, &[]);
-        test_correct_pkglength(&[0x00], 0, &[]);
+        test_correct_pkglength(&[0x01], 1, &[]);
         test_correct_pkglength(&[0x05, 0xf5, 0x7f, 0x3e, 0x54, 0x03], 5, &[0xf5, 0x7f, 0x3e, 0x54, 0x03]);
 
         check_ok!(
@@ -148,5 +156,22 @@ mod tests {
     fn not_enough_stream() {
         let mut context = make_test_context();
         check_err!(pkg_length().parse(&[0x05, 0xf5], &mut context), AmlError::InvalidPkgLength, &[0x05, 0xf5]);
+        check_err!(
+            pkg_length().parse(&[0b10001111, 0xff, 0xff, 0x01, 0x02], &mut context),
+            AmlError::InvalidPkgLength,
+            &[0b10001111, 0xff, 0xff, 0x01, 0x02]
+        );
+    }
+
+    #[test]
+    fn pkglength_shorter_than_itself() {
+        let mut context = make_test_context();
+        check_err!(pkg_length().parse(&[0x00, 0xf5], &mut context), AmlError::InvalidPkgLength, &[0x00, 0xf5]);
+        check_err!(
+            pkg_length().parse(&[0b01000001, 0x00, 0xf5], &mut context),
+            AmlError::InvalidPkgLength,
+            &[0b01000001, 0x00, 0xf5]
+        );
+        test_correct_pkglength(&[0b01000010, 0x00, 0xf5], 2, &[0xf5]);
     }
 }
@@ -18,7 +18,7 @@ pub enum Resource {
 }
 
 /// Parse a `ResourceDescriptor` into a list of resources. Returns `AmlError::IncompatibleValueConversion` if the passed value is not a
-/// `Buffer`.
+/// `Buffer`, and `AmlError::InvalidResourceDescriptorChecksum` if the End Tag's checksum is incorrect.
 pub fn resource_descriptor_list(descriptor: &AmlValue) -> Result<Vec<Resource>, AmlError> {
     if let AmlValue::Buffer(bytes) = descriptor {
         let mut descriptors = Vec::new();
@@ -32,6 +32,19 @@ pub fn resource_descriptor_list(descriptor: &AmlValue) -> Result<Vec<Resource>,
                 descriptors.push(descriptor);
                 bytes = remaining_bytes;
             } else {
+                /*
+                 * We've reached the End Tag. Its second byte is a checksum that makes the bytes of the entire
+                 * resource template (including the End Tag) sum to zero, or zero if the template has not been
+                 * checksummed.
+                 */
+                let end_tag_offset = buffer_data.len() - bytes.len();
+                if bytes[1] != 0 {
+                    let sum =
+                        buffer_data[0..(end_tag_offset + 2)].iter().fold(0u8, |sum, &byte| sum.wrapping_add(byte));
+                    if sum != 0 {
+                        return Err(AmlError::InvalidResourceDescriptorChecksum);
+                    }
+                }
                 break;
             }
         }
@@ -75,8 +88,15 @@ fn resource_descriptor(bytes: &[u8]) -> Result<(Option<Resource>, &[u8]), AmlErr
          * Byte 1 contains bits 0-7 of the length, and Byte 2 contains bits 8-15 of the length. Subsequent
          * bytes contain the actual data items.
          */
+        if bytes.len() < 3 {
+            return Err(AmlError::ResourceDescriptorTooShort);
+        }
+
         let descriptor_type = bytes[0].get_bits(0..7);
         let length = LittleEndian::read_u16(&bytes[1..=2]) as usize;
+        if bytes.len() < length + 3 {
+            return Err(AmlError::ResourceDescriptorTooShort);
+        }
         let (descriptor_bytes, remaining_bytes) = bytes.split_at(length + 3);
 
         let descriptor = match descriptor_type {
@@ -84,7 +104,7 @@ fn resource_descriptor(bytes: &[u8]) -> Result<(Option<Resource>, &[u8]), AmlErr
             0x02 => unimplemented!("Generic Register Descriptor"),
             0x03 => unimplemented!("0x03 Reserved"),
             0x04 => unimplemented!("Vendor-defined Descriptor"),
-            0x05 => unimplemented!("32-bit Memory Range Descriptor"),
+            0x05 => memory_range_descriptor(descriptor_bytes),
             0x06 => fixed_memory_descriptor(descriptor_bytes),
             0x07 => address_space_descriptor::<u32>(descriptor_bytes),
             0x08 => address_space_descriptor::<u16>(descriptor_bytes),
@@ -128,6 +148,9 @@ fn resource_descriptor(bytes: &[u8]) -> Result<(Option<Resource>, &[u8]), AmlErr
          */
         let descriptor_type = bytes[0].get_bits(3..=6);
         let length: usize = bytes[0].get_bits(0..=2) as usize;
+        if bytes.len() < length + 1 {
+            return Err(AmlError::ResourceDescriptorTooShort);
+        }
         let (descriptor_bytes, remaining_bytes) = bytes.split_at(length + 1);
 
         let descriptor = match descriptor_type {
@@ -141,6 +164,8 @@ fn resource_descriptor(bytes: &[u8]) -> Result<(Option<Resource>, &[u8]), AmlErr
             0x0A => unimplemented!("Fixed DMA Descriptor"),
             0x0B..=0x0D => Err(AmlError::ReservedResourceType),
             0x0E => unimplemented!("Vendor Defined Descriptor"),
+            // The End Tag must contain its checksum byte
+            0x0F if length == 0 => Err(AmlError::ResourceDescriptorTooShort),
             0x0F => return Ok((None, &[])),
             0x10..=0xFF => unreachable!(),
         }?;
@@ -190,6 +215,48 @@ pub struct AddressSpaceDescriptor {
 #[derive(Debug, PartialEq, Eq)]
 pub enum MemoryRangeDescriptor {
     FixedLocation { is_writable: bool, base_address: u32, range_length: u32 },
+    Range { is_writable: bool, address_range: (u32, u32), base_alignment: u32, range_length: u32 },
+}
+
+fn memory_range_descriptor(bytes: &[u8]) -> Result<Resource, AmlError> {
+    /*
+     * -- 32-bit Memory Range Descriptor ---
+     * Offset     Field Name                                  Definition
+     * Byte 0     32-bit Memory Range Descriptor              Value = 0x85 (10000101B) – Type = 1, Large item name = 0x05
+     * Byte 1     Length, bits [7:0]                          Value = 0x11 (17)
+     * Byte 2     Length, bits [15:8]                         Value = 0x00
+     * Byte 3     Information                                 This field provides extra information about this memory.
+     *                                                        Bit [7:1]   Ignored
+     *                                                        Bit [0]     Write status, _RW
+     *                                                            1  writeable (read/write)
+     *                                                            0  non-writeable (read-only)
+     * Byte 4-7   Range minimum base address, _MIN            Minimum base memory address for which the card may be configured.
+     * Byte 8-11  Range maximum base address, _MAX            Maximum base memory address for which the card may be configured.
+     * Byte 12-15 Base alignment, _ALN                        This field contains the base alignment of the memory range. The range may start on any multiple of this value.
+     * Byte 16-19 Range length, _LEN                          This field contains the length of the memory range in 1-byte blocks.
+     */
+    if bytes.len() < 20 {
+        return Err(AmlError::ResourceDescriptorTooShort);
+    }
+
+    if bytes.len() > 20 {
+        return Err(AmlError::ResourceDescriptorTooLong);
+    }
+
+    let information = bytes[3];
+    let is_writable = information.get_bit(0);
+
+    let address_range_min = LittleEndian::read_u32(&bytes[4..=7]);
+    let address_range_max = LittleEndian::read_u32(&bytes[8..=11]);
+    let base_alignment = LittleEndian::read_u32(&bytes[12..=15]);
+    let range_length = LittleEndian::read_u32(&bytes[16..=19]);
+
+    Ok(Resource::MemoryRange(MemoryRangeDescriptor::Range {
+        is_writable,
+        address_range: (address_range_min, address_range_max),
+        base_alignment,
+        range_length,
+    }))
 }
 
 fn fixed_memory_descriptor(bytes: &[u8]) -> Result<Resource, AmlError> {
@@ -847,4 +914,70 @@ mod tests {
             ])
         );
     }
+
+    #[test]
+    fn test_memory_crs() {
+        let bytes: Vec<u8> = [
+            // Memory32 (ReadWrite, 0xFED40000, 0xFED40000, 0x00001000, 0x00005000, )
+            0x85, 0x11, 0x00, 0x01, 0x00, 0x00, 0xD4, 0xFE, 0x00, 0x00, 0xD4, 0xFE, 0x00, 0x10, 0x00, 0x00, 0x00,
+            0x50, 0x00, 0x00, // Memory32Fixed (ReadOnly, 0xFEC00000, 0x00001000, )
+            0x86, 0x09, 0x00, 0x00, 0x00, 0x00, 0xC0, 0xFE, 0x00, 0x10, 0x00, 0x00,
+            // EndTag, with a checksum that makes the template sum to zero
+            0x79, 0x8F,
+        ]
+        .to_vec();
+
+        let value: AmlValue = AmlValue::Buffer(Arc::new(spinning_top::Spinlock::new(bytes)));
+        let resources = resource_descriptor_list(&value).unwrap();
+
+        assert_eq!(
+            resources,
+            Vec::from([
+                Resource::MemoryRange(MemoryRangeDescriptor::Range {
+                    is_writable: true,
+                    address_range: (0xFED40000, 0xFED40000),
+                    base_alignment: 0x1000,
+                    range_length: 0x5000
+                }),
+                Resource::MemoryRange(MemoryRangeDescriptor::FixedLocation {
+                    is_writable: false,
+                    base_address: 0xFEC00000,
+                    range_length: 0x1000
+                }),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_invalid_crs() {
+        let buffer = |bytes: &[u8]| AmlValue::Buffer(Arc::new(spinning_top::Spinlock::new(bytes.to_vec())));
+
+        // IRQNoFlags () {1}, followed by an EndTag with an incorrect checksum
+        assert_eq!(
+            resource_descriptor_list(&buffer(&[0x22, 0x02, 0x00, 0x79, 0x01])),
+            Err(AmlError::InvalidResourceDescriptorChecksum)
+        );
+        assert_eq!(resource_descriptor_list(&buffer(&[0x22, 0x02, 0x00, 0x79, 0x63])).map(|r| r.len()), Ok(1));
+
+        // EndTags that are missing their checksum byte
+        assert_eq!(
+            resource_descriptor_list(&buffer(&[0x22, 0x02, 0x00, 0x79])),
+            Err(AmlError::ResourceDescriptorTooShort)
+        );
+        assert_eq!(
+            resource_descriptor_list(&buffer(&[0x22, 0x02, 0x00, 0x78])),
+            Err(AmlError::ResourceDescriptorTooShort)
+        );
+
+        // An IO descriptor that's been cut short
+        assert_eq!(
+            resource_descriptor_list(&buffer(&[0x47, 0x01, 0x60, 0x00])),
+            Err(AmlError::ResourceDescriptorTooShort)
+        );
+        // A large descriptor whose length runs past the end of the buffer
+        assert_eq!(
+            resource_descriptor_list(&buffer(&[0x86, 0x09, 0x00, 0x00])),
+            Err(AmlError::ResourceDescriptorTooShort)
+        );
+    }
 }
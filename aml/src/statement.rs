@@ -1,4 +1,5 @@
 use crate::{
+    name_object::{super_name, Target},
     opcode::{self, ext_opcode, opcode},
     parser::{
         choice,
@@ -15,6 +16,7 @@ use crate::{
     },
     pkg_length::{pkg_length, PkgLength},
     term_object::{term_arg, term_list},
+    value::AmlType,
     AmlContext,
     AmlError,
     DebugVerbosity,
@@ -38,7 +40,13 @@ where
             def_fatal(),
             def_if_else(),
             def_noop(),
+            def_notify(),
+            def_release(),
+            def_reset(),
             def_return(),
+            def_signal(),
+            def_sleep(),
+            def_stall(),
             def_while()
         ),
     )
@@ -131,27 +139,31 @@ where
             pkg_length()
                 .then(term_arg())
                 .feed(|(length, predicate_arg)| {
-                    take_to_end_of_pkglength(length)
-                        .map(move |then_branch| Ok((predicate_arg.as_bool()?, then_branch)))
+                    take_to_end_of_pkglength(length).map_with_context(move |then_branch, context| {
+                        let predicate = try_with_context!(context, predicate_arg.as_integer(context)) != 0;
+                        (Ok((predicate, then_branch)), context)
+                    })
                 })
-                .then(choice!(
+                .then(|input: &'a [u8], context: &'c mut AmlContext| -> ParseResult<'a, 'c, &'a [u8]> {
+                    /*
+                     * If there isn't an else branch, we simply return an empty slice, so if the predicate is
+                     * false, we don't execute anything. We check for this explicitly, rather than letting the
+                     * `DefElse` parser fail, because a `DefIfElse` with no else branch can be the last thing in
+                     * the stream.
+                     */
+                    if input.first() != Some(&opcode::DEF_ELSE_OP) {
+                        return Ok((input, context, &[]));
+                    }
+
                     opcode(opcode::DEF_ELSE_OP)
                         .then(comment_scope(
                             DebugVerbosity::AllScopes,
                             "DefElse",
-                            pkg_length().feed(|length| take_to_end_of_pkglength(length))
+                            pkg_length().feed(|length| take_to_end_of_pkglength(length)),
                         ))
-                        .map(|((), else_branch): ((), &[u8])| Ok(else_branch)),
-                    // TODO: can this be `id().map(&[])`?
-                    |input, context| -> ParseResult<'a, 'c, &[u8]> {
-                        /*
-                         * This path parses an DefIfElse that doesn't have an else branch. We simply
-                         * return an empty slice, so if the predicate is false, we don't execute
-                         * anything.
-                         */
-                        Ok((input, context, &[]))
-                    }
-                ))
+                        .map(|((), else_branch): ((), &[u8])| Ok(else_branch))
+                        .parse(input, context)
+                })
                 .map_with_context(|((predicate, then_branch), else_branch), context| {
                     let branch = if predicate { then_branch } else { else_branch };
 
@@ -176,6 +188,81 @@ where
     opcode(opcode::DEF_NOOP_OP).then(comment_scope(DebugVerbosity::AllScopes, "DefNoop", id())).discard_result()
 }
 
+fn def_notify<'a, 'c>() -> impl Parser<'a, 'c, ()>
+where
+    'c: 'a,
+{
+    /*
+     * DefNotify := 0x86 NotifyObject NotifyValue
+     * NotifyObject := SuperName => ThermalZone | Processor | Device
+     * NotifyValue := TermArg => Integer
+     */
+    opcode(opcode::DEF_NOTIFY_OP)
+        .then(comment_scope(
+            DebugVerbosity::Scopes,
+            "DefNotify",
+            super_name().then(term_arg()).map_with_context(|(object, value), context| {
+                let object = match object {
+                    Target::Name(name) => {
+                        try_with_context!(context, context.namespace.search(&name, &context.current_scope)).0
+                    }
+                    _ => return (Err(Propagate::Err(AmlError::InvalidNotifyObject)), context),
+                };
+                match try_with_context!(context, context.namespace.get_by_path(&object)).type_of() {
+                    AmlType::Device | AmlType::Processor | AmlType::ThermalZone => (),
+                    _ => return (Err(Propagate::Err(AmlError::InvalidNotifyObject)), context),
+                }
+
+                let value = try_with_context!(context, value.as_integer(context));
+                context.handler.handle_notify(&object, value);
+                (Ok(()), context)
+            }),
+        ))
+        .discard_result()
+}
+
+fn def_release<'a, 'c>() -> impl Parser<'a, 'c, ()>
+where
+    'c: 'a,
+{
+    /*
+     * DefRelease := ExtOpPrefix 0x27 MutexObject
+     * MutexObject := SuperName
+     */
+    ext_opcode(opcode::EXT_DEF_RELEASE_OP)
+        .then(comment_scope(
+            DebugVerbosity::AllScopes,
+            "DefRelease",
+            super_name().map_with_context(|mutex, context| {
+                let mutex = try_with_context!(context, context.resolve_mutex(mutex));
+                context.handler.release_mutex(&mutex);
+                (Ok(()), context)
+            }),
+        ))
+        .discard_result()
+}
+
+fn def_reset<'a, 'c>() -> impl Parser<'a, 'c, ()>
+where
+    'c: 'a,
+{
+    /*
+     * DefReset := ExtOpPrefix 0x26 EventObject
+     * EventObject := SuperName
+     */
+    ext_opcode(opcode::EXT_DEF_RESET_OP)
+        .then(comment_scope(
+            DebugVerbosity::AllScopes,
+            "DefReset",
+            super_name().map_with_context(|event, context| {
+                let event = try_with_context!(context, context.resolve_event(event));
+                context.handler.reset_event(&event);
+                (Ok(()), context)
+            }),
+        ))
+        .discard_result()
+}
+
 fn def_return<'a, 'c>() -> impl Parser<'a, 'c, ()>
 where
     'c: 'a,
@@ -201,6 +288,79 @@ where
         .discard_result()
 }
 
+fn def_signal<'a, 'c>() -> impl Parser<'a, 'c, ()>
+where
+    'c: 'a,
+{
+    /*
+     * DefSignal := ExtOpPrefix 0x24 EventObject
+     * EventObject := SuperName
+     */
+    ext_opcode(opcode::EXT_DEF_SIGNAL_OP)
+        .then(comment_scope(
+            DebugVerbosity::AllScopes,
+            "DefSignal",
+            super_name().map_with_context(|event, context| {
+                let event = try_with_context!(context, context.resolve_event(event));
+                context.handler.signal_event(&event);
+                (Ok(()), context)
+            }),
+        ))
+        .discard_result()
+}
+
+fn def_sleep<'a, 'c>() -> impl Parser<'a, 'c, ()>
+where
+    'c: 'a,
+{
+    /*
+     * DefSleep := ExtOpPrefix 0x22 MSecTime
+     * MSecTime := TermArg => Integer
+     */
+    ext_opcode(opcode::EXT_DEF_SLEEP_OP)
+        .then(comment_scope(
+            DebugVerbosity::Scopes,
+            "DefSleep",
+            term_arg().map_with_context(|milliseconds, context| {
+                let milliseconds = try_with_context!(context, milliseconds.as_integer(context));
+                context.handler.sleep(milliseconds);
+                (Ok(()), context)
+            }),
+        ))
+        .discard_result()
+}
+
+fn def_stall<'a, 'c>() -> impl Parser<'a, 'c, ()>
+where
+    'c: 'a,
+{
+    /*
+     * DefStall := ExtOpPrefix 0x21 USecTime
+     * USecTime := TermArg => ByteData
+     *
+     * Stalls longer than a `ByteData` can encode are cut short, as the processor is not relinquished during them.
+     */
+    ext_opcode(opcode::EXT_DEF_STALL_OP)
+        .then(comment_scope(
+            DebugVerbosity::Scopes,
+            "DefStall",
+            term_arg().map_with_context(|microseconds, context| {
+                let mut microseconds = try_with_context!(context, microseconds.as_integer(context));
+                if microseconds > u8::MAX as u64 {
+                    log::warn!(
+                        "AML tried to stall for {} microseconds. Stalling for {} instead.",
+                        microseconds,
+                        u8::MAX
+                    );
+                    microseconds = u8::MAX as u64;
+                }
+                context.handler.stall(microseconds);
+                (Ok(()), context)
+            }),
+        ))
+        .discard_result()
+}
+
 fn def_while<'a, 'c>() -> impl Parser<'a, 'c, ()>
 where
     'c: 'a,
@@ -224,7 +384,7 @@ where
                         .map(move |body| Ok((first_predicate.clone(), predicate_stream, body)))
                 })
                 .map_with_context(|(first_predicate, predicate_stream, body), mut context| {
-                    if !try_with_context!(context, first_predicate.as_bool()) {
+                    if try_with_context!(context, first_predicate.as_integer(context)) == 0 {
                         return (Ok(()), context);
                     }
 
@@ -255,7 +415,7 @@ where
                             {
                                 Ok((_, new_context, result)) => {
                                     context = new_context;
-                                    try_with_context!(context, result.as_bool())
+                                    try_with_context!(context, result.as_integer(context)) != 0
                                 }
                                 Err((_, context, err)) => return (Err(err), context),
                             };
@@ -270,3 +430,294 @@ where
         ))
         .discard_result()
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{test_utils::*, value::Args, AmlError, AmlName, AmlValue};
+    use alloc::{sync::Arc, vec};
+    use spinning_top::Spinlock;
+
+    #[test]
+    fn test_def_return() {
+        let mut context = make_test_context();
+
+        /*
+         * Method(FOO) {
+         *     Return(0x2a)
+         *     Return(One)
+         * }
+         * Method(BAR, 1) { Return(Arg0) }
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x14, 0x0b, b'F', b'O', b'O', b'_', 0x00, 0xa4, 0x0a, 0x2a, 0xa4, 0x01, 0x14, 0x08, b'B', b'A',
+                b'R', b'_', 0x01, 0xa4, 0x68,
+            ]),
+            Ok(())
+        );
+        assert!(crudely_cmp_values(
+            &context.invoke_method(&AmlName::from_str("\\FOO").unwrap(), Args::EMPTY).unwrap(),
+            &AmlValue::Integer(0x2a)
+        ));
+        assert!(crudely_cmp_values(
+            &context
+                .invoke_method(
+                    &AmlName::from_str("\\BAR").unwrap(),
+                    Args::from_list(vec![AmlValue::Integer(7)]).unwrap()
+                )
+                .unwrap(),
+            &AmlValue::Integer(7)
+        ));
+    }
+
+    #[test]
+    fn test_def_if_else() {
+        let mut context = make_test_context();
+
+        /*
+         * Method(FOO, 1) {
+         *     If (Arg0) {
+         *         Return(One)
+         *     } Else {
+         *         If (Zero) {
+         *             Return(2)
+         *         } Else {
+         *             Return(3)
+         *         }
+         *     }
+         * }
+         * Method(BAR, 1) {
+         *     If (Arg0) {
+         *         Return(One)
+         *     }
+         * }
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x14, 0x18, b'F', b'O', b'O', b'_', 0x01, 0xa0, 0x04, 0x68, 0xa4, 0x01, 0xa1, 0x0c, 0xa0, 0x05,
+                0x00, 0xa4, 0x0a, 0x02, 0xa1, 0x04, 0xa4, 0x0a, 0x03, 0x14, 0x0b, b'B', b'A', b'R', b'_', 0x01,
+                0xa0, 0x04, 0x68, 0xa4, 0x01,
+            ]),
+            Ok(())
+        );
+
+        let mut invoke = |name, arg| {
+            context.invoke_method(&AmlName::from_str(name).unwrap(), Args::from_list(vec![arg]).unwrap()).unwrap()
+        };
+        assert!(crudely_cmp_values(&invoke("\\FOO", AmlValue::Integer(1)), &AmlValue::Integer(1)));
+        assert!(crudely_cmp_values(&invoke("\\FOO", AmlValue::Integer(0)), &AmlValue::Integer(3)));
+        // Predicates are implicitly converted to integers
+        assert!(crudely_cmp_values(
+            &invoke("\\FOO", AmlValue::Buffer(Arc::new(Spinlock::new(vec![0x00, 0x01])))),
+            &AmlValue::Integer(1)
+        ));
+        assert!(crudely_cmp_values(&invoke("\\BAR", AmlValue::Integer(5)), &AmlValue::Integer(1)));
+        assert!(crudely_cmp_values(&invoke("\\BAR", AmlValue::Integer(0)), &AmlValue::Integer(0)));
+    }
+
+    #[test]
+    fn test_def_while() {
+        let mut context = make_test_context();
+
+        /*
+         * Method(FOO) {
+         *     While (One) { Break }
+         *     Return(5)
+         * }
+         * Method(BAR) {
+         *     While (Zero) { }
+         *     Return(6)
+         * }
+         * Method(BAZ) {
+         *     Store(Zero, Local0)
+         *     While (LLess(Local0, 5)) {
+         *         Increment(Local0)
+         *         If (LEqual(Local0, 3)) { Break }
+         *     }
+         *     Return(Local0)
+         * }
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x14, 0x0d, b'F', b'O', b'O', b'_', 0x00, 0xa2, 0x03, 0x01, 0xa5, 0xa4, 0x0a, 0x05, 0x14, 0x0c,
+                b'B', b'A', b'R', b'_', 0x00, 0xa2, 0x02, 0x00, 0xa4, 0x0a, 0x06, 0x14, 0x1a, b'B', b'A', b'Z',
+                b'_', 0x00, 0x70, 0x00, 0x60, 0xa2, 0x0e, 0x95, 0x60, 0x0a, 0x05, 0x75, 0x60, 0xa0, 0x06, 0x93,
+                0x60, 0x0a, 0x03, 0xa5, 0xa4, 0x60,
+            ]),
+            Ok(())
+        );
+
+        let mut invoke = |name| context.invoke_method(&AmlName::from_str(name).unwrap(), Args::EMPTY).unwrap();
+        assert!(crudely_cmp_values(&invoke("\\FOO"), &AmlValue::Integer(5)));
+        assert!(crudely_cmp_values(&invoke("\\BAR"), &AmlValue::Integer(6)));
+        assert!(crudely_cmp_values(&invoke("\\BAZ"), &AmlValue::Integer(3)));
+    }
+
+    #[test]
+    fn test_def_notify() {
+        let mut context = make_test_context();
+
+        /*
+         * Device(DEV0) { }
+         * Method(FOO) { Notify(DEV0, 0x80) }
+         * Name(INT0, One)
+         * Method(BAR) { Notify(INT0, 0x80) }
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x5b, 0x82, 0x05, b'D', b'E', b'V', b'0', 0x14, 0x0d, b'F', b'O', b'O', b'_', 0x00, 0x86, b'D',
+                b'E', b'V', b'0', 0x0a, 0x80, 0x08, b'I', b'N', b'T', b'0', 0x01, 0x14, 0x0d, b'B', b'A', b'R',
+                b'_', 0x00, 0x86, b'I', b'N', b'T', b'0', 0x0a, 0x80,
+            ]),
+            Ok(())
+        );
+        assert!(crudely_cmp_values(
+            &context.invoke_method(&AmlName::from_str("\\FOO").unwrap(), Args::EMPTY).unwrap(),
+            &AmlValue::Integer(0)
+        ));
+        assert!(matches!(
+            context.invoke_method(&AmlName::from_str("\\BAR").unwrap(), Args::EMPTY),
+            Err(AmlError::InvalidNotifyObject)
+        ));
+    }
+
+    #[test]
+    fn test_def_sleep_and_stall() {
+        let (mut context, hardware) = make_recording_test_context();
+
+        /*
+         * Method(FOO) {
+         *     Stall(100)
+         *     Sleep(10)
+         *     Stall(0x1000)
+         *     Return(One)
+         * }
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x14, 0x15, b'F', b'O', b'O', b'_', 0x00, 0x5b, 0x21, 0x0a, 0x64, 0x5b, 0x22, 0x0a, 0x0a, 0x5b,
+                0x21, 0x0b, 0x00, 0x10, 0xa4, 0x01,
+            ]),
+            Ok(())
+        );
+        assert!(crudely_cmp_values(
+            &context.invoke_method(&AmlName::from_str("\\FOO").unwrap(), Args::EMPTY).unwrap(),
+            &AmlValue::Integer(1)
+        ));
+
+        // Stalls longer than a `ByteData` are cut short
+        assert_eq!(
+            hardware.lock().accesses,
+            vec![
+                Access::Stall { microseconds: 100 },
+                Access::Sleep { milliseconds: 10 },
+                Access::Stall { microseconds: 255 }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_def_fatal() {
+        let mut context = make_test_context();
+
+        // Fatal(0x01, 0x12345678, 5)
+        assert_eq!(
+            context.parse_table(&[0x5b, 0x32, 0x01, 0x78, 0x56, 0x34, 0x12, 0x0a, 0x05]).map_err(|err| err.error),
+            Err(AmlError::FatalError)
+        );
+
+        // Method(FOO) { Fatal(0x01, 0x12345678, 5) }
+        assert_eq!(
+            context.parse_table(&[
+                0x14, 0x0f, b'F', b'O', b'O', b'_', 0x00, 0x5b, 0x32, 0x01, 0x78, 0x56, 0x34, 0x12, 0x0a, 0x05,
+            ]),
+            Ok(())
+        );
+        assert!(matches!(
+            context.invoke_method(&AmlName::from_str("\\FOO").unwrap(), Args::EMPTY),
+            Err(AmlError::FatalError)
+        ));
+    }
+
+    #[test]
+    fn test_acquire_and_release() {
+        let mut context = make_test_context();
+
+        /*
+         * Mutex(MUTX, 0)
+         * Method(FOO, 0, Serialized) {
+         *     If (Acquire(MUTX, 0xffff)) {
+         *         Return(One)
+         *     }
+         *     Acquire(\_GL, 0x0000)
+         *     Release(\_GL)
+         *     Release(MUTX)
+         *     Return(Zero)
+         * }
+         * Name(INT0, One)
+         * Method(BAR) { Release(INT0) }
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x5b, 0x01, b'M', b'U', b'T', b'X', 0x00, 0x14, 0x2a, b'F', b'O', b'O', b'_', 0x08, 0xa0, 0x0b,
+                0x5b, 0x23, b'M', b'U', b'T', b'X', 0xff, 0xff, 0xa4, 0x01, 0x5b, 0x23, b'\\', b'_', b'G', b'L',
+                b'_', 0x00, 0x00, 0x5b, 0x27, b'\\', b'_', b'G', b'L', b'_', 0x5b, 0x27, b'M', b'U', b'T', b'X',
+                0xa4, 0x00, 0x08, b'I', b'N', b'T', b'0', 0x01, 0x14, 0x0c, b'B', b'A', b'R', b'_', 0x00, 0x5b,
+                0x27, b'I', b'N', b'T', b'0',
+            ]),
+            Ok(())
+        );
+        assert!(crudely_cmp_values(
+            &context.invoke_method(&AmlName::from_str("\\FOO").unwrap(), Args::EMPTY).unwrap(),
+            &AmlValue::Integer(0)
+        ));
+        assert!(matches!(
+            context.invoke_method(&AmlName::from_str("\\BAR").unwrap(), Args::EMPTY),
+            Err(AmlError::NotAMutex)
+        ));
+    }
+
+    #[test]
+    fn test_signal_wait_and_reset() {
+        let (mut context, hardware) = make_recording_test_context();
+
+        /*
+         * Event(EVT0)
+         * Method(FOO, 0) {
+         *     Signal(EVT0)
+         *     Local0 = Wait(EVT0, 0x10)
+         *     Reset(EVT0)
+         *     Return(Local0)
+         * }
+         * Name(INT0, One)
+         * Method(BAR) { Signal(INT0) }
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x5b, 0x02, b'E', b'V', b'T', b'0', 0x14, 0x1e, b'F', b'O', b'O', b'_', 0x00, 0x5b, 0x24, b'E',
+                b'V', b'T', b'0', 0x70, 0x5b, 0x25, b'E', b'V', b'T', b'0', 0x0a, 0x10, 0x60, 0x5b, 0x26, b'E',
+                b'V', b'T', b'0', 0xa4, 0x60, 0x08, b'I', b'N', b'T', b'0', 0x01, 0x14, 0x0c, b'B', b'A', b'R',
+                b'_', 0x00, 0x5b, 0x24, b'I', b'N', b'T', b'0',
+            ]),
+            Ok(())
+        );
+        assert!(crudely_cmp_values(
+            &context.invoke_method(&AmlName::from_str("\\FOO").unwrap(), Args::EMPTY).unwrap(),
+            &AmlValue::Boolean(false)
+        ));
+
+        let event = AmlName::from_str("\\EVT0").unwrap();
+        assert_eq!(
+            hardware.lock().accesses,
+            vec![
+                Access::SignalEvent(event.clone()),
+                Access::WaitForEvent(event.clone()),
+                Access::ResetEvent(event)
+            ]
+        );
+        assert!(matches!(
+            context.invoke_method(&AmlName::from_str("\\BAR").unwrap(), Args::EMPTY),
+            Err(AmlError::NotAnEvent)
+        ));
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{
-    expression::{def_buffer, def_package, expression_opcode},
+    expression::{def_buffer, def_package, def_var_package, expression_opcode},
     misc::{arg_obj, local_obj},
     name_object::{name_seg, name_string},
     namespace::{AmlName, LevelType},
@@ -13,20 +13,23 @@ use crate::{
         take_u32,
         take_u64,
         try_with_context,
+        within_pkglength,
         ParseResult,
         Parser,
         Propagate,
     },
-    pkg_length::{pkg_length, PkgLength},
+    pkg_length::{pkg_length, raw_pkg_length, PkgLength},
     statement::statement_opcode,
-    value::{AmlValue, FieldFlags, MethodCode, MethodFlags, RegionSpace},
+    value::{AmlType, AmlValue, FieldAccessAttrib, FieldFlags, MethodCode, MethodFlags, RegionSpace},
     AmlContext,
     AmlError,
     AmlHandle,
     DebugVerbosity,
 };
 use alloc::{string::String, sync::Arc, vec::Vec};
+use bit_field::BitField;
 use core::str;
+use spinning_top::Spinlock;
 
 /// `TermList`s are usually found within explicit-length objects (so they have a `PkgLength`
 /// elsewhere in the structure), so this takes a number of bytes to parse.
@@ -37,12 +40,33 @@ where
     /*
      * TermList := Nothing | <TermObj TermList>
      */
-    // TODO: why does this use still_parsing, instead of just taking the whole thing and parsing it til it's empty?
+    within_pkglength(list_length, list_of(term_object()))
+}
+
+/// Parse items with `item_parser` until the stream is empty. If an item is parsed without consuming any of the
+/// stream, this fails with `AmlError::NoProgress` instead of trying to parse it forever.
+fn list_of<'a, 'c, P, R>(item_parser: P) -> impl Parser<'a, 'c, ()>
+where
+    'c: 'a,
+    P: Parser<'a, 'c, R>,
+{
     move |mut input: &'a [u8], mut context: &'c mut AmlContext| {
-        while list_length.still_parsing(input) {
+        while !input.is_empty() {
             // TODO: currently, we ignore the value of the expression. We may need to propagate
             // this.
-            let (new_input, new_context, _) = term_object().parse(input, context)?;
+            let (new_input, new_context, _) = match item_parser.parse(input, context) {
+                Ok(result) => result,
+                Err((remaining, context, Propagate::Err(err))) => {
+                    if err != AmlError::WrongParser {
+                        context.record_failed_term(input);
+                    }
+                    return Err((remaining, context, Propagate::Err(err)));
+                }
+                Err(err) => return Err(err),
+            };
+            if new_input.len() == input.len() {
+                return Err((input, new_context, Propagate::Err(AmlError::NoProgress)));
+            }
             input = new_input;
             context = new_context;
         }
@@ -77,7 +101,37 @@ where
     /*
      * NamespaceModifierObj := DefAlias | DefName | DefScope
      */
-    choice!(def_name(), def_scope())
+    choice!(def_alias(), def_name(), def_scope())
+}
+
+pub fn def_alias<'a, 'c>() -> impl Parser<'a, 'c, ()>
+where
+    'c: 'a,
+{
+    /*
+     * DefAlias := 0x06 NameString NameString
+     *
+     * The first name is the existing object, and the second is the alias to create. If the object already exists,
+     * we apply the search rules to find it. Otherwise, it's allowed to be defined later, so we resolve it against
+     * the current scope and look it up when the alias is used.
+     */
+    opcode(opcode::DEF_ALIAS_OP)
+        .then(comment_scope(
+            DebugVerbosity::Scopes,
+            "DefAlias",
+            name_string().then(name_string()).map_with_context(|(target, alias), context| {
+                let target = match context.namespace.search(&target, &context.current_scope) {
+                    Ok((target, _)) => target,
+                    Err(_) => try_with_context!(context, target.resolve(&context.current_scope)),
+                };
+                try_with_context!(
+                    context,
+                    context.namespace.add_alias_at_resolved_path(alias, &context.current_scope, target)
+                );
+                (Ok(()), context)
+            }),
+        ))
+        .discard_result()
 }
 
 pub fn named_obj<'a, 'c>() -> impl Parser<'a, 'c, ()>
@@ -87,9 +141,9 @@ where
     /*
      * NamedObj := DefBankField | DefCreateBitField | DefCreateByteField | DefCreateWordField | DefCreateDWordField |
      *             DefCreateQWordField | DefCreateField | DefDataRegion | DefExternal | DefOpRegion | DefPowerRes |
-     *             DefProcessor | DefThermalZone | DefMethod | DefMutex
+     *             DefProcessor | DefThermalZone | DefMethod | DefMutex | DefEvent
      *
-     * XXX: DefMethod and DefMutex (at least) are not included in any rule in the AML grammar,
+     * XXX: DefMethod, DefMutex, and DefEvent (at least) are not included in any rule in the AML grammar,
      * but are defined in the NamedObj section so we assume they're part of NamedObj
      */
     comment_scope(
@@ -103,14 +157,17 @@ where
             def_create_qword_field(),
             def_create_field(),
             def_op_region(),
+            def_data_region(),
             def_field(),
+            def_index_field(),
             def_method(),
             def_external(),
             def_device(),
             def_processor(),
             def_power_res(),
             def_thermal_zone(),
-            def_mutex()
+            def_mutex(),
+            def_event()
         ),
     )
 }
@@ -230,13 +287,17 @@ where
                     let source_data: Arc<spinning_top::Spinlock<Vec<u8>>> =
                         try_with_context!(context, source.as_buffer(context)).clone();
                     let index = try_with_context!(context, index.as_integer(context));
+                    let offset = try_with_context!(
+                        context,
+                        index.checked_mul(8).ok_or(AmlError::BufferFieldIndexesOutOfBounds)
+                    );
 
                     try_with_context!(
                         context,
                         context.namespace.add_value_at_resolved_path(
                             name,
                             &context.current_scope,
-                            AmlValue::BufferField { buffer_data: source_data, offset: index * 8, length: 8 }
+                            AmlValue::BufferField { buffer_data: source_data, offset, length: 8 }
                         )
                     );
 
@@ -265,13 +326,17 @@ where
                     let source_data: Arc<spinning_top::Spinlock<Vec<u8>>> =
                         try_with_context!(context, source.as_buffer(context)).clone();
                     let index = try_with_context!(context, index.as_integer(context));
+                    let offset = try_with_context!(
+                        context,
+                        index.checked_mul(8).ok_or(AmlError::BufferFieldIndexesOutOfBounds)
+                    );
 
                     try_with_context!(
                         context,
                         context.namespace.add_value_at_resolved_path(
                             name,
                             &context.current_scope,
-                            AmlValue::BufferField { buffer_data: source_data, offset: index * 8, length: 16 }
+                            AmlValue::BufferField { buffer_data: source_data, offset, length: 16 }
                         )
                     );
 
@@ -300,13 +365,17 @@ where
                     let source_data: Arc<spinning_top::Spinlock<Vec<u8>>> =
                         try_with_context!(context, source.as_buffer(context)).clone();
                     let index = try_with_context!(context, index.as_integer(context));
+                    let offset = try_with_context!(
+                        context,
+                        index.checked_mul(8).ok_or(AmlError::BufferFieldIndexesOutOfBounds)
+                    );
 
                     try_with_context!(
                         context,
                         context.namespace.add_value_at_resolved_path(
                             name,
                             &context.current_scope,
-                            AmlValue::BufferField { buffer_data: source_data, offset: index * 8, length: 32 }
+                            AmlValue::BufferField { buffer_data: source_data, offset, length: 32 }
                         )
                     );
 
@@ -335,13 +404,17 @@ where
                     let source_data: Arc<spinning_top::Spinlock<Vec<u8>>> =
                         try_with_context!(context, source.as_buffer(context)).clone();
                     let index = try_with_context!(context, index.as_integer(context));
+                    let offset = try_with_context!(
+                        context,
+                        index.checked_mul(8).ok_or(AmlError::BufferFieldIndexesOutOfBounds)
+                    );
 
                     try_with_context!(
                         context,
                         context.namespace.add_value_at_resolved_path(
                             name,
                             &context.current_scope,
-                            AmlValue::BufferField { buffer_data: source_data, offset: index * 8, length: 64 }
+                            AmlValue::BufferField { buffer_data: source_data, offset, length: 64 }
                         )
                     );
 
@@ -364,7 +437,7 @@ where
      */
     ext_opcode(opcode::EXT_DEF_CREATE_FIELD_OP)
         .then(comment_scope(
-            DebugVerbosity::Scopes,
+            DebugVerbosity::AllScopes,
             "DefCreateField",
             term_arg().then(term_arg()).then(term_arg()).then(name_string()).map_with_context(
                 |(((source, index), num_bits), name), context| {
@@ -461,6 +534,59 @@ where
         .discard_result()
 }
 
+pub fn def_data_region<'a, 'c>() -> impl Parser<'a, 'c, ()>
+where
+    'c: 'a,
+{
+    /*
+     * DefDataRegion := ExtOpPrefix 0x88 NameString TermArg TermArg TermArg
+     *
+     * The `TermArg`s evaluate to the signature, OEM ID, and OEM Table ID of the table that the region covers.
+     */
+    ext_opcode(opcode::EXT_DEF_DATA_REGION_OP)
+        .then(comment_scope(
+            DebugVerbosity::Scopes,
+            "DefDataRegion",
+            name_string().then(term_arg()).then(term_arg()).then(term_arg()).map_with_context(
+                |(((name, signature), oem_id), oem_table_id), context| {
+                    let signature = try_with_context!(context, signature.as_string(context));
+                    let oem_id = try_with_context!(context, oem_id.as_string(context));
+                    let oem_table_id = try_with_context!(context, oem_table_id.as_string(context));
+
+                    let (offset, length) = match context.handler.find_table(&signature, &oem_id, &oem_table_id) {
+                        Some(table) => table,
+                        None => {
+                            return (
+                                Err(Propagate::Err(AmlError::DataRegionTableNotFound {
+                                    signature,
+                                    oem_id,
+                                    oem_table_id,
+                                })),
+                                context,
+                            )
+                        }
+                    };
+
+                    try_with_context!(
+                        context,
+                        context.namespace.add_value_at_resolved_path(
+                            name,
+                            &context.current_scope,
+                            AmlValue::OpRegion {
+                                region: RegionSpace::SystemMemory,
+                                offset,
+                                length,
+                                parent_device: None
+                            }
+                        )
+                    );
+                    (Ok(()), context)
+                },
+            ),
+        ))
+        .discard_result()
+}
+
 pub fn def_field<'a, 'c>() -> impl Parser<'a, 'c, ()>
 where
     'c: 'a,
@@ -484,35 +610,113 @@ where
             DebugVerbosity::Scopes,
             "DefField",
             pkg_length().then(opregion_as_handle).then(take()).feed(|((list_length, region_handle), flags)| {
-                move |mut input: &'a [u8], mut context: &'c mut AmlContext| -> ParseResult<'a, 'c, ()> {
-                    /*
-                     * FieldList := Nothing | <FieldElement FieldList>
-                     */
-                    // TODO: can this pattern be expressed as a combinator
-                    let mut current_offset = 0;
-                    while list_length.still_parsing(input) {
-                        let (new_input, new_context, field_length) =
-                            field_element(region_handle, FieldFlags::new(flags), current_offset)
-                                .parse(input, context)?;
-                        input = new_input;
-                        context = new_context;
-                        current_offset += field_length;
-                    }
-
-                    Ok((input, context, ()))
-                }
+                field_list(list_length, FieldListKind::Region(region_handle), flags)
             }),
         ))
         .discard_result()
 }
 
-/// Parses a `FieldElement`. Takes the current offset within the field list, and returns the length
-/// of the field element parsed.
-pub fn field_element<'a, 'c>(
-    region_handle: AmlHandle,
-    flags: FieldFlags,
-    current_offset: u64,
-) -> impl Parser<'a, 'c, u64>
+pub fn def_index_field<'a, 'c>() -> impl Parser<'a, 'c, ()>
+where
+    'c: 'a,
+{
+    /*
+     * DefIndexField := ExtOpPrefix 0x86 PkgLength NameString NameString FieldFlags FieldList
+     *
+     * The first name is the field used to select a register, and the second is the field the register is then
+     * accessed through. Like for `DefField`, we find them here while we have the correct scope.
+     */
+    let field_as_handle = || {
+        name_string().map_with_context(|field_name, context| {
+            let (_, handle) =
+                try_with_context!(context, context.namespace.search(&field_name, &context.current_scope));
+            (Ok(handle), context)
+        })
+    };
+
+    ext_opcode(opcode::EXT_DEF_INDEX_FIELD_OP)
+        .then(comment_scope(
+            DebugVerbosity::Scopes,
+            "DefIndexField",
+            pkg_length().then(field_as_handle()).then(field_as_handle()).then(take()).feed(
+                |(((list_length, index), data), flags)| {
+                    field_list(list_length, FieldListKind::Index { index, data }, flags)
+                },
+            ),
+        ))
+        .discard_result()
+}
+
+/// What the field units defined by a `FieldList` are accessed through.
+#[derive(Clone, Copy, Debug)]
+pub enum FieldListKind {
+    /// The fields of a `DefField`, which lie directly within an operation region.
+    Region(AmlHandle),
+    /// The fields of a `DefIndexField`, which are accessed through a pair of index and data fields.
+    Index { index: AmlHandle, data: AmlHandle },
+}
+
+fn field_list<'a, 'c>(list_length: PkgLength, kind: FieldListKind, flags: u8) -> impl Parser<'a, 'c, ()>
+where
+    'c: 'a,
+{
+    /*
+     * FieldList := Nothing | <FieldElement FieldList>
+     */
+    move |mut input: &'a [u8], mut context: &'c mut AmlContext| -> ParseResult<'a, 'c, ()> {
+        /*
+         * Fields in an operation region can't extend past its end. Banks of indexed registers don't have a length
+         * we can check against.
+         */
+        let region_length = match kind {
+            FieldListKind::Region(region_handle) => match context.namespace.get(region_handle) {
+                Ok(AmlValue::OpRegion { length, .. }) => Some(*length),
+                Ok(_) => return Err((input, context, Propagate::Err(AmlError::FieldRegionIsNotOpRegion))),
+                Err(err) => return Err((input, context, Propagate::Err(err))),
+            },
+            FieldListKind::Index { .. } => None,
+        };
+
+        // TODO: can this pattern be expressed as a combinator
+        let mut state =
+            FieldListState { offset: 0, flags: FieldFlags::new(flags), access_attrib: None, connection: None };
+        while list_length.still_parsing(input) {
+            let (new_input, new_context, new_state) = field_element(kind, state.clone()).parse(input, context)?;
+            input = new_input;
+            context = new_context;
+            state = new_state;
+
+            /*
+             * Catch fields that extend past the end of the region here, rather than letting them silently produce
+             * bogus accesses when they're read or written.
+             */
+            if let Some(region_length) = region_length {
+                if state.offset > region_length.saturating_mul(8) {
+                    return Err((input, context, Propagate::Err(AmlError::FieldOutOfRegionBounds)));
+                }
+            }
+        }
+
+        Ok((input, context, ()))
+    }
+}
+
+/// The state that is carried between the elements of a `FieldList`. As well as the offset of the next field, some
+/// elements (e.g. `AccessField`s) change how the fields that follow them are accessed.
+#[derive(Clone, Debug)]
+pub struct FieldListState {
+    /// The offset, in bits, of the next field in the list.
+    pub offset: u64,
+    /// The flags that fields defined at this point in the list are created with.
+    pub flags: FieldFlags,
+    /// The access attribute that fields defined at this point in the list are created with.
+    pub access_attrib: Option<FieldAccessAttrib>,
+    /// The resource descriptor set by the last `ConnectField`, if there has been one.
+    pub connection: Option<Arc<Spinlock<Vec<u8>>>>,
+}
+
+/// Parses a `FieldElement`. Takes the state of the field list before the element, and returns the state after it.
+pub fn field_element<'a, 'c>(kind: FieldListKind, state: FieldListState) -> impl Parser<'a, 'c, FieldListState>
 where
     'c: 'a,
 {
@@ -532,40 +736,103 @@ where
      * object (it seems to be defined in ASL). We treat BufferData as if it was encoded like
      * DefBuffer, and this seems to work so far.
      */
-    // TODO: parse ConnectField and ExtendedAccessField
 
     /*
      * Reserved fields shouldn't actually be added to the namespace; they seem to show gaps in
-     * the operation region that aren't used for anything.
+     * the operation region that aren't used for anything. Like named fields, their `PkgLength` is a length in bits.
+     */
+    let reserved_state = state.clone();
+    let reserved_field = opcode(opcode::RESERVED_FIELD).then(raw_pkg_length()).map(move |((), length)| {
+        Ok(FieldListState { offset: reserved_state.offset + length as u64, ..reserved_state.clone() })
+    });
+
+    /*
+     * An access field changes the access type and attribute of all of the fields that follow it in the list, until
+     * the next access field. The bottom four bits of `AccessType` are encoded in the same way as in `FieldFlags`.
+     */
+    let access_state = state.clone();
+    let access_field =
+        opcode(opcode::ACCESS_FIELD).then(take()).then(take()).map(move |(((), access_type), access_attrib)| {
+            let flags = access_state.flags.with_access_type(access_type);
+            flags.access_type()?;
+            let access_attrib = FieldAccessAttrib::from_access_field(access_type, access_attrib)?;
+            Ok(FieldListState { flags, access_attrib, ..access_state.clone() })
+        });
+
+    /*
+     * Extended access fields are used for attributes that need a length (e.g. `AttribBytes`), which are used for
+     * accessing `GenericSerialBus` and `IPMI` regions.
      */
-    let reserved_field =
-        opcode(opcode::RESERVED_FIELD).then(pkg_length()).map(|((), length)| Ok(length.raw_length as u64));
+    let extended_access_state = state.clone();
+    let extended_access_field = opcode(opcode::EXTENDED_ACCESS_FIELD).then(take()).then(take()).then(take()).map(
+        move |((((), access_type), extended_access_attrib), access_length)| {
+            let flags = extended_access_state.flags.with_access_type(access_type);
+            flags.access_type()?;
+            let access_attrib =
+                Some(FieldAccessAttrib::from_extended_access_field(extended_access_attrib, access_length)?);
+            Ok(FieldListState { flags, access_attrib, ..extended_access_state.clone() })
+        },
+    );
 
-    // TODO: work out what to do with an access field
-    // let access_field = opcode(opcode::ACCESS_FIELD)
-    //     .then(take())
-    //     .then(take())
-    //     .map_with_context(|(((), access_type), access_attrib), context| (Ok(    , context));
+    /*
+     * A connect field attaches a resource descriptor (e.g. a `GpioIo` or `I2cSerialBus` descriptor) to the fields
+     * that follow it, until the next connect field. The descriptor is either encoded inline, or is the name of a
+     * buffer containing it.
+     */
+    let connect_state = state.clone();
+    let connect_field = opcode(opcode::CONNECT_FIELD)
+        .then(choice!(
+            def_buffer(),
+            name_string().map_with_context(|name, context| {
+                let (_, handle) =
+                    try_with_context!(context, context.namespace.search(&name, &context.current_scope));
+                (Ok(try_with_context!(context, context.namespace.get(handle)).clone()), context)
+            })
+        ))
+        .map(move |((), connection)| match connection {
+            AmlValue::Buffer(connection) => {
+                Ok(FieldListState { connection: Some(connection), ..connect_state.clone() })
+            }
+            other => Err(Propagate::Err(AmlError::IncompatibleValueConversion {
+                current: other.type_of(),
+                target: AmlType::Buffer,
+            })),
+        });
 
-    let named_field = name_seg().then(pkg_length()).map_with_context(move |(name_seg, length), context| {
+    /*
+     * NOTE: the `PkgLength`s in field elements encode the length of the field in bits, and so are not related to
+     * the length of the stream. We therefore only parse the raw length.
+     */
+    let named_field = name_seg().then(raw_pkg_length()).map_with_context(move |(name_seg, length), context| {
         try_with_context!(
             context,
             context.namespace.add_value_at_resolved_path(
                 AmlName::from_name_seg(name_seg),
                 &context.current_scope,
-                AmlValue::Field {
-                    region: region_handle,
-                    flags,
-                    offset: current_offset,
-                    length: length.raw_length as u64,
+                match kind {
+                    FieldListKind::Region(region) => AmlValue::Field {
+                        region,
+                        flags: state.flags,
+                        offset: state.offset,
+                        length: length as u64,
+                        access_attrib: state.access_attrib,
+                        connection: state.connection.clone(),
+                    },
+                    FieldListKind::Index { index, data } => AmlValue::IndexField {
+                        index,
+                        data,
+                        flags: state.flags,
+                        offset: state.offset,
+                        length: length as u64,
+                    },
                 },
             )
         );
 
-        (Ok(length.raw_length as u64), context)
+        (Ok(FieldListState { offset: state.offset + length as u64, ..state.clone() }), context)
     });
 
-    choice!(reserved_field, named_field)
+    choice!(reserved_field, access_field, connect_field, extended_access_field, named_field)
 }
 
 pub fn def_method<'a, 'c>() -> impl Parser<'a, 'c, ()>
@@ -614,9 +881,28 @@ where
      * DefExternal = 0x15 NameString ObjectType ArgumentCount
      * ObjectType := ByteData
      * ArgumentCount := ByteData (0 to 7)
+     *
+     * We add a placeholder for the object, so references to it can be parsed before it's defined. If the object
+     * has already been defined, or the scope it's in hasn't been yet, there's nothing to do.
      */
     opcode(opcode::DEF_EXTERNAL_OP)
-        .then(comment_scope(DebugVerbosity::Scopes, "DefExternal", name_string().then(take()).then(take())))
+        .then(comment_scope(
+            DebugVerbosity::Scopes,
+            "DefExternal",
+            name_string().then(take()).then(take()).map_with_context(
+                |((name, object_type), arg_count), context| {
+                    match context.namespace.add_value_at_resolved_path(
+                        name,
+                        &context.current_scope,
+                        AmlValue::External { object_type, arg_count },
+                    ) {
+                        Ok(_) | Err(AmlError::NameCollision(_)) | Err(AmlError::LevelDoesNotExist(_)) => (),
+                        Err(err) => return (Err(Propagate::Err(err)), context),
+                    }
+                    (Ok(()), context)
+                },
+            ),
+        ))
         .discard_result()
 }
 
@@ -813,13 +1099,13 @@ where
         .then(comment_scope(
             DebugVerbosity::Scopes,
             "DefMutex",
-            name_string().then(take()).map_with_context(|(name, sync_level), context| {
+            name_string().then(take()).map_with_context(|(name, sync_flags), context| {
                 try_with_context!(
                     context,
                     context.namespace.add_value_at_resolved_path(
                         name,
                         &context.current_scope,
-                        AmlValue::Mutex { sync_level }
+                        AmlValue::Mutex { sync_level: sync_flags.get_bits(0..4) }
                     )
                 );
                 (Ok(()), context)
@@ -828,6 +1114,28 @@ where
         .discard_result()
 }
 
+pub fn def_event<'a, 'c>() -> impl Parser<'a, 'c, ()>
+where
+    'c: 'a,
+{
+    /*
+     * DefEvent := ExtOpPrefix 0x02 NameString
+     */
+    ext_opcode(opcode::EXT_DEF_EVENT_OP)
+        .then(comment_scope(
+            DebugVerbosity::Scopes,
+            "DefEvent",
+            name_string().map_with_context(|name, context| {
+                try_with_context!(
+                    context,
+                    context.namespace.add_value_at_resolved_path(name, &context.current_scope, AmlValue::Event)
+                );
+                (Ok(()), context)
+            }),
+        ))
+        .discard_result()
+}
+
 pub fn term_arg<'a, 'c>() -> impl Parser<'a, 'c, AmlValue>
 where
     'c: 'a,
@@ -871,8 +1179,11 @@ where
      * The order of the parsers are important here, as DefPackage and DefVarPackage can be
      * accidently parsed as ComputationalDatas.
      */
-    // TODO: this doesn't yet parse DefVarPackage
-    comment_scope(DebugVerbosity::AllScopes, "DataObject", choice!(def_package(), computational_data()))
+    comment_scope(
+        DebugVerbosity::AllScopes,
+        "DataObject",
+        choice!(def_package(), def_var_package(), computational_data()),
+    )
 }
 
 pub fn computational_data<'a, 'c>() -> impl Parser<'a, 'c, AmlValue>
@@ -901,10 +1212,17 @@ where
                 None => return Err((input, context, Propagate::Err(AmlError::UnterminatedStringConstant))),
             };
 
-            let string = String::from(match str::from_utf8(&input[0..nul_position]) {
-                Ok(string) => string,
+            /*
+             * Strings can only contain ASCII characters between 0x01 and 0x7f. We've already found the first null,
+             * so only need to check for characters above 0x7f.
+             */
+            if !input[0..nul_position].is_ascii() {
+                return Err((input, context, Propagate::Err(AmlError::InvalidStringConstant)));
+            }
+            let string = match str::from_utf8(&input[0..nul_position]) {
+                Ok(string) => String::from(string),
                 Err(_) => return Err((input, context, Propagate::Err(AmlError::InvalidStringConstant))),
-            });
+            };
 
             Ok((&input[(nul_position + 1)..], context, AmlValue::String(string)))
         };
@@ -924,7 +1242,10 @@ where
             opcode::STRING_PREFIX => string_parser.parse(new_input, context),
             opcode::ZERO_OP => Ok((new_input, context, AmlValue::zero())),
             opcode::ONE_OP => Ok((new_input, context, AmlValue::one())),
-            opcode::ONES_OP => Ok((new_input, context, AmlValue::ones())),
+            opcode::ONES_OP => {
+                let ones = AmlValue::Integer(context.truncate_integer(u64::MAX));
+                Ok((new_input, context, ones))
+            }
 
             _ => Err((input, context, Propagate::Err(AmlError::WrongParser))),
         }
@@ -934,7 +1255,8 @@ where
         DebugVerbosity::AllScopes,
         "ComputationalData",
         choice!(
-            ext_opcode(opcode::EXT_REVISION_OP).map(|_| Ok(AmlValue::Integer(crate::AML_INTERPRETER_REVISION))),
+            ext_opcode(opcode::EXT_REVISION_OP)
+                .map_with_context(|_, context| (Ok(AmlValue::Integer(context.interpreter_revision)), context)),
             const_parser,
             def_buffer()
         ),
@@ -944,7 +1266,756 @@ where
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::test_utils::*;
+    use crate::{test_utils::*, value::FieldAccessType};
+    use alloc::vec;
+
+    #[test]
+    fn test_def_name() {
+        let mut context = make_test_context();
+
+        /*
+         * Name(FOO, 0x1234)
+         * Scope(\_SB) {
+         *     Name(BAR, "AB")
+         * }
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x08, b'F', b'O', b'O', b'_', 0x0b, 0x34, 0x12, 0x10, 0x0f, b'\\', b'_', b'S', b'B', b'_', 0x08,
+                b'B', b'A', b'R', b'_', 0x0d, b'A', b'B', 0x00,
+            ]),
+            Ok(())
+        );
+        assert!(crudely_cmp_values(
+            context.namespace.get_by_path(&AmlName::from_str("\\FOO").unwrap()).unwrap(),
+            &AmlValue::Integer(0x1234)
+        ));
+        assert!(crudely_cmp_values(
+            context.namespace.get_by_path(&AmlName::from_str("\\_SB.BAR").unwrap()).unwrap(),
+            &AmlValue::String(String::from("AB"))
+        ));
+    }
+
+    #[test]
+    fn test_def_alias() {
+        let mut context = make_test_context();
+
+        /*
+         * Alias(FOO, BAR)
+         * Name(FOO, 0x07)
+         * Alias(BAR, BAZ)
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x06, b'F', b'O', b'O', b'_', b'B', b'A', b'R', b'_', 0x08, b'F', b'O', b'O', b'_', 0x0a, 0x07,
+                0x06, b'B', b'A', b'R', b'_', b'B', b'A', b'Z', b'_',
+            ]),
+            Ok(())
+        );
+        assert!(crudely_cmp_values(
+            context.namespace.get_by_path(&AmlName::from_str("\\BAR").unwrap()).unwrap(),
+            &AmlValue::Integer(0x07)
+        ));
+        assert!(crudely_cmp_values(
+            context.namespace.get_by_path(&AmlName::from_str("\\BAZ").unwrap()).unwrap(),
+            &AmlValue::Integer(0x07)
+        ));
+        assert_eq!(
+            context.namespace.search(&AmlName::from_str("BAZ").unwrap(), &AmlName::root()),
+            Ok((
+                AmlName::from_str("\\FOO").unwrap(),
+                context.namespace.get_handle(&AmlName::from_str("\\FOO").unwrap()).unwrap()
+            ))
+        );
+
+        // Alias(FOO, BAR)
+        assert_eq!(
+            context.parse_table(&[0x06, b'F', b'O', b'O', b'_', b'B', b'A', b'R', b'_']).map_err(|err| err.error),
+            Err(AmlError::NameCollision(AmlName::from_str("\\BAR").unwrap()))
+        );
+    }
+
+    #[test]
+    fn test_def_method() {
+        let mut context = make_test_context();
+
+        /*
+         * Method(FOO, 2, Serialized, 3) { ... }
+         *
+         * The body isn't valid AML, which is fine because it isn't parsed until the method is invoked.
+         */
+        assert_eq!(context.parse_table(&[0x14, 0x08, b'F', b'O', b'O', b'_', 0x3a, 0xa4, 0x0a]), Ok(()));
+        match context.namespace.get_by_path(&AmlName::from_str("\\FOO").unwrap()) {
+            Ok(AmlValue::Method { flags, code: MethodCode::Aml(code) }) => {
+                assert_eq!(flags.arg_count(), 2);
+                assert_eq!(flags.serialize(), true);
+                assert_eq!(flags.sync_level(), 3);
+                assert_eq!(*code, [0xa4, 0x0a]);
+            }
+            other => panic!("Unexpected value: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_def_external() {
+        let mut context = make_test_context();
+
+        /*
+         * External(\_SB.FOO, MethodObj, 2)
+         * External(\_SB.PCI0.BAR, IntObj, 0)
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x15, b'\\', 0x2e, b'_', b'S', b'B', b'_', b'F', b'O', b'O', b'_', 0x08, 0x02, 0x15, b'\\', 0x2f,
+                0x03, b'_', b'S', b'B', b'_', b'P', b'C', b'I', b'0', b'B', b'A', b'R', b'_', 0x01, 0x00,
+            ]),
+            Ok(())
+        );
+        let handle = context.namespace.get_handle(&AmlName::from_str("\\_SB.FOO").unwrap()).unwrap();
+        assert!(crudely_cmp_values(
+            context.namespace.get(handle).unwrap(),
+            &AmlValue::External { object_type: 8, arg_count: 2 }
+        ));
+
+        // Invocations of the method can be parsed before it's defined, but fail: `\_SB.FOO(One, One)`
+        context.current_scope = AmlName::from_str("\\_SB").unwrap();
+        check_err!(
+            expression_opcode().parse(&[b'F', b'O', b'O', b'_', 0x01, 0x01, 0xa3], &mut context),
+            AmlError::ValueDoesNotExist(_),
+            &[b'F', b'O', b'O', b'_', 0x01, 0x01, 0xa3]
+        );
+        context.current_scope = AmlName::root();
+
+        /*
+         * Method(\_SB.FOO, 2) { }
+         * Name(BAZ, One)
+         * External(BAZ, IntObj, 0)
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x14, 0x0c, b'\\', 0x2e, b'_', b'S', b'B', b'_', b'F', b'O', b'O', b'_', 0x02, 0x08, b'B', b'A',
+                b'Z', b'_', 0x01, 0x15, b'B', b'A', b'Z', b'_', 0x01, 0x00,
+            ]),
+            Ok(())
+        );
+        match context.namespace.get(handle) {
+            Ok(AmlValue::Method { flags, .. }) => assert_eq!(flags.arg_count(), 2),
+            other => panic!("Unexpected value: {:?}", other),
+        }
+        assert!(crudely_cmp_values(
+            context.namespace.get_by_path(&AmlName::from_str("\\BAZ").unwrap()).unwrap(),
+            &AmlValue::Integer(1)
+        ));
+    }
+
+    #[test]
+    fn test_def_device() {
+        let mut context = make_test_context();
+
+        /*
+         * Device(\_SB.PCI0) {
+         *     Name(_HID, EisaId("PNP0A03"))
+         * }
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x5b, 0x82, 0x15, b'\\', 0x2e, b'_', b'S', b'B', b'_', b'P', b'C', b'I', b'0', 0x08, b'_', b'H',
+                b'I', b'D', 0x0c, 0x41, 0xd0, 0x0a, 0x03,
+            ]),
+            Ok(())
+        );
+        assert!(crudely_cmp_values(
+            context.namespace.get_by_path(&AmlName::from_str("\\_SB.PCI0").unwrap()).unwrap(),
+            &AmlValue::Device
+        ));
+        assert!(crudely_cmp_values(
+            context.namespace.get_by_path(&AmlName::from_str("\\_SB.PCI0._HID").unwrap()).unwrap(),
+            &AmlValue::Integer(0x030ad041)
+        ));
+        assert_eq!(context.current_scope, AmlName::root());
+    }
+
+    #[test]
+    fn test_def_processor() {
+        let mut context = make_test_context();
+
+        /*
+         * Processor(\_PR.CPU0, 0x01, 0x00000810, 0x06) {
+         *     Name(FOO, One)
+         * }
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x5b, 0x83, 0x17, b'\\', 0x2e, b'_', b'P', b'R', b'_', b'C', b'P', b'U', b'0', 0x01, 0x10, 0x08,
+                0x00, 0x00, 0x06, 0x08, b'F', b'O', b'O', b'_', 0x01,
+            ]),
+            Ok(())
+        );
+        assert!(crudely_cmp_values(
+            context.namespace.get_by_path(&AmlName::from_str("\\_PR.CPU0").unwrap()).unwrap(),
+            &AmlValue::Processor { id: 0x01, pblk_address: 0x810, pblk_len: 0x06 }
+        ));
+        assert!(crudely_cmp_values(
+            context.namespace.get_by_path(&AmlName::from_str("\\_PR.CPU0.FOO").unwrap()).unwrap(),
+            &AmlValue::Integer(1)
+        ));
+        assert_eq!(context.current_scope, AmlName::root());
+    }
+
+    #[test]
+    fn test_def_thermal_zone() {
+        let mut context = make_test_context();
+
+        /*
+         * ThermalZone(\_TZ.THRM) {
+         *     Method(_TMP, 0, NotSerialized) { Return(0x0bb8) }
+         * }
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x5b, 0x85, 0x16, b'\\', 0x2e, b'_', b'T', b'Z', b'_', b'T', b'H', b'R', b'M', 0x14, 0x0a, b'_',
+                b'T', b'M', b'P', 0x00, 0xa4, 0x0b, 0xb8, 0x0b,
+            ]),
+            Ok(())
+        );
+        assert!(crudely_cmp_values(
+            context.namespace.get_by_path(&AmlName::from_str("\\_TZ.THRM").unwrap()).unwrap(),
+            &AmlValue::ThermalZone
+        ));
+        match context.namespace.get_by_path(&AmlName::from_str("\\_TZ.THRM._TMP").unwrap()) {
+            Ok(AmlValue::Method { flags, code: MethodCode::Aml(code) }) => {
+                assert_eq!(flags.arg_count(), 0);
+                assert_eq!(*code, [0xa4, 0x0b, 0xb8, 0x0b]);
+            }
+            other => panic!("Unexpected value: {:?}", other),
+        }
+        assert_eq!(context.current_scope, AmlName::root());
+    }
+
+    #[test]
+    fn test_def_power_res() {
+        let mut context = make_test_context();
+
+        /*
+         * PowerResource(PWRA, 0x00, 0x0001) {
+         *     Method(_ON) { }
+         *     Method(_OFF) { }
+         * }
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x5b, 0x84, 0x16, b'P', b'W', b'R', b'A', 0x00, 0x01, 0x00, 0x14, 0x06, b'_', b'O', b'N', b'_',
+                0x00, 0x14, 0x06, b'_', b'O', b'F', b'F', 0x00,
+            ]),
+            Ok(())
+        );
+        assert!(crudely_cmp_values(
+            context.namespace.get_by_path(&AmlName::from_str("\\PWRA").unwrap()).unwrap(),
+            &AmlValue::PowerResource { system_level: 0x00, resource_order: 0x0001 }
+        ));
+        for method in &["\\PWRA._ON", "\\PWRA._OFF"] {
+            match context.namespace.get_by_path(&AmlName::from_str(method).unwrap()) {
+                Ok(AmlValue::Method { code: MethodCode::Aml(code), .. }) => assert!(code.is_empty()),
+                other => panic!("Unexpected value for {}: {:?}", method, other),
+            }
+        }
+        assert_eq!(context.current_scope, AmlName::root());
+    }
+
+    #[test]
+    fn test_def_mutex() {
+        let mut context = make_test_context();
+
+        /*
+         * Mutex(MUTX, 3), but with some of the reserved bits of SyncFlags set
+         * Scope(\_SB) {
+         *     Mutex(LOCK, 0)
+         * }
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x5b, 0x01, b'M', b'U', b'T', b'X', 0xf3, 0x10, 0x0d, b'\\', b'_', b'S', b'B', b'_', 0x5b, 0x01,
+                b'L', b'O', b'C', b'K', 0x00,
+            ]),
+            Ok(())
+        );
+        assert!(crudely_cmp_values(
+            context.namespace.get_by_path(&AmlName::from_str("\\MUTX").unwrap()).unwrap(),
+            &AmlValue::Mutex { sync_level: 3 }
+        ));
+        assert!(crudely_cmp_values(
+            context.namespace.get_by_path(&AmlName::from_str("\\_SB.LOCK").unwrap()).unwrap(),
+            &AmlValue::Mutex { sync_level: 0 }
+        ));
+    }
+
+    #[test]
+    fn test_def_event() {
+        let mut context = make_test_context();
+
+        /*
+         * Scope(\_SB) {
+         *     Event(EVT0)
+         * }
+         */
+        assert_eq!(
+            context.parse_table(&[0x10, 0x0c, b'\\', b'_', b'S', b'B', b'_', 0x5b, 0x02, b'E', b'V', b'T', b'0']),
+            Ok(())
+        );
+        assert!(crudely_cmp_values(
+            context.namespace.get_by_path(&AmlName::from_str("\\_SB.EVT0").unwrap()).unwrap(),
+            &AmlValue::Event
+        ));
+        assert_eq!(
+            context.namespace.search(&AmlName::from_str("EVT0").unwrap(), &AmlName::from_str("\\_SB").unwrap()),
+            Ok((
+                AmlName::from_str("\\_SB.EVT0").unwrap(),
+                context.namespace.get_handle(&AmlName::from_str("\\_SB.EVT0").unwrap()).unwrap()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_packages() {
+        let mut context = make_test_context();
+
+        // Package(3) { 1, Package() { 2 } }
+        check_ok_value!(
+            data_object().parse(&[0x12, 0x08, 0x03, 0x01, 0x12, 0x04, 0x01, 0x0a, 0x02], &mut context),
+            AmlValue::Package(vec![
+                AmlValue::Integer(1),
+                AmlValue::Package(vec![AmlValue::Integer(2)]),
+                AmlValue::Uninitialized
+            ]),
+            &[]
+        );
+        // Package(1) { 1, 2 }
+        check_err!(
+            data_object().parse(&[0x12, 0x05, 0x01, 0x01, 0x0a, 0x02], &mut context),
+            AmlError::MalformedPackage,
+            &[0x12, 0x05, 0x01, 0x01, 0x0a, 0x02]
+        );
+    }
+
+    #[test]
+    fn test_var_packages() {
+        let mut context = make_test_context();
+
+        // VarPackage(3) { 1, 2 }
+        check_ok_value!(
+            data_object().parse(&[0x13, 0x06, 0x0a, 0x03, 0x01, 0x0a, 0x02], &mut context),
+            AmlValue::Package(vec![AmlValue::Integer(1), AmlValue::Integer(2), AmlValue::Uninitialized]),
+            &[]
+        );
+        // VarPackage(1) {}
+        check_ok_value!(
+            data_object().parse(&[0x13, 0x02, 0x01, 0xa4], &mut context),
+            AmlValue::Package(vec![AmlValue::Uninitialized]),
+            &[0xa4]
+        );
+    }
+
+    #[test]
+    fn test_buffers() {
+        let mut context = make_test_context();
+
+        // Buffer(4) { 0x01, 0x02 }
+        check_ok_value!(
+            computational_data().parse(&[0x11, 0x05, 0x0a, 0x04, 0x01, 0x02, 0x33], &mut context),
+            AmlValue::Buffer(Arc::new(Spinlock::new(vec![0x01, 0x02, 0x00, 0x00]))),
+            &[0x33]
+        );
+        // Buffer(1) { 0x01, 0x02 }
+        check_ok_value!(
+            computational_data().parse(&[0x11, 0x05, 0x0a, 0x01, 0x01, 0x02], &mut context),
+            AmlValue::Buffer(Arc::new(Spinlock::new(vec![0x01, 0x02]))),
+            &[]
+        );
+        // Buffer(2) {}
+        check_ok_value!(
+            computational_data().parse(&[0x11, 0x03, 0x0a, 0x02], &mut context),
+            AmlValue::Buffer(Arc::new(Spinlock::new(vec![0x00, 0x00]))),
+            &[]
+        );
+    }
+
+    #[test]
+    fn test_named_fields() {
+        let mut context = make_test_context();
+
+        /*
+         * OperationRegion(GIO0, SystemIO, 0x125, 0x02)
+         * Field(GIO0, ByteAcc, NoLock, Preserve) {
+         *     GLB1, 1,
+         *     GLB2, 9,
+         * }
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x5b, 0x80, b'G', b'I', b'O', b'0', 0x01, 0x0b, 0x25, 0x01, 0x0a, 0x02, 0x5b, 0x81, 0x10, b'G',
+                b'I', b'O', b'0', 0x01, b'G', b'L', b'B', b'1', 0x01, b'G', b'L', b'B', b'2', 0x09,
+            ]),
+            Ok(())
+        );
+        assert!(matches!(
+            context.namespace.get_by_path(&AmlName::from_str("\\GLB1").unwrap()),
+            Ok(AmlValue::Field { offset: 0, length: 1, .. })
+        ));
+        assert!(matches!(
+            context.namespace.get_by_path(&AmlName::from_str("\\GLB2").unwrap()),
+            Ok(AmlValue::Field { offset: 1, length: 9, .. })
+        ));
+    }
+
+    #[test]
+    fn test_reserved_fields() {
+        let mut context = make_test_context();
+
+        /*
+         * OperationRegion(GIO0, SystemIO, 0x125, 0x02)
+         * Field(GIO0, ByteAcc, NoLock, Preserve) {
+         *     GLB1, 1,
+         *     , 6,
+         *     GLB2, 1,
+         *     Offset(2),
+         * }
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x5b, 0x80, b'G', b'I', b'O', b'0', 0x01, 0x0b, 0x25, 0x01, 0x0a, 0x02, 0x5b, 0x81, 0x14, b'G',
+                b'I', b'O', b'0', 0x01, b'G', b'L', b'B', b'1', 0x01, 0x00, 0x06, b'G', b'L', b'B', b'2', 0x01,
+                0x00, 0x08,
+            ]),
+            Ok(())
+        );
+        assert!(matches!(
+            context.namespace.get_by_path(&AmlName::from_str("\\GLB1").unwrap()),
+            Ok(AmlValue::Field { offset: 0, length: 1, .. })
+        ));
+        assert!(matches!(
+            context.namespace.get_by_path(&AmlName::from_str("\\GLB2").unwrap()),
+            Ok(AmlValue::Field { offset: 7, length: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn test_access_fields() {
+        let mut context = make_test_context();
+
+        /*
+         * OperationRegion(GIO0, SystemIO, 0x125, 0x04)
+         * Field(GIO0, ByteAcc, NoLock, Preserve) {
+         *     GLB1, 8,
+         *     AccessAs(WordAcc, 0),
+         *     GLB2, 16,
+         * }
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x5b, 0x80, b'G', b'I', b'O', b'0', 0x01, 0x0b, 0x25, 0x01, 0x0a, 0x04, 0x5b, 0x81, 0x13, b'G',
+                b'I', b'O', b'0', 0x01, b'G', b'L', b'B', b'1', 0x08, 0x01, 0x02, 0x00, b'G', b'L', b'B', b'2',
+                0x10,
+            ]),
+            Ok(())
+        );
+        match context.namespace.get_by_path(&AmlName::from_str("\\GLB1").unwrap()) {
+            Ok(AmlValue::Field { flags, offset: 0, length: 8, .. }) => {
+                assert_eq!(flags.access_type(), Ok(FieldAccessType::Byte))
+            }
+            other => panic!("Unexpected value: {:?}", other),
+        }
+        match context.namespace.get_by_path(&AmlName::from_str("\\GLB2").unwrap()) {
+            Ok(AmlValue::Field { flags, offset: 8, length: 16, .. }) => {
+                assert_eq!(flags.access_type(), Ok(FieldAccessType::Word))
+            }
+            other => panic!("Unexpected value: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_extended_access_fields() {
+        let mut context = make_test_context();
+
+        /*
+         * OperationRegion(GSB0, GenericSerialBus, 0x00, 0x100)
+         * Field(GSB0, BufferAcc, NoLock, Preserve) {
+         *     AccessAs(BufferAcc, AttribBytes(4)),
+         *     CMD0, 8,
+         * }
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x5b, 0x80, b'G', b'S', b'B', b'0', 0x09, 0x00, 0x0b, 0x00, 0x01, 0x5b, 0x81, 0x0f, b'G', b'S',
+                b'B', b'0', 0x05, 0x03, 0x05, 0x0b, 0x04, b'C', b'M', b'D', b'0', 0x08,
+            ]),
+            Ok(())
+        );
+        match context.namespace.get_by_path(&AmlName::from_str("\\CMD0").unwrap()) {
+            Ok(AmlValue::Field { flags, offset: 0, length: 8, access_attrib, .. }) => {
+                assert_eq!(flags.access_type(), Ok(FieldAccessType::Buffer));
+                assert_eq!(*access_attrib, Some(FieldAccessAttrib::Bytes(4)));
+            }
+            other => panic!("Unexpected value: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_connect_fields() {
+        let mut context = make_test_context();
+
+        /*
+         * Name(RES0, Buffer() { 0x79, 0x00 })
+         * OperationRegion(GPO0, GeneralPurposeIo, 0x00, 0x01)
+         * Field(GPO0, ByteAcc, NoLock, Preserve) {
+         *     Connection(RES0),
+         *     PIN0, 1,
+         *     Connection(Buffer() { 0x79, 0x01 }),
+         *     PIN1, 1,
+         * }
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x08, b'R', b'E', b'S', b'0', 0x11, 0x05, 0x0a, 0x02, 0x79, 0x00, 0x5b, 0x80, b'G', b'P', b'O',
+                b'0', 0x08, 0x00, 0x0a, 0x01, 0x5b, 0x81, 0x1c, b'G', b'P', b'O', b'0', 0x01, 0x02, b'R', b'E',
+                b'S', b'0', b'P', b'I', b'N', b'0', 0x01, 0x02, 0x11, 0x05, 0x0a, 0x02, 0x79, 0x01, b'P', b'I',
+                b'N', b'1', 0x01,
+            ]),
+            Ok(())
+        );
+        match context.namespace.get_by_path(&AmlName::from_str("\\PIN0").unwrap()) {
+            Ok(AmlValue::Field { offset: 0, length: 1, connection: Some(connection), .. }) => {
+                assert_eq!(*connection.lock(), [0x79, 0x00])
+            }
+            other => panic!("Unexpected value: {:?}", other),
+        }
+        match context.namespace.get_by_path(&AmlName::from_str("\\PIN1").unwrap()) {
+            Ok(AmlValue::Field { offset: 1, length: 1, connection: Some(connection), .. }) => {
+                assert_eq!(*connection.lock(), [0x79, 0x01])
+            }
+            other => panic!("Unexpected value: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_op_region_spaces() {
+        let mut context = make_test_context();
+
+        /*
+         * OperationRegion(OEM0, 0x80, 0x00, 0x10)
+         * OperationRegion(OEM1, 0xff, 0x00, 0x10)
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x5b, 0x80, b'O', b'E', b'M', b'0', 0x80, 0x00, 0x0a, 0x10, 0x5b, 0x80, b'O', b'E', b'M', b'1',
+                0xff, 0x00, 0x0a, 0x10,
+            ]),
+            Ok(())
+        );
+        for (name, space) in [("\\OEM0", 0x80), ("\\OEM1", 0xff)] {
+            assert!(matches!(
+                context.namespace.get_by_path(&AmlName::from_str(name).unwrap()),
+                Ok(AmlValue::OpRegion { region: RegionSpace::OemDefined(s), offset: 0, length: 0x10, .. })
+                    if *s == space
+            ));
+        }
+
+        // OperationRegion(RSV0, 0x0a, 0x00, 0x10)
+        assert_eq!(
+            context
+                .parse_table(&[0x5b, 0x80, b'R', b'S', b'V', b'0', 0x0a, 0x00, 0x0a, 0x10])
+                .map_err(|err| err.error),
+            Err(AmlError::InvalidRegionSpace(0x0a))
+        );
+    }
+
+    #[test]
+    fn test_def_op_region_consumes_opcode() {
+        let mut context = make_test_context();
+
+        // OperationRegion(REG0, SystemMemory, 0x00, 0x10), followed by a Noop
+        check_ok!(
+            def_op_region()
+                .parse(&[0x5b, 0x80, b'R', b'E', b'G', b'0', 0x00, 0x00, 0x0a, 0x10, 0xa3], &mut context),
+            (),
+            &[0xa3]
+        );
+
+        // Other extended opcodes should be left for the parser they belong to
+        check_err!(
+            def_op_region().parse(&[0x5b, 0x81, b'R', b'E', b'G', b'0'], &mut context),
+            AmlError::WrongParser,
+            &[0x5b, 0x81, b'R', b'E', b'G', b'0']
+        );
+    }
+
+    #[test]
+    fn test_field_out_of_region_bounds() {
+        let mut context = make_test_context();
+
+        /*
+         * OperationRegion(GIO0, SystemIO, 0x125, 0x02)
+         * Field(GIO0, ByteAcc, NoLock, Preserve) {
+         *     GLB1, 1,
+         *     GLB2, 16,
+         * }
+         */
+        assert_eq!(
+            context
+                .parse_table(&[
+                    0x5b, 0x80, b'G', b'I', b'O', b'0', 0x01, 0x0b, 0x25, 0x01, 0x0a, 0x02, 0x5b, 0x81, 0x10,
+                    b'G', b'I', b'O', b'0', 0x01, b'G', b'L', b'B', b'1', 0x01, b'G', b'L', b'B', b'2', 0x10,
+                ])
+                .map_err(|err| err.error),
+            Err(AmlError::FieldOutOfRegionBounds)
+        );
+    }
+
+    #[test]
+    fn test_data_regions() {
+        let mut context = make_test_context();
+
+        // DataTableRegion(DREG, "TEST", "OEMID", "OEMTABLE")
+        assert_eq!(
+            context.parse_table(&[
+                0x5b, 0x88, b'D', b'R', b'E', b'G', 0x0d, b'T', b'E', b'S', b'T', 0x00, 0x0d, b'O', b'E', b'M',
+                b'I', b'D', 0x00, 0x0d, b'O', b'E', b'M', b'T', b'A', b'B', b'L', b'E', 0x00,
+            ]),
+            Ok(())
+        );
+        assert!(crudely_cmp_values(
+            context.namespace.get_by_path(&AmlName::from_str("\\DREG").unwrap()).unwrap(),
+            &AmlValue::OpRegion {
+                region: RegionSpace::SystemMemory,
+                offset: 0x1000,
+                length: 0x40,
+                parent_device: None
+            }
+        ));
+
+        // DataTableRegion(NONE, "NOPE", "", "")
+        assert_eq!(
+            context
+                .parse_table(&[
+                    0x5b, 0x88, b'N', b'O', b'N', b'E', 0x0d, b'N', b'O', b'P', b'E', 0x00, 0x0d, 0x00, 0x0d,
+                    0x00,
+                ])
+                .map_err(|err| err.error),
+            Err(AmlError::DataRegionTableNotFound {
+                signature: String::from("NOPE"),
+                oem_id: String::new(),
+                oem_table_id: String::new(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_index_fields() {
+        let mut context = make_test_context();
+
+        /*
+         * Device(EC0) {
+         *     OperationRegion(ECIO, SystemIO, 0x62, 0x02)
+         *     Field(ECIO, ByteAcc, NoLock, Preserve) {
+         *         IDX0, 8,
+         *         DAT0, 8,
+         *     }
+         *     IndexField(IDX0, DAT0, ByteAcc, NoLock, Preserve) {
+         *         Offset(0x10),
+         *         TMP0, 8,
+         *         TMP1, 16,
+         *     }
+         * }
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x5b, 0x82, 0x3b, b'E', b'C', b'0', b'_', 0x5b, 0x80, b'E', b'C', b'I', b'O', 0x01, 0x0a, 0x62,
+                0x0a, 0x02, 0x5b, 0x81, 0x10, b'E', b'C', b'I', b'O', 0x01, b'I', b'D', b'X', b'0', 0x08, b'D',
+                b'A', b'T', b'0', 0x08, 0x5b, 0x86, 0x17, b'I', b'D', b'X', b'0', b'D', b'A', b'T', b'0', 0x01,
+                0x00, 0x40, 0x08, b'T', b'M', b'P', b'0', 0x08, b'T', b'M', b'P', b'1', 0x10,
+            ]),
+            Ok(())
+        );
+
+        let index = context.namespace.get_handle(&AmlName::from_str("\\EC0.IDX0").unwrap()).unwrap();
+        let data = context.namespace.get_handle(&AmlName::from_str("\\EC0.DAT0").unwrap()).unwrap();
+        assert!(crudely_cmp_values(
+            context.namespace.get_by_path(&AmlName::from_str("\\EC0.TMP0").unwrap()).unwrap(),
+            &AmlValue::IndexField { index, data, flags: FieldFlags::new(0x01), offset: 0x80, length: 8 }
+        ));
+        assert!(crudely_cmp_values(
+            context.namespace.get_by_path(&AmlName::from_str("\\EC0.TMP1").unwrap()).unwrap(),
+            &AmlValue::IndexField { index, data, flags: FieldFlags::new(0x01), offset: 0x88, length: 16 }
+        ));
+    }
+
+    #[test]
+    fn test_create_fixed_width_fields() {
+        let mut context = make_test_context();
+
+        /*
+         * Name(BUF0, Buffer(8) { 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08 })
+         * CreateDWordField(BUF0, 4, DWD0)
+         * CreateBitField(BUF0, 9, BIT0)
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x08, b'B', b'U', b'F', b'0', 0x11, 0x0b, 0x0a, 0x08, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
+                0x08, 0x8a, b'B', b'U', b'F', b'0', 0x0a, 0x04, b'D', b'W', b'D', b'0', 0x8d, b'B', b'U', b'F',
+                b'0', 0x0a, 0x09, b'B', b'I', b'T', b'0',
+            ]),
+            Ok(())
+        );
+
+        let dword_field = context.namespace.get_by_path(&AmlName::from_str("\\DWD0").unwrap()).unwrap();
+        match dword_field {
+            AmlValue::BufferField { offset, length, .. } => {
+                assert_eq!(*offset, 32);
+                assert_eq!(*length, 32);
+            }
+            other => panic!("Unexpected value: {:?}", other),
+        }
+        assert!(crudely_cmp_values(
+            &dword_field.read_buffer_field(&context).unwrap(),
+            &AmlValue::Integer(0x08070605)
+        ));
+
+        let bit_field = context.namespace.get_by_path(&AmlName::from_str("\\BIT0").unwrap()).unwrap();
+        match bit_field {
+            AmlValue::BufferField { offset, length, .. } => {
+                assert_eq!(*offset, 9);
+                assert_eq!(*length, 1);
+            }
+            other => panic!("Unexpected value: {:?}", other),
+        }
+        assert!(crudely_cmp_values(&bit_field.read_buffer_field(&context).unwrap(), &AmlValue::Integer(1)));
+    }
+
+    #[test]
+    fn test_create_field() {
+        let mut context = make_test_context();
+
+        /*
+         * Name(BUF0, Buffer(2) { 0xa0, 0xff })
+         * CreateField(BUF0, 5, 3, FLD0)
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x08, b'B', b'U', b'F', b'0', 0x11, 0x05, 0x0a, 0x02, 0xa0, 0xff, 0x5b, 0x13, b'B', b'U', b'F',
+                b'0', 0x0a, 0x05, 0x0a, 0x03, b'F', b'L', b'D', b'0',
+            ]),
+            Ok(())
+        );
+
+        let field = context.namespace.get_by_path(&AmlName::from_str("\\FLD0").unwrap()).unwrap();
+        match field {
+            AmlValue::BufferField { offset, length, .. } => {
+                assert_eq!(*offset, 5);
+                assert_eq!(*length, 3);
+            }
+            other => panic!("Unexpected value: {:?}", other),
+        }
+        assert!(crudely_cmp_values(&field.read_buffer_field(&context).unwrap(), &AmlValue::Integer(0b101)));
+    }
 
     #[test]
     fn test_computational_data() {
@@ -995,5 +2066,95 @@ mod test {
             AmlValue::String(String::from("ABCD")),
             &[0xff, 0xf5]
         );
+        context.interpreter_revision = 5;
+        check_ok_value!(
+            computational_data().parse(&[0x5b, 0x30, 0x00], &mut context),
+            AmlValue::Integer(5),
+            &[0x00]
+        );
+        check_ok_value!(
+            computational_data().parse(&[0x0d, b'\0', 0x0a], &mut context),
+            AmlValue::String(String::new()),
+            &[0x0a]
+        );
+        check_err!(
+            computational_data().parse(&[0x0d, b'A', 0xc3, 0xa9, b'\0'], &mut context),
+            AmlError::InvalidStringConstant,
+            &[0x0d, b'A', 0xc3, 0xa9, b'\0']
+        );
+        check_err!(
+            computational_data().parse(&[0x0d, b'A', b'B', b'C'], &mut context),
+            AmlError::UnterminatedStringConstant,
+            &[0x0d, b'A', b'B', b'C']
+        );
+    }
+
+    #[test]
+    fn test_wide_integer_constants_in_table() {
+        let mut context = make_test_context();
+
+        /*
+         * Check that the objects following each constant are still parsed from the right offset.
+         *
+         * Name(QWRD, 0x0123456789abcdef)
+         * Name(DWRD, 0x12345678)
+         * Name(BYTE, 0x05)
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x08, b'Q', b'W', b'R', b'D', 0x0e, 0xef, 0xcd, 0xab, 0x89, 0x67, 0x45, 0x23, 0x01, 0x08, b'D',
+                b'W', b'R', b'D', 0x0c, 0x78, 0x56, 0x34, 0x12, 0x08, b'B', b'Y', b'T', b'E', 0x0a, 0x05,
+            ]),
+            Ok(())
+        );
+
+        for &(name, value) in &[("\\QWRD", 0x0123456789abcdef), ("\\DWRD", 0x12345678), ("\\BYTE", 0x05)] {
+            assert!(crudely_cmp_values(
+                context.namespace.get_by_path(&AmlName::from_str(name).unwrap()).unwrap(),
+                &AmlValue::Integer(value)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_adjacent_scopes() {
+        let mut context = make_test_context();
+
+        /*
+         * Each term list should stop exactly at the end of its PkgLength, so nothing from one object should be
+         * parsed as part of the object before it.
+         *
+         * Device(DEV0) { Name(INN, One) }
+         * Device(DEV1) { Name(INN, 2) }
+         * Name(OUT, 3)
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x5b, 0x82, 0x0b, b'D', b'E', b'V', b'0', 0x08, b'I', b'N', b'N', b'_', 0x01, 0x5b, 0x82, 0x0c,
+                b'D', b'E', b'V', b'1', 0x08, b'I', b'N', b'N', b'_', 0x0a, 0x02, 0x08, b'O', b'U', b'T', b'_',
+                0x0a, 0x03,
+            ]),
+            Ok(())
+        );
+
+        for &(name, value) in &[("\\DEV0.INN", 1), ("\\DEV1.INN", 2), ("\\OUT", 3)] {
+            assert!(crudely_cmp_values(
+                context.namespace.get_by_path(&AmlName::from_str(name).unwrap()).unwrap(),
+                &AmlValue::Integer(value)
+            ));
+        }
+        assert!(context.namespace.get_by_path(&AmlName::from_str("\\DEV0.DEV1").unwrap()).is_err());
+        assert!(context.namespace.get_by_path(&AmlName::from_str("\\DEV1.OUT").unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_list_of_without_progress() {
+        let mut context = make_test_context();
+        check_err!(
+            list_of(crate::parser::id()).parse(&[0x01, 0x02], &mut context),
+            AmlError::NoProgress,
+            &[0x01, 0x02]
+        );
+        check_ok!(list_of(take()).parse(&[0x01, 0x02], &mut context), (), &[]);
     }
 }
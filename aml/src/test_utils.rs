@@ -1,5 +1,14 @@
-use crate::{parser::Propagate, AmlContext, AmlValue, Handler};
-use alloc::boxed::Box;
+use crate::{
+    parser::Propagate,
+    value::{GsbAttribute, SmbusProtocol},
+    AmlContext,
+    AmlError,
+    AmlName,
+    AmlValue,
+    Handler,
+};
+use alloc::{boxed::Box, collections::BTreeMap, sync::Arc, vec::Vec};
+use spinning_top::Spinlock;
 
 struct TestHandler;
 
@@ -68,12 +77,360 @@ impl Handler for TestHandler {
     fn write_pci_u32(&self, _segment: u16, _bus: u8, device: u8, _function: u8, _offset: u16, _value: u32) {
         unimplemented!()
     }
+
+    fn stall(&self, _microseconds: u64) {}
+    fn sleep(&self, _milliseconds: u64) {}
+    fn monotonic_timer(&self) -> u64 {
+        unimplemented!()
+    }
+
+    fn handle_fatal_error(&self, _fatal_type: u8, _fatal_code: u32, _fatal_arg: u64) {
+        // Don't panic, so tests can check that `DefFatal` produces `AmlError::FatalError`
+    }
+
+    fn find_table(&self, signature: &str, oem_id: &str, oem_table_id: &str) -> Option<(u64, u64)> {
+        // Pretend that there's a single table, so that `DefDataRegion` can be tested
+        if (signature, oem_id, oem_table_id) == ("TEST", "OEMID", "OEMTABLE") {
+            Some((0x1000, 0x40))
+        } else {
+            None
+        }
+    }
 }
 
 pub(crate) fn make_test_context() -> AmlContext {
     AmlContext::new(Box::new(TestHandler), crate::DebugVerbosity::None)
 }
 
+/// An access made to the hardware modelled by a `RecordingHandler`. Widths are in bits. Mutexes being acquired and
+/// released are also recorded, so tests can check that accesses are made while holding them, as are operations on
+/// events. Serial bus transactions record the request passed to the handler, before the response is copied out.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub(crate) enum Access {
+    AcquireMutex(AmlName),
+    ReleaseMutex(AmlName),
+    SignalEvent(AmlName),
+    WaitForEvent(AmlName),
+    ResetEvent(AmlName),
+    ReadMemory { address: usize, width: u8 },
+    WriteMemory { address: usize, width: u8, value: u64 },
+    ReadIo { port: u16, width: u8 },
+    WriteIo { port: u16, width: u8, value: u32 },
+    ReadPci { address: PciAddress, width: u8 },
+    WritePci { address: PciAddress, width: u8, value: u32 },
+    ReadEc { address: u8 },
+    WriteEc { address: u8, value: u8 },
+    ReadCmos { index: u8 },
+    WriteCmos { index: u8, value: u8 },
+    ReadOem { space: u8, offset: u64, width: u8 },
+    WriteOem { space: u8, offset: u64, width: u8, value: u64 },
+    Gsb { connection: Vec<u8>, attribute: GsbAttribute, command: u8, data: Vec<u8> },
+    ReadGpio { connection: Vec<u8>, pin_offset: u16, bit_count: u16 },
+    WriteGpio { connection: Vec<u8>, pin_offset: u16, bit_count: u16, value: u64 },
+    Ipmi { network_function: u8, command: u8, request: Vec<u8> },
+    Smbus { protocol: SmbusProtocol, address: u8, command: u8, data: Vec<u8> },
+    Stall { microseconds: u64 },
+    Sleep { milliseconds: u64 },
+}
+
+/// The segment, bus, device, function, and offset of a register in PCI configuration space.
+pub(crate) type PciAddress = (u16, u8, u8, u8, u16);
+
+/// The state of the hardware modelled by a `RecordingHandler`. Tests can set it up before running AML, and then check
+/// what the AML did to it.
+#[derive(Default)]
+pub(crate) struct MockHardware {
+    /// The contents of physical memory. Addresses that haven't been written read as zero.
+    pub memory: BTreeMap<usize, u8>,
+    /// The contents of the IO port space. Ports that haven't been written read as zero.
+    pub io_ports: BTreeMap<u16, u8>,
+    /// The contents of PCI configuration space, addressed by byte. Registers that haven't been written read as
+    /// zero.
+    pub pci_config: BTreeMap<PciAddress, u8>,
+    /// The registers of the embedded controller. Registers that haven't been written read as zero.
+    pub ec: BTreeMap<u8, u8>,
+    /// The contents of the CMOS, addressed by index. Bytes that haven't been written read as zero.
+    pub cmos: BTreeMap<u8, u8>,
+    /// The registers of OEM-defined region spaces, addressed by space and offset. Each access reads or replaces
+    /// the whole register at its offset, and registers that haven't been written read as zero.
+    pub oem: BTreeMap<(u8, u64), u64>,
+    /// The buffer copied into the transaction buffer of every serial bus transaction (e.g. on a `GenericSerialBus`
+    /// or to an IPMI BMC), as the response of the device.
+    pub serial_bus_response: Vec<u8>,
+    /// The levels of GPIO pins, one per bit, indexed by their offset into the pin list of a connection. Pins of
+    /// different connections aren't distinguished.
+    pub gpio_pins: u64,
+    /// Every access that has been made to the hardware, in order.
+    pub accesses: Vec<Access>,
+    /// Every value that has been stored into the `Debug` object, in order.
+    pub debug_output: Vec<AmlValue>,
+    /// The current value of the monotonic timer. It advances by 10 (one microsecond) each time it is read.
+    pub timer: u64,
+}
+
+impl MockHardware {
+    fn read_memory(&mut self, address: usize, width: u8) -> u64 {
+        self.accesses.push(Access::ReadMemory { address, width });
+        (0..(width / 8)).fold(0, |value, i| {
+            value | (*self.memory.get(&(address + i as usize)).unwrap_or(&0) as u64) << (i * 8)
+        })
+    }
+
+    fn write_memory(&mut self, address: usize, width: u8, value: u64) {
+        self.accesses.push(Access::WriteMemory { address, width, value });
+        for i in 0..(width / 8) {
+            self.memory.insert(address + i as usize, (value >> (i * 8)) as u8);
+        }
+    }
+
+    fn read_io(&mut self, port: u16, width: u8) -> u32 {
+        self.accesses.push(Access::ReadIo { port, width });
+        (0..(width / 8))
+            .fold(0, |value, i| value | (*self.io_ports.get(&(port + i as u16)).unwrap_or(&0) as u32) << (i * 8))
+    }
+
+    fn write_io(&mut self, port: u16, width: u8, value: u32) {
+        self.accesses.push(Access::WriteIo { port, width, value });
+        for i in 0..(width / 8) {
+            self.io_ports.insert(port + i as u16, (value >> (i * 8)) as u8);
+        }
+    }
+
+    fn read_pci(&mut self, address: PciAddress, width: u8) -> u32 {
+        self.accesses.push(Access::ReadPci { address, width });
+        let (segment, bus, device, function, offset) = address;
+        (0..(width / 8)).fold(0, |value, i| {
+            let byte_address = (segment, bus, device, function, offset + i as u16);
+            value | (*self.pci_config.get(&byte_address).unwrap_or(&0) as u32) << (i * 8)
+        })
+    }
+
+    fn write_pci(&mut self, address: PciAddress, width: u8, value: u32) {
+        self.accesses.push(Access::WritePci { address, width, value });
+        let (segment, bus, device, function, offset) = address;
+        for i in 0..(width / 8) {
+            self.pci_config.insert((segment, bus, device, function, offset + i as u16), (value >> (i * 8)) as u8);
+        }
+    }
+}
+
+/// A handler that models some simple hardware, and records every access made to it, so tests can check how the
+/// interpreter accesses operation regions.
+struct RecordingHandler(Arc<Spinlock<MockHardware>>);
+
+impl Handler for RecordingHandler {
+    fn read_u8(&self, address: usize) -> u8 {
+        self.0.lock().read_memory(address, 8) as u8
+    }
+    fn read_u16(&self, address: usize) -> u16 {
+        self.0.lock().read_memory(address, 16) as u16
+    }
+    fn read_u32(&self, address: usize) -> u32 {
+        self.0.lock().read_memory(address, 32) as u32
+    }
+    fn read_u64(&self, address: usize) -> u64 {
+        self.0.lock().read_memory(address, 64)
+    }
+
+    fn write_u8(&mut self, address: usize, value: u8) {
+        self.0.lock().write_memory(address, 8, value as u64)
+    }
+    fn write_u16(&mut self, address: usize, value: u16) {
+        self.0.lock().write_memory(address, 16, value as u64)
+    }
+    fn write_u32(&mut self, address: usize, value: u32) {
+        self.0.lock().write_memory(address, 32, value as u64)
+    }
+    fn write_u64(&mut self, address: usize, value: u64) {
+        self.0.lock().write_memory(address, 64, value)
+    }
+
+    fn read_io_u8(&self, port: u16) -> u8 {
+        self.0.lock().read_io(port, 8) as u8
+    }
+    fn read_io_u16(&self, port: u16) -> u16 {
+        self.0.lock().read_io(port, 16) as u16
+    }
+    fn read_io_u32(&self, port: u16) -> u32 {
+        self.0.lock().read_io(port, 32)
+    }
+
+    fn write_io_u8(&self, port: u16, value: u8) {
+        self.0.lock().write_io(port, 8, value as u32)
+    }
+    fn write_io_u16(&self, port: u16, value: u16) {
+        self.0.lock().write_io(port, 16, value as u32)
+    }
+    fn write_io_u32(&self, port: u16, value: u32) {
+        self.0.lock().write_io(port, 32, value)
+    }
+
+    fn read_pci_u8(&self, segment: u16, bus: u8, device: u8, function: u8, offset: u16) -> u8 {
+        self.0.lock().read_pci((segment, bus, device, function, offset), 8) as u8
+    }
+    fn read_pci_u16(&self, segment: u16, bus: u8, device: u8, function: u8, offset: u16) -> u16 {
+        self.0.lock().read_pci((segment, bus, device, function, offset), 16) as u16
+    }
+    fn read_pci_u32(&self, segment: u16, bus: u8, device: u8, function: u8, offset: u16) -> u32 {
+        self.0.lock().read_pci((segment, bus, device, function, offset), 32)
+    }
+    fn write_pci_u8(&self, segment: u16, bus: u8, device: u8, function: u8, offset: u16, value: u8) {
+        self.0.lock().write_pci((segment, bus, device, function, offset), 8, value as u32)
+    }
+    fn write_pci_u16(&self, segment: u16, bus: u8, device: u8, function: u8, offset: u16, value: u16) {
+        self.0.lock().write_pci((segment, bus, device, function, offset), 16, value as u32)
+    }
+    fn write_pci_u32(&self, segment: u16, bus: u8, device: u8, function: u8, offset: u16, value: u32) {
+        self.0.lock().write_pci((segment, bus, device, function, offset), 32, value)
+    }
+
+    fn read_ec_u8(&self, address: u8) -> Result<u8, AmlError> {
+        let mut hardware = self.0.lock();
+        hardware.accesses.push(Access::ReadEc { address });
+        Ok(*hardware.ec.get(&address).unwrap_or(&0))
+    }
+    fn write_ec_u8(&self, address: u8, value: u8) -> Result<(), AmlError> {
+        let mut hardware = self.0.lock();
+        hardware.accesses.push(Access::WriteEc { address, value });
+        hardware.ec.insert(address, value);
+        Ok(())
+    }
+
+    fn read_cmos_u8(&self, index: u8) -> Result<u8, AmlError> {
+        let mut hardware = self.0.lock();
+        hardware.accesses.push(Access::ReadCmos { index });
+        Ok(*hardware.cmos.get(&index).unwrap_or(&0))
+    }
+    fn write_cmos_u8(&self, index: u8, value: u8) -> Result<(), AmlError> {
+        let mut hardware = self.0.lock();
+        hardware.accesses.push(Access::WriteCmos { index, value });
+        hardware.cmos.insert(index, value);
+        Ok(())
+    }
+
+    fn read_oem_region(&self, space: u8, offset: u64, width: u8) -> Result<u64, AmlError> {
+        let mut hardware = self.0.lock();
+        hardware.accesses.push(Access::ReadOem { space, offset, width });
+        Ok(*hardware.oem.get(&(space, offset)).unwrap_or(&0))
+    }
+    fn write_oem_region(&self, space: u8, offset: u64, width: u8, value: u64) -> Result<(), AmlError> {
+        let mut hardware = self.0.lock();
+        hardware.accesses.push(Access::WriteOem { space, offset, width, value });
+        hardware.oem.insert((space, offset), value);
+        Ok(())
+    }
+
+    fn gsb_transaction(
+        &self,
+        connection: &[u8],
+        attribute: GsbAttribute,
+        command: u8,
+        buffer: &mut [u8],
+    ) -> Result<(), AmlError> {
+        let mut hardware = self.0.lock();
+        hardware.accesses.push(Access::Gsb {
+            connection: connection.to_vec(),
+            attribute,
+            command,
+            data: buffer.to_vec(),
+        });
+        let length = usize::min(buffer.len(), hardware.serial_bus_response.len());
+        buffer[..length].copy_from_slice(&hardware.serial_bus_response[..length]);
+        Ok(())
+    }
+
+    fn smbus_transaction(
+        &self,
+        protocol: SmbusProtocol,
+        address: u8,
+        command: u8,
+        buffer: &mut [u8],
+    ) -> Result<(), AmlError> {
+        let mut hardware = self.0.lock();
+        hardware.accesses.push(Access::Smbus { protocol, address, command, data: buffer.to_vec() });
+        let length = usize::min(buffer.len(), hardware.serial_bus_response.len());
+        buffer[..length].copy_from_slice(&hardware.serial_bus_response[..length]);
+        Ok(())
+    }
+
+    fn ipmi_transaction(
+        &self,
+        network_function: u8,
+        command: u8,
+        request: &[u8],
+        response: &mut [u8],
+    ) -> Result<(), AmlError> {
+        let mut hardware = self.0.lock();
+        hardware.accesses.push(Access::Ipmi { network_function, command, request: request.to_vec() });
+        let length = usize::min(response.len(), hardware.serial_bus_response.len());
+        response[..length].copy_from_slice(&hardware.serial_bus_response[..length]);
+        Ok(())
+    }
+
+    fn read_gpio(&self, connection: &[u8], pin_offset: u16, bit_count: u16) -> Result<u64, AmlError> {
+        use bit_field::BitField;
+
+        let mut hardware = self.0.lock();
+        hardware.accesses.push(Access::ReadGpio { connection: connection.to_vec(), pin_offset, bit_count });
+        Ok(hardware.gpio_pins.get_bits((pin_offset as usize)..((pin_offset + bit_count) as usize)))
+    }
+    fn write_gpio(&self, connection: &[u8], pin_offset: u16, bit_count: u16, value: u64) -> Result<(), AmlError> {
+        use bit_field::BitField;
+
+        let mut hardware = self.0.lock();
+        hardware.accesses.push(Access::WriteGpio {
+            connection: connection.to_vec(),
+            pin_offset,
+            bit_count,
+            value,
+        });
+        hardware.gpio_pins.set_bits((pin_offset as usize)..((pin_offset + bit_count) as usize), value);
+        Ok(())
+    }
+
+    fn stall(&self, microseconds: u64) {
+        self.0.lock().accesses.push(Access::Stall { microseconds });
+    }
+    fn sleep(&self, milliseconds: u64) {
+        self.0.lock().accesses.push(Access::Sleep { milliseconds });
+    }
+    fn monotonic_timer(&self) -> u64 {
+        let mut hardware = self.0.lock();
+        let value = hardware.timer;
+        hardware.timer += 10;
+        value
+    }
+
+    fn acquire_mutex(&self, mutex: &AmlName, _timeout: u16) -> bool {
+        self.0.lock().accesses.push(Access::AcquireMutex(mutex.clone()));
+        true
+    }
+    fn release_mutex(&self, mutex: &AmlName) {
+        self.0.lock().accesses.push(Access::ReleaseMutex(mutex.clone()));
+    }
+
+    fn signal_event(&self, event: &AmlName) {
+        self.0.lock().accesses.push(Access::SignalEvent(event.clone()));
+    }
+    fn wait_for_event(&self, event: &AmlName, _timeout: u16) -> bool {
+        self.0.lock().accesses.push(Access::WaitForEvent(event.clone()));
+        true
+    }
+    fn reset_event(&self, event: &AmlName) {
+        self.0.lock().accesses.push(Access::ResetEvent(event.clone()));
+    }
+
+    fn debug_output(&self, value: &AmlValue) {
+        self.0.lock().debug_output.push(value.clone());
+    }
+}
+
+/// Make a context with a `RecordingHandler`, also returning the hardware it models.
+pub(crate) fn make_recording_test_context() -> (AmlContext, Arc<Spinlock<MockHardware>>) {
+    let hardware = Arc::new(Spinlock::new(MockHardware::default()));
+    (AmlContext::new(Box::new(RecordingHandler(hardware.clone())), crate::DebugVerbosity::None), hardware)
+}
+
 pub(crate) macro check_err($parse: expr, $error: pat, $remains: expr) {
     match $parse {
         Ok((remains, _, result)) => panic!("Expected Err, got {:#?}. Remaining = {:#x?}", result, remains),
@@ -119,6 +476,10 @@ pub(crate) fn crudely_cmp_values(a: &AmlValue, b: &AmlValue) -> bool {
     use crate::value::MethodCode;
 
     match a {
+        AmlValue::Uninitialized => match b {
+            AmlValue::Uninitialized => true,
+            _ => false,
+        },
         AmlValue::Boolean(a) => match b {
             AmlValue::Boolean(b) => a == b,
             _ => false,
@@ -142,9 +503,37 @@ pub(crate) fn crudely_cmp_values(a: &AmlValue, b: &AmlValue) -> bool {
             }
             _ => false,
         },
-        AmlValue::Field { region, flags, offset, length } => match b {
-            AmlValue::Field { region: b_region, flags: b_flags, offset: b_offset, length: b_length } => {
-                region == b_region && flags == b_flags && offset == b_offset && length == b_length
+        AmlValue::Field { region, flags, offset, length, access_attrib, connection } => match b {
+            AmlValue::Field {
+                region: b_region,
+                flags: b_flags,
+                offset: b_offset,
+                length: b_length,
+                access_attrib: b_access_attrib,
+                connection: b_connection,
+            } => {
+                region == b_region
+                    && flags == b_flags
+                    && offset == b_offset
+                    && length == b_length
+                    && access_attrib == b_access_attrib
+                    && match (connection, b_connection) {
+                        (Some(a), Some(b)) => *a.lock() == *b.lock(),
+                        (None, None) => true,
+                        _ => false,
+                    }
+            }
+            _ => false,
+        },
+        AmlValue::IndexField { index, data, flags, offset, length } => match b {
+            AmlValue::IndexField {
+                index: b_index,
+                data: b_data,
+                flags: b_flags,
+                offset: b_offset,
+                length: b_length,
+            } => {
+                index == b_index && data == b_data && flags == b_flags && offset == b_offset && length == b_length
             }
             _ => false,
         },
@@ -189,6 +578,10 @@ pub(crate) fn crudely_cmp_values(a: &AmlValue, b: &AmlValue) -> bool {
             AmlValue::Mutex { sync_level: b_sync_level } => sync_level == b_sync_level,
             _ => false,
         },
+        AmlValue::Event => match b {
+            AmlValue::Event => true,
+            _ => false,
+        },
         AmlValue::Package(a) => match b {
             AmlValue::Package(b) => {
                 for (a, b) in a.iter().zip(b) {
@@ -211,5 +604,25 @@ pub(crate) fn crudely_cmp_values(a: &AmlValue, b: &AmlValue) -> bool {
             AmlValue::ThermalZone => true,
             _ => false,
         },
+        AmlValue::Reference(object) => match b {
+            AmlValue::Reference(b_object) => crudely_cmp_values(object, b_object),
+            _ => false,
+        },
+        AmlValue::NamedReference(object) => match b {
+            AmlValue::NamedReference(b_object) => object == b_object,
+            _ => false,
+        },
+        AmlValue::PackageElement { package, index } => match b {
+            AmlValue::PackageElement { package: b_package, index: b_index } => {
+                package == b_package && index == b_index
+            }
+            _ => false,
+        },
+        AmlValue::External { object_type, arg_count } => match b {
+            AmlValue::External { object_type: b_object_type, arg_count: b_arg_count } => {
+                object_type == b_object_type && arg_count == b_arg_count
+            }
+            _ => false,
+        },
     }
 }
@@ -1,11 +1,13 @@
 use crate::{misc::ArgNum, AmlContext, AmlError, AmlHandle, AmlName};
 use alloc::{
+    boxed::Box,
+    format,
     string::{String, ToString},
     sync::Arc,
     vec::Vec,
 };
 use bit_field::BitField;
-use core::{cmp, fmt, fmt::Debug};
+use core::{cmp, fmt, fmt::Debug, ops::Range};
 use spinning_top::Spinlock;
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
@@ -23,6 +25,15 @@ pub enum RegionSpace {
     OemDefined(u8),
 }
 
+impl RegionSpace {
+    /// Whether fields in regions of this space are accessed by performing transactions with a device on a serial
+    /// bus, rather than by reading and writing registers. Fields in these regions are read and written as buffers,
+    /// framed as described by the spec for the region space.
+    pub fn is_serial_bus(&self) -> bool {
+        matches!(self, RegionSpace::SMBus | RegionSpace::GenericSerialBus | RegionSpace::IPMI)
+    }
+}
+
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum FieldAccessType {
     Any,
@@ -49,6 +60,14 @@ impl FieldFlags {
         FieldFlags(value)
     }
 
+    /// Get a copy of these flags, but with the access type replaced by the one encoded in the bottom four bits of
+    /// `access_type`.
+    pub fn with_access_type(self, access_type: u8) -> FieldFlags {
+        let mut value = self.0;
+        value.set_bits(0..4, access_type.get_bits(0..4));
+        FieldFlags(value)
+    }
+
     pub fn access_type(&self) -> Result<FieldAccessType, AmlError> {
         match self.0.get_bits(0..4) {
             0 => Ok(FieldAccessType::Any),
@@ -75,6 +94,123 @@ impl FieldFlags {
     }
 }
 
+/// Describes the protocol used to access a field in a `SMBus`, `GenericSerialBus`, or `IPMI` region. The lengths of
+/// the `Bytes`, `RawBytes`, and `RawProcessBytes` variants are in bytes.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FieldAccessAttrib {
+    Quick,
+    SendReceive,
+    Byte,
+    Word,
+    Block,
+    ProcessCall,
+    BlockProcessCall,
+    Bytes(u8),
+    RawBytes(u8),
+    RawProcessBytes(u8),
+}
+
+impl FieldAccessAttrib {
+    /// Decodes the `AccessType` and `AccessAttrib` of an `AccessField`. Returns `None` if the field doesn't specify
+    /// an access attribute.
+    pub fn from_access_field(access_type: u8, access_attrib: u8) -> Result<Option<FieldAccessAttrib>, AmlError> {
+        /*
+         * The top two bits of `AccessType` select whether `AccessAttrib` encodes a normal attribute, or is the
+         * length of a multi-byte attribute.
+         */
+        match access_type.get_bits(6..8) {
+            0 => match access_attrib {
+                0x00 => Ok(None),
+                0x02 => Ok(Some(FieldAccessAttrib::Quick)),
+                0x04 => Ok(Some(FieldAccessAttrib::SendReceive)),
+                0x06 => Ok(Some(FieldAccessAttrib::Byte)),
+                0x08 => Ok(Some(FieldAccessAttrib::Word)),
+                0x0a => Ok(Some(FieldAccessAttrib::Block)),
+                0x0c => Ok(Some(FieldAccessAttrib::ProcessCall)),
+                0x0d => Ok(Some(FieldAccessAttrib::BlockProcessCall)),
+                _ => Err(AmlError::InvalidFieldFlags),
+            },
+            1 => Ok(Some(FieldAccessAttrib::Bytes(access_attrib))),
+            2 => Ok(Some(FieldAccessAttrib::RawBytes(access_attrib))),
+            3 => Ok(Some(FieldAccessAttrib::RawProcessBytes(access_attrib))),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Decodes the `ExtendedAccessAttrib` and `AccessLength` of an `ExtendedAccessField`.
+    pub fn from_extended_access_field(
+        extended_access_attrib: u8,
+        access_length: u8,
+    ) -> Result<FieldAccessAttrib, AmlError> {
+        match extended_access_attrib {
+            0x0b => Ok(FieldAccessAttrib::Bytes(access_length)),
+            0x0e => Ok(FieldAccessAttrib::RawBytes(access_length)),
+            0x0f => Ok(FieldAccessAttrib::RawProcessBytes(access_length)),
+            _ => Err(AmlError::InvalidFieldFlags),
+        }
+    }
+
+    /// The number of bytes of data transferred by a `GenericSerialBus` transaction that uses this protocol.
+    pub fn gsb_data_length(&self) -> usize {
+        match self {
+            FieldAccessAttrib::Quick => 0,
+            FieldAccessAttrib::SendReceive | FieldAccessAttrib::Byte => 1,
+            FieldAccessAttrib::Word | FieldAccessAttrib::ProcessCall => 2,
+            FieldAccessAttrib::Block | FieldAccessAttrib::BlockProcessCall => 255,
+            FieldAccessAttrib::Bytes(length)
+            | FieldAccessAttrib::RawBytes(length)
+            | FieldAccessAttrib::RawProcessBytes(length) => *length as usize,
+        }
+    }
+}
+
+/// Describes a transaction on a `GenericSerialBus` region: the protocol used, which comes from the access attribute
+/// of the field, and whether the transaction writes to the device or reads from it.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct GsbAttribute {
+    pub protocol: FieldAccessAttrib,
+    pub write: bool,
+}
+
+/// The SMBus protocols that can be used by a transaction on a `SMBus` region.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SmbusProtocol {
+    QuickRead,
+    QuickWrite,
+    ReceiveByte,
+    SendByte,
+    ReadByte,
+    WriteByte,
+    ReadWord,
+    WriteWord,
+    ReadBlock,
+    WriteBlock,
+    ProcessCall,
+    BlockProcessCall,
+}
+
+impl SmbusProtocol {
+    /// Decodes the protocol of a transaction from the access attribute of the field it accesses, and whether it
+    /// writes to the device. Returns `None` if the attribute isn't an SMBus protocol.
+    pub fn from_access_attrib(access_attrib: FieldAccessAttrib, write: bool) -> Option<SmbusProtocol> {
+        match (access_attrib, write) {
+            (FieldAccessAttrib::Quick, false) => Some(SmbusProtocol::QuickRead),
+            (FieldAccessAttrib::Quick, true) => Some(SmbusProtocol::QuickWrite),
+            (FieldAccessAttrib::SendReceive, false) => Some(SmbusProtocol::ReceiveByte),
+            (FieldAccessAttrib::SendReceive, true) => Some(SmbusProtocol::SendByte),
+            (FieldAccessAttrib::Byte, false) => Some(SmbusProtocol::ReadByte),
+            (FieldAccessAttrib::Byte, true) => Some(SmbusProtocol::WriteByte),
+            (FieldAccessAttrib::Word, false) => Some(SmbusProtocol::ReadWord),
+            (FieldAccessAttrib::Word, true) => Some(SmbusProtocol::WriteWord),
+            (FieldAccessAttrib::Block, false) => Some(SmbusProtocol::ReadBlock),
+            (FieldAccessAttrib::Block, true) => Some(SmbusProtocol::WriteBlock),
+            (FieldAccessAttrib::ProcessCall, _) => Some(SmbusProtocol::ProcessCall),
+            (FieldAccessAttrib::BlockProcessCall, _) => Some(SmbusProtocol::BlockProcessCall),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub struct MethodFlags(u8);
 
@@ -157,6 +293,32 @@ pub enum AmlType {
     ThermalZone,
 }
 
+impl AmlType {
+    /// Get the code used to identify this type by the `ObjectType` operator (§19.6.97).
+    pub fn object_type_code(&self) -> u64 {
+        match self {
+            // References are followed before their type is taken, so they don't have a code of their own
+            AmlType::Uninitialized | AmlType::ObjReference => 0,
+            AmlType::Integer => 1,
+            AmlType::String => 2,
+            AmlType::Buffer | AmlType::RawDataBuffer => 3,
+            AmlType::Package => 4,
+            AmlType::FieldUnit => 5,
+            AmlType::Device => 6,
+            AmlType::Event => 7,
+            AmlType::Method => 8,
+            AmlType::Mutex => 9,
+            AmlType::OpRegion => 10,
+            AmlType::PowerResource => 11,
+            AmlType::Processor => 12,
+            AmlType::ThermalZone => 13,
+            AmlType::BufferField => 14,
+            AmlType::DdbHandle => 15,
+            AmlType::DebugObject => 16,
+        }
+    }
+}
+
 #[derive(Clone)]
 pub enum MethodCode {
     Aml(Vec<u8>),
@@ -174,6 +336,9 @@ impl fmt::Debug for MethodCode {
 
 #[derive(Clone, Debug)]
 pub enum AmlValue {
+    /// An object that has not been given a value. For example, the elements of a package that aren't initialized by
+    /// its `PackageElementList` are uninitialized.
+    Uninitialized,
     Boolean(bool),
     Integer(u64),
     String(String),
@@ -186,12 +351,27 @@ pub enum AmlValue {
         length: u64,
         parent_device: Option<AmlName>,
     },
-    /// Describes a field unit within an operation region.
+    /// Describes a field unit within an operation region. `access_attrib` and `connection` are only used by fields
+    /// in some types of region (e.g. `GeneralPurposeIo` and `GenericSerialBus`), and are set by the access and
+    /// connect fields that most recently preceded the field in its field list. `connection` is a buffer containing
+    /// the resource descriptor of the connection.
     Field {
         region: AmlHandle,
         flags: FieldFlags,
         offset: u64,
         length: u64,
+        access_attrib: Option<FieldAccessAttrib>,
+        connection: Option<Arc<Spinlock<Vec<u8>>>>,
+    },
+    /// Describes a field unit within a bank of registers that are accessed indirectly, by writing the register's
+    /// offset to the `index` field and then accessing the `data` field. Like for `Field`, `offset` and `length` are
+    /// in bits.
+    IndexField {
+        index: AmlHandle,
+        data: AmlHandle,
+        flags: FieldFlags,
+        offset: u64,
+        length: u64,
     },
     Device,
     Method {
@@ -214,6 +394,7 @@ pub enum AmlValue {
     Mutex {
         sync_level: u8,
     },
+    Event,
     // TODO: I think this will need to be `Arc`ed as well, as `Index` can be used on both Buffers and Packages
     Package(Vec<AmlValue>),
     PowerResource {
@@ -221,6 +402,26 @@ pub enum AmlValue {
         resource_order: u16,
     },
     ThermalZone,
+    /// A reference to another object, produced by `DefRefOf` and `DefCondRefOf` on objects that aren't named, and
+    /// `DefIndex` on a `String` or a `Package` that isn't a named object, and resolved by `DefDerefOf`. Objects
+    /// aren't generally shared, so this holds a copy of the object it refers to.
+    Reference(Box<AmlValue>),
+    /// A reference to a named object, produced by `DefRefOf` and `DefCondRefOf`. Unlike `Reference`, this refers to
+    /// the object in the namespace, so stores through it change the object.
+    NamedReference(AmlHandle),
+    /// A reference to an element of a named `Package`, produced by `DefIndex`. Unlike `Reference`, this refers to
+    /// the element in place, so stores through it change the package.
+    PackageElement {
+        package: AmlHandle,
+        index: usize,
+    },
+    /// A placeholder for an object that is declared by `DefExternal` to be defined by another table. It's replaced
+    /// by the real object when that table is loaded. `object_type` is encoded as for the `ObjectType` operator, and
+    /// `arg_count` is the number of arguments taken by the object if it's a method.
+    External {
+        object_type: u8,
+        arg_count: u8,
+    },
 }
 
 impl AmlValue {
@@ -236,6 +437,17 @@ impl AmlValue {
         AmlValue::Integer(u64::max_value())
     }
 
+    pub fn string<S>(string: S) -> AmlValue
+    where
+        S: Into<String>,
+    {
+        AmlValue::String(string.into())
+    }
+
+    pub fn buffer(bytes: Vec<u8>) -> AmlValue {
+        AmlValue::Buffer(Arc::new(Spinlock::new(bytes)))
+    }
+
     pub fn native_method<F>(arg_count: u8, serialize: bool, sync_level: u8, f: F) -> AmlValue
     where
         F: (Fn(&mut AmlContext) -> Result<AmlValue, AmlError>) + 'static + Send + Sync,
@@ -246,20 +458,51 @@ impl AmlValue {
 
     pub fn type_of(&self) -> AmlType {
         match self {
+            AmlValue::Uninitialized => AmlType::Uninitialized,
             AmlValue::Boolean(_) => AmlType::Integer,
             AmlValue::Integer(_) => AmlType::Integer,
             AmlValue::String(_) => AmlType::String,
             AmlValue::OpRegion { .. } => AmlType::OpRegion,
             AmlValue::Field { .. } => AmlType::FieldUnit,
+            AmlValue::IndexField { .. } => AmlType::FieldUnit,
             AmlValue::Device => AmlType::Device,
             AmlValue::Method { .. } => AmlType::Method,
             AmlValue::Buffer(_) => AmlType::Buffer,
             AmlValue::BufferField { .. } => AmlType::BufferField,
             AmlValue::Processor { .. } => AmlType::Processor,
             AmlValue::Mutex { .. } => AmlType::Mutex,
+            AmlValue::Event => AmlType::Event,
             AmlValue::Package(_) => AmlType::Package,
             AmlValue::PowerResource { .. } => AmlType::PowerResource,
             AmlValue::ThermalZone => AmlType::ThermalZone,
+            AmlValue::Reference(_) | AmlValue::NamedReference(_) | AmlValue::PackageElement { .. } => {
+                AmlType::ObjReference
+            }
+            AmlValue::External { .. } => AmlType::Uninitialized,
+        }
+    }
+
+    /// Get the code that the `ObjectType` operator returns for this object. References are followed to the object
+    /// they refer to, and external objects have the type they were declared with. References to named objects and
+    /// package elements can only be followed with the namespace, so should be resolved with
+    /// `AmlContext::dereference` first.
+    pub fn object_type_code(&self) -> u64 {
+        match self {
+            AmlValue::Reference(object) => object.object_type_code(),
+            AmlValue::External { object_type, .. } => *object_type as u64,
+            _ => self.type_of().object_type_code(),
+        }
+    }
+
+    /// Make a copy of this object that doesn't share any data with the original. Copies made with `clone` share
+    /// the data of `Buffer`s, including those inside `Package`s, so writes through one copy are visible in the
+    /// other.
+    pub fn deep_clone(&self) -> AmlValue {
+        match self {
+            AmlValue::Buffer(bytes) => AmlValue::buffer(bytes.lock().clone()),
+            AmlValue::Package(elements) => AmlValue::Package(elements.iter().map(AmlValue::deep_clone).collect()),
+            AmlValue::Reference(object) => AmlValue::Reference(Box::new(object.deep_clone())),
+            _ => self.clone(),
         }
     }
 
@@ -286,12 +529,24 @@ impl AmlValue {
                 let bytes = bytes.lock();
                 let bytes = if bytes.len() > 8 { &bytes[0..8] } else { &bytes[..] };
 
-                Ok(bytes.iter().rev().fold(0: u64, |mut i, &popped| {
+                Ok(bytes.iter().rev().fold(0u64, |mut i, &popped| {
                     i <<= 8;
                     i += popped as u64;
                     i
                 }))
             }
+            /*
+             * "The string is treated as a hexadecimal constant. [...] Conversion stops when the first
+             * non-hexadecimal character is reached [...] or when 16 characters have been converted." - §19.3.5.7
+             *
+             * Only 8 characters are converted if integers are 32 bits wide. Unlike explicit conversions with
+             * `ToInteger`, a `0x` prefix is not allowed, and so stops the conversion at the `x`.
+             */
+            AmlValue::String(ref string) => Ok(string
+                .chars()
+                .take_while(|c| c.is_ascii_hexdigit())
+                .take(context.integer_width() as usize / 4)
+                .fold(0, |value, c| (value << 4) | c.to_digit(16).unwrap() as u64)),
             /*
              * Read from a field or buffer field. These can return either a `Buffer` or an `Integer`, so we make sure to call
              * `as_integer` on the result.
@@ -306,7 +561,19 @@ impl AmlValue {
     pub fn as_buffer(&self, context: &AmlContext) -> Result<Arc<Spinlock<Vec<u8>>>, AmlError> {
         match self {
             AmlValue::Buffer(ref bytes) => Ok(bytes.clone()),
-            // TODO: implement conversion of String and Integer to Buffer
+            /*
+             * Integers are converted to 8 bytes (or 4, if integers are 32 bits wide), least significant byte first,
+             * and strings are copied byte-for-byte, including the null terminator (this matches what ACPICA does).
+             */
+            AmlValue::Integer(_) | AmlValue::Boolean(_) => {
+                let bytes = self.as_integer(context)?.to_le_bytes();
+                Ok(Arc::new(Spinlock::new(bytes[0..(context.integer_width() as usize / 8)].to_vec())))
+            }
+            AmlValue::String(ref string) => {
+                let mut bytes = string.as_bytes().to_vec();
+                bytes.push(0x00);
+                Ok(Arc::new(Spinlock::new(bytes)))
+            }
             AmlValue::Field { .. } => self.read_field(context)?.as_buffer(context),
             AmlValue::BufferField { .. } => self.read_buffer_field(context)?.as_buffer(context),
             _ => Err(AmlError::IncompatibleValueConversion { current: self.type_of(), target: AmlType::Buffer }),
@@ -316,12 +583,35 @@ impl AmlValue {
     pub fn as_string(&self, context: &AmlContext) -> Result<String, AmlError> {
         match self {
             AmlValue::String(ref string) => Ok(string.clone()),
-            // TODO: implement conversion of Buffer to String
+            /*
+             * Integers are converted to 16 hexadecimal digits (or 8, if integers are 32 bits wide). Buffers are
+             * converted to the hexadecimal values of each byte, separated by spaces (this matches what ACPICA does).
+             */
+            AmlValue::Integer(_) | AmlValue::Boolean(_) => Ok(format!(
+                "{:0width$X}",
+                context.truncate_integer(self.as_integer(context)?),
+                width = context.integer_width() as usize / 4
+            )),
+            AmlValue::Buffer(ref bytes) => {
+                Ok(bytes.lock().iter().map(|byte| format!("{:02X}", byte)).collect::<Vec<String>>().join(" "))
+            }
             AmlValue::Field { .. } => self.read_field(context)?.as_string(context),
+            AmlValue::BufferField { .. } => self.read_buffer_field(context)?.as_string(context),
             _ => Err(AmlError::IncompatibleValueConversion { current: self.type_of(), target: AmlType::String }),
         }
     }
 
+    /// Get the string form of a device ID, such as a `_HID` or `_CID`. IDs are either strings, which are returned
+    /// as they are, or compressed EISA IDs (as produced by the ASL `EisaId` macro), which are decoded - e.g.
+    /// `0x030ad041` is decoded to `PNP0A03`. Returns `None` if the value isn't a valid ID.
+    pub fn as_eisa_id(&self) -> Option<String> {
+        match self {
+            AmlValue::String(ref string) => Some(string.clone()),
+            AmlValue::Integer(id) if *id <= u32::MAX as u64 => Some(decode_eisa_id(*id as u32)),
+            _ => None,
+        }
+    }
+
     /// Converts an `AmlValue` to the representation that should be used when concatenating it with other values,
     /// primarily by the `DefConcat` opcode. This will always produce a `AmlValue::Integer`, `AmlValue::String`, or
     /// `AmlValue::Buffer`, with other types being converted to strings containing the name of their type.
@@ -397,6 +687,7 @@ impl AmlValue {
         match desired_type {
             AmlType::Integer => self.as_integer(context).map(|value| AmlValue::Integer(value)),
             AmlType::Buffer => self.as_buffer(context).map(|value| AmlValue::Buffer(value)),
+            AmlType::String => self.as_string(context).map(|value| AmlValue::String(value)),
             AmlType::FieldUnit => panic!(
                 "Can't implicitly convert to FieldUnit. This must be special-cased by the caller for now :("
             ),
@@ -405,98 +696,199 @@ impl AmlValue {
     }
 
     /// Reads from a field of an opregion, returning either a `AmlValue::Integer` or an `AmlValue::Buffer`,
-    /// depending on the size of the field.
+    /// depending on the size of the field. The field is read through each of the region's access units that it
+    /// overlaps, so fields that are larger than the access size, or that aren't aligned to it, may need multiple
+    /// reads.
     pub fn read_field(&self, context: &AmlContext) -> Result<AmlValue, AmlError> {
-        if let AmlValue::Field { region, flags, offset, length } = self {
-            let maximum_access_size = {
-                if let AmlValue::OpRegion { region, .. } = context.namespace.get(*region)? {
-                    match region {
-                        RegionSpace::SystemMemory => 64,
-                        RegionSpace::SystemIo | RegionSpace::PciConfig => 32,
-                        _ => unimplemented!(),
-                    }
-                } else {
-                    return Err(AmlError::FieldRegionIsNotOpRegion);
+        use bitvec::{order::Lsb0, vec::BitVec, view::BitView};
+
+        if let AmlValue::Field { region, flags, offset, length, connection, .. } = self {
+            match field_region_space(*region, context)? {
+                space if space.is_serial_bus() => return self.serial_bus_transaction(None, context),
+                RegionSpace::GeneralPurposeIo => {
+                    let connection = connection.as_ref().map(|connection| connection.lock().clone());
+                    return context.read_gpio(connection.as_deref(), *offset, *length).map(AmlValue::Integer);
                 }
-            };
-            let minimum_access_size = match flags.access_type()? {
-                FieldAccessType::Any => 8,
-                FieldAccessType::Byte => 8,
-                FieldAccessType::Word => 16,
-                FieldAccessType::DWord => 32,
-                FieldAccessType::QWord => 64,
-                FieldAccessType::Buffer => 8, // TODO
-            };
+                _ => (),
+            }
 
-            /*
-             * Find the access size, as either the minimum access size allowed by the region, or the field length
-             * rounded up to the next power-of-2, whichever is larger.
-             */
-            let access_size = u64::max(minimum_access_size, length.next_power_of_two());
+            let access_size = field_access_size(*region, *flags, *offset, *length, context)?;
+            let mut field_bits = BitVec::<u8, Lsb0>::repeat(false, *length as usize);
 
-            /*
-             * TODO: we need to decide properly how to read from the region itself. Complications:
-             *    - if the region has a minimum access size greater than the desired length, we need to read the
-             *      minimum and mask it (reading a byte from a WordAcc region)
-             *    - if the desired length is larger than we can read, we need to do multiple reads
-             */
-            Ok(AmlValue::Integer(
-                context.read_region(*region, *offset, access_size)?.get_bits(0..(*length as usize)),
-            ))
+            if flags.lock_rule() {
+                acquire_global_lock(context);
+            }
+            let result = field_access_units(*offset, *length, access_size).try_for_each(|unit| {
+                let unit_value = context.read_region(*region, unit.byte_offset, access_size)?;
+                field_bits[unit.field_bits].clone_from_bitslice(&unit_value.view_bits::<Lsb0>()[unit.unit_bits]);
+                Ok(())
+            });
+            if flags.lock_rule() {
+                release_global_lock(context);
+            }
+
+            result.map(|()| field_bits_to_value(field_bits))
         } else {
             Err(AmlError::IncompatibleValueConversion { current: self.type_of(), target: AmlType::FieldUnit })
         }
     }
 
+    /// Writes to a field of an opregion. Like reads, this may need multiple writes, to each of the access units the
+    /// field overlaps. Integers written to the field are zero-extended or truncated to the field's length, and
+    /// buffers and strings are copied into it bit-for-bit. Writes to fields in serial bus regions perform a write
+    /// transaction, the result of which is discarded.
     pub fn write_field(&mut self, value: AmlValue, context: &mut AmlContext) -> Result<(), AmlError> {
-        /*
-         * If the field's update rule is `Preserve`, we need to read the initial value of the field, so we can
-         * overwrite the correct bits. We destructure the field to do the actual write, so we read from it if
-         * needed here, otherwise the borrow-checker doesn't understand.
-         */
-        let field_update_rule = if let AmlValue::Field { region, flags, offset, length } = self {
-            flags.field_update_rule()?
-        } else {
-            return Err(AmlError::IncompatibleValueConversion {
-                current: self.type_of(),
-                target: AmlType::FieldUnit,
+        use bitvec::{order::Lsb0, view::BitView};
+
+        if self.is_serial_bus_field(context)? {
+            return self.serial_bus_transaction(Some(value), context).map(|_| ());
+        }
+
+        if let AmlValue::Field { region, flags, offset, length, connection, .. } = self {
+            if field_region_space(*region, context)? == RegionSpace::GeneralPurposeIo {
+                let connection = connection.as_ref().map(|connection| connection.lock().clone());
+                let value = value.as_integer(context)?;
+                return context.write_gpio(connection.as_deref(), *offset, *length, value);
+            }
+
+            let access_size = field_access_size(*region, *flags, *offset, *length, context)?;
+            let field_update_rule = flags.field_update_rule()?;
+            let value_bits = field_value_to_bits(value, *length as usize, context)?;
+
+            if flags.lock_rule() {
+                acquire_global_lock(context);
+            }
+            let result = field_access_units(*offset, *length, access_size).try_for_each(|unit| {
+                /*
+                 * The bits of the access unit that aren't part of the field are filled according to the field's
+                 * update rule. `Preserve` needs a read-modify-write to keep their current values, unless the field
+                 * covers the entire unit.
+                 */
+                let mut unit_value = match field_update_rule {
+                    _ if unit.unit_bits.len() as u64 == access_size => 0x0,
+                    FieldUpdateRule::Preserve => context.read_region(*region, unit.byte_offset, access_size)?,
+                    FieldUpdateRule::WriteAsOnes => 0xffffffff_ffffffff,
+                    FieldUpdateRule::WriteAsZeros => 0x0,
+                };
+                unit_value.view_bits_mut::<Lsb0>()[unit.unit_bits]
+                    .clone_from_bitslice(&value_bits[unit.field_bits]);
+                context.write_region(*region, unit.byte_offset, access_size, unit_value)
             });
-        };
-        let mut field_value = match field_update_rule {
-            FieldUpdateRule::Preserve => self.read_field(context)?.as_integer(context)?,
-            FieldUpdateRule::WriteAsOnes => 0xffffffff_ffffffff,
-            FieldUpdateRule::WriteAsZeros => 0x0,
-        };
+            if flags.lock_rule() {
+                release_global_lock(context);
+            }
 
-        if let AmlValue::Field { region, flags, offset, length } = self {
-            let maximum_access_size = {
-                if let AmlValue::OpRegion { region, .. } = context.namespace.get(*region)? {
-                    match region {
-                        RegionSpace::SystemMemory => 64,
-                        RegionSpace::SystemIo | RegionSpace::PciConfig => 32,
-                        _ => unimplemented!(),
-                    }
-                } else {
-                    return Err(AmlError::FieldRegionIsNotOpRegion);
-                }
-            };
-            let minimum_access_size = match flags.access_type()? {
-                FieldAccessType::Any => 8,
-                FieldAccessType::Byte => 8,
-                FieldAccessType::Word => 16,
-                FieldAccessType::DWord => 32,
-                FieldAccessType::QWord => 64,
-                FieldAccessType::Buffer => 8, // TODO
+            result
+        } else {
+            Err(AmlError::IncompatibleValueConversion { current: self.type_of(), target: AmlType::FieldUnit })
+        }
+    }
+
+    /// Whether this is a `Field` in a region that is accessed with serial bus transactions (see
+    /// [`RegionSpace::is_serial_bus`]).
+    pub(crate) fn is_serial_bus_field(&self, context: &AmlContext) -> Result<bool, AmlError> {
+        match self {
+            AmlValue::Field { region, .. } => Ok(field_region_space(*region, context)?.is_serial_bus()),
+            _ => Ok(false),
+        }
+    }
+
+    /// Perform a transaction with the device behind a field in a serial bus region. `value` is written to the
+    /// device, or the transaction reads from the device if it's `None`. Returns the buffer produced by the
+    /// transaction, which contains its status and any data read.
+    pub(crate) fn serial_bus_transaction(
+        &self,
+        value: Option<AmlValue>,
+        context: &AmlContext,
+    ) -> Result<AmlValue, AmlError> {
+        if let AmlValue::Field { region, offset, access_attrib, connection, .. } = self {
+            let write_data = match value {
+                Some(value) => Some(value.as_buffer(context)?.lock().clone()),
+                None => None,
             };
+            let connection = connection.as_ref().map(|connection| connection.lock().clone());
+
+            context
+                .serial_bus_transaction(
+                    *region,
+                    offset / 8,
+                    *access_attrib,
+                    connection.as_deref(),
+                    write_data.as_deref(),
+                )
+                .map(AmlValue::buffer)
+        } else {
+            Err(AmlError::IncompatibleValueConversion { current: self.type_of(), target: AmlType::FieldUnit })
+        }
+    }
 
-            /*
-             * Find the access size, as either the minimum access size allowed by the region, or the field length
-             * rounded up to the next power-of-2, whichever is larger.
-             */
-            let access_size = u64::max(minimum_access_size, length.next_power_of_two());
+    /// Reads from an `IndexField`. Each access unit of the field is read by writing its offset, in bytes, to the
+    /// index field, and then reading the data field. This writes to the index field, and so, unlike `read_field`,
+    /// needs a mutable reference to the context.
+    pub fn read_index_field(&self, context: &mut AmlContext) -> Result<AmlValue, AmlError> {
+        use bitvec::{order::Lsb0, vec::BitVec, view::BitView};
 
-            field_value.set_bits(0..(*length as usize), value.as_integer(context)?);
-            context.write_region(*region, *offset, access_size, field_value)
+        if let AmlValue::IndexField { index, data, flags, offset, length } = self {
+            let mut index = context.namespace.get(*index)?.clone();
+            let data = context.namespace.get(*data)?.clone();
+            let access_size = index_field_access_size(&data, *flags, *offset, *length)?;
+            let mut field_bits = BitVec::<u8, Lsb0>::repeat(false, *length as usize);
+
+            if flags.lock_rule() {
+                acquire_global_lock(context);
+            }
+            let result = field_access_units(*offset, *length, access_size).try_for_each(|unit| {
+                index.write_field(AmlValue::Integer(unit.byte_offset), context)?;
+                let unit_value = data.read_field(context)?.as_integer(context)?;
+                field_bits[unit.field_bits].clone_from_bitslice(&unit_value.view_bits::<Lsb0>()[unit.unit_bits]);
+                Ok(())
+            });
+            if flags.lock_rule() {
+                release_global_lock(context);
+            }
+
+            result.map(|()| field_bits_to_value(field_bits))
+        } else {
+            Err(AmlError::IncompatibleValueConversion { current: self.type_of(), target: AmlType::FieldUnit })
+        }
+    }
+
+    /// Writes to an `IndexField`, by writing the offset of each access unit of the field to the index field, and
+    /// then writing to the data field.
+    pub fn write_index_field(&self, value: AmlValue, context: &mut AmlContext) -> Result<(), AmlError> {
+        use bitvec::{order::Lsb0, view::BitView};
+
+        if let AmlValue::IndexField { index, data, flags, offset, length } = self {
+            let mut index = context.namespace.get(*index)?.clone();
+            let mut data = context.namespace.get(*data)?.clone();
+            let access_size = index_field_access_size(&data, *flags, *offset, *length)?;
+            let field_update_rule = flags.field_update_rule()?;
+            let value_bits = field_value_to_bits(value, *length as usize, context)?;
+
+            if flags.lock_rule() {
+                acquire_global_lock(context);
+            }
+            let result = field_access_units(*offset, *length, access_size).try_for_each(|unit| {
+                let mut unit_value = match field_update_rule {
+                    _ if unit.unit_bits.len() as u64 == access_size => 0x0,
+                    FieldUpdateRule::Preserve => {
+                        index.write_field(AmlValue::Integer(unit.byte_offset), context)?;
+                        data.read_field(context)?.as_integer(context)?
+                    }
+                    FieldUpdateRule::WriteAsOnes => 0xffffffff_ffffffff,
+                    FieldUpdateRule::WriteAsZeros => 0x0,
+                };
+                unit_value.view_bits_mut::<Lsb0>()[unit.unit_bits]
+                    .clone_from_bitslice(&value_bits[unit.field_bits]);
+
+                index.write_field(AmlValue::Integer(unit.byte_offset), context)?;
+                data.write_field(AmlValue::Integer(unit_value), context)
+            });
+            if flags.lock_rule() {
+                release_global_lock(context);
+            }
+
+            result
         } else {
             Err(AmlError::IncompatibleValueConversion { current: self.type_of(), target: AmlType::FieldUnit })
         }
@@ -506,20 +898,15 @@ impl AmlValue {
         use bitvec::view::BitView;
 
         if let AmlValue::BufferField { buffer_data, offset, length } = self {
-            let offset = *offset as usize;
-            let length = *length as usize;
             let inner_data = buffer_data.lock();
-
-            if (offset + length) > (inner_data.len() * 8) {
-                return Err(AmlError::BufferFieldIndexesOutOfBounds);
-            }
+            let (offset, length) = buffer_field_bounds(*offset, *length, inner_data.len())?;
 
             let bitslice = inner_data.view_bits::<bitvec::order::Lsb0>();
             let bits = &bitslice[offset..(offset + length)];
             if length > 64 {
                 let mut bitvec = bits.to_bitvec();
                 bitvec.set_uninitialized(false);
-                Ok(AmlValue::Buffer(Arc::new(spinning_top::Spinlock::new(bitvec.into_vec()))))
+                Ok(AmlValue::buffer(bitvec.into_vec()))
             } else {
                 let mut value = 0u64;
                 value.view_bits_mut::<bitvec::order::Lsb0>()[0..length].clone_from_bitslice(bits);
@@ -534,10 +921,8 @@ impl AmlValue {
         use bitvec::view::BitView;
 
         if let AmlValue::BufferField { buffer_data, offset, length } = self {
-            let offset = *offset as usize;
-            let length = *length as usize;
-            // TODO: check these against the size of the buffer to be written into
             let mut inner_data = buffer_data.lock();
+            let (offset, length) = buffer_field_bounds(*offset, *length, inner_data.len())?;
             let bitslice = inner_data.view_bits_mut::<bitvec::order::Lsb0>();
 
             match value {
@@ -555,7 +940,9 @@ impl AmlValue {
                     Ok(())
                 }
                 AmlValue::Boolean(value) => {
-                    bitslice.set(offset, value);
+                    if length > 0 {
+                        bitslice.set(offset, value);
+                    }
                     Ok(())
                 }
                 AmlValue::Buffer(value) => {
@@ -602,6 +989,229 @@ impl AmlValue {
     }
 }
 
+/// A compact, human-readable rendering of a value, for logging and debugging. Integers are shown in hex, strings
+/// are quoted, buffers show their length and first few bytes, and packages show their elements. Use the `Debug`
+/// implementation instead to see every detail of a value.
+impl fmt::Display for AmlValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const BUFFER_BYTES_SHOWN: usize = 8;
+
+        match self {
+            AmlValue::Uninitialized => write!(f, "Uninitialized"),
+            AmlValue::Boolean(value) => write!(f, "{}", value),
+            AmlValue::Integer(value) => write!(f, "{:#x}", value),
+            AmlValue::String(string) => write!(f, "{:?}", string),
+            AmlValue::OpRegion { region, offset, length, .. } => {
+                write!(f, "OpRegion({:?}, offset {:#x}, length {:#x})", region, offset, length)
+            }
+            AmlValue::Field { offset, length, .. } | AmlValue::IndexField { offset, length, .. } => {
+                write!(f, "Field(bit offset {:#x}, {} bits)", offset, length)
+            }
+            AmlValue::Device => write!(f, "Device"),
+            AmlValue::Method { flags, code } => match code {
+                MethodCode::Aml(_) => write!(f, "Method({} args)", flags.arg_count()),
+                MethodCode::Native(_) => write!(f, "Method({} args, native)", flags.arg_count()),
+            },
+            AmlValue::Buffer(bytes) => {
+                let bytes = bytes.lock();
+                write!(f, "Buffer({} bytes) [", bytes.len())?;
+                for (i, byte) in bytes.iter().take(BUFFER_BYTES_SHOWN).enumerate() {
+                    if i != 0 {
+                        write!(f, " ")?;
+                    }
+                    write!(f, "{:02x}", byte)?;
+                }
+                if bytes.len() > BUFFER_BYTES_SHOWN {
+                    write!(f, " ...")?;
+                }
+                write!(f, "]")
+            }
+            AmlValue::BufferField { offset, length, .. } => {
+                write!(f, "BufferField(bit offset {:#x}, {} bits)", offset, length)
+            }
+            AmlValue::Processor { id, .. } => write!(f, "Processor({})", id),
+            AmlValue::Mutex { sync_level } => write!(f, "Mutex(sync level {})", sync_level),
+            AmlValue::Event => write!(f, "Event"),
+            AmlValue::Package(elements) => {
+                write!(f, "Package [")?;
+                for (i, element) in elements.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", element)?;
+                }
+                write!(f, "]")
+            }
+            AmlValue::PowerResource { system_level, resource_order } => {
+                write!(f, "PowerResource(system level {}, resource order {})", system_level, resource_order)
+            }
+            AmlValue::ThermalZone => write!(f, "ThermalZone"),
+            AmlValue::Reference(object) => write!(f, "Reference({})", object),
+            AmlValue::NamedReference(object) => write!(f, "NamedReference({:?})", object),
+            AmlValue::PackageElement { package, index } => write!(f, "PackageElement({:?}, {})", package, index),
+            AmlValue::External { .. } => write!(f, "External"),
+        }
+    }
+}
+
+/// Check that a buffer field at bit `offset`, `length` bits long, fits inside a buffer of `buffer_len` bytes, and
+/// convert its bounds to `usize`s to index the buffer's bits with.
+fn buffer_field_bounds(offset: u64, length: u64, buffer_len: usize) -> Result<(usize, usize), AmlError> {
+    match offset.checked_add(length) {
+        Some(end) if end <= (buffer_len as u64) * 8 => Ok((offset as usize, length as usize)),
+        _ => Err(AmlError::BufferFieldIndexesOutOfBounds),
+    }
+}
+
+/// Get the space of the operation region a field is in.
+fn field_region_space(region: AmlHandle, context: &AmlContext) -> Result<RegionSpace, AmlError> {
+    match context.namespace.get(region)? {
+        AmlValue::OpRegion { region, .. } => Ok(*region),
+        _ => Err(AmlError::FieldRegionIsNotOpRegion),
+    }
+}
+
+/// Work out the size, in bits, of the accesses used to access a field of an operation region. Fields declared with
+/// a specific access type are accessed with that size, while `AnyAcc` fields use the smallest access size that can
+/// access the whole field at once, if the region supports it. Accesses are never larger than the region supports.
+fn field_access_size(
+    region: AmlHandle,
+    flags: FieldFlags,
+    offset: u64,
+    length: u64,
+    context: &AmlContext,
+) -> Result<u64, AmlError> {
+    let maximum_access_size = {
+        if let AmlValue::OpRegion { region, .. } = context.namespace.get(region)? {
+            match region {
+                RegionSpace::SystemMemory | RegionSpace::OemDefined(_) => 64,
+                RegionSpace::SystemIo | RegionSpace::PciConfig => 32,
+                RegionSpace::EmbeddedControl | RegionSpace::SystemCmos => 8,
+                space => return Err(AmlError::UnsupportedRegionSpace(*space)),
+            }
+        } else {
+            return Err(AmlError::FieldRegionIsNotOpRegion);
+        }
+    };
+
+    access_size_for_flags(flags, offset, length, maximum_access_size)
+}
+
+/// Work out the size, in bits, of the accesses used to access an `IndexField`. These can't be larger than the data
+/// field, as each access is made by reading or writing it.
+fn index_field_access_size(data: &AmlValue, flags: FieldFlags, offset: u64, length: u64) -> Result<u64, AmlError> {
+    match data {
+        AmlValue::Field { length: data_length, .. } if *data_length >= 8 => {
+            access_size_for_flags(flags, offset, length, u64::min(data_length.next_power_of_two(), 64))
+        }
+        AmlValue::Field { .. } => Err(AmlError::FieldInvalidAccessSize),
+        _ => Err(AmlError::IncompatibleValueConversion { current: data.type_of(), target: AmlType::FieldUnit }),
+    }
+}
+
+fn access_size_for_flags(
+    flags: FieldFlags,
+    offset: u64,
+    length: u64,
+    maximum_access_size: u64,
+) -> Result<u64, AmlError> {
+    let access_size = match flags.access_type()? {
+        FieldAccessType::Byte => 8,
+        FieldAccessType::Word => 16,
+        FieldAccessType::DWord => 32,
+        FieldAccessType::QWord => 64,
+        FieldAccessType::Buffer => 8, // TODO
+        FieldAccessType::Any => {
+            let last_bit = offset + length.saturating_sub(1);
+            [8, 16, 32, 64]
+                .iter()
+                .copied()
+                .find(|size| offset / size == last_bit / size)
+                .unwrap_or(maximum_access_size)
+        }
+    };
+
+    Ok(u64::min(access_size, maximum_access_size))
+}
+
+/// The part of a field that lies in a single access unit of its region.
+struct FieldAccessUnit {
+    /// The offset of the access unit into the region, in bytes.
+    byte_offset: u64,
+    /// The bits of the access unit that are part of the field.
+    unit_bits: Range<usize>,
+    /// The bits of the field that lie in the access unit.
+    field_bits: Range<usize>,
+}
+
+/// Split a field at bit `offset`, with a length of `length` bits, into the parts that lie in each of the access units
+/// it overlaps. Access units are `access_size` bits long, and are aligned to their size.
+fn field_access_units(offset: u64, length: u64, access_size: u64) -> impl Iterator<Item = FieldAccessUnit> {
+    let end = offset + length;
+
+    ((offset / access_size)..).map(move |unit| unit * access_size).take_while(move |&start| start < end).map(
+        move |start| {
+            let overlap_start = u64::max(start, offset);
+            let overlap_end = u64::min(start + access_size, end);
+
+            FieldAccessUnit {
+                byte_offset: start / 8,
+                unit_bits: ((overlap_start - start) as usize)..((overlap_end - start) as usize),
+                field_bits: ((overlap_start - offset) as usize)..((overlap_end - offset) as usize),
+            }
+        },
+    )
+}
+
+/// Fields that fit in an integer are read as an `AmlValue::Integer`, and larger ones as an `AmlValue::Buffer`.
+fn field_bits_to_value(bits: bitvec::vec::BitVec<u8, bitvec::order::Lsb0>) -> AmlValue {
+    use bitvec::field::BitField;
+
+    if bits.len() > 64 {
+        AmlValue::buffer(bits.into_vec())
+    } else if bits.is_empty() {
+        AmlValue::Integer(0)
+    } else {
+        AmlValue::Integer(bits.load_le())
+    }
+}
+
+/// Convert a value being written to a field into exactly `length` bits, zero-extending or truncating it as needed.
+fn field_value_to_bits(
+    value: AmlValue,
+    length: usize,
+    context: &AmlContext,
+) -> Result<bitvec::vec::BitVec<u8, bitvec::order::Lsb0>, AmlError> {
+    let bytes = match value {
+        AmlValue::Buffer(ref bytes) => bytes.lock().clone(),
+        AmlValue::String(ref string) => string.as_bytes().to_vec(),
+        other => other.as_integer(context)?.to_le_bytes().to_vec(),
+    };
+
+    let mut bits = bitvec::vec::BitVec::from_vec(bytes);
+    bits.resize(length, false);
+    Ok(bits)
+}
+
+/// Acquire the Global Lock, which must be held while accessing fields with a `Lock` lock rule.
+fn acquire_global_lock(context: &AmlContext) {
+    // We wait forever, so the lock is always acquired
+    context.handler.acquire_mutex(&AmlName::from_str("\\_GL").unwrap(), 0xffff);
+}
+
+fn release_global_lock(context: &AmlContext) {
+    context.handler.release_mutex(&AmlName::from_str("\\_GL").unwrap());
+}
+
+/// Decode a compressed EISA ID, as produced by the ASL `EisaId` macro, into its string form (e.g. `0x030ad041`
+/// decodes to `PNP0A03`). The ID is made up of three 5-bit vendor characters, followed by four hex digits, and is
+/// stored big-endian inside the little-endian integer.
+pub(crate) fn decode_eisa_id(id: u32) -> String {
+    let id = id.swap_bytes();
+    let vendor_char = |shift: u32| (b'@' + ((id >> shift) & 0x1f) as u8) as char;
+    format!("{}{}{}{:04X}", vendor_char(26), vendor_char(21), vendor_char(16), id & 0xffff)
+}
+
 /// A control method can take up to 7 arguments, each of which is an `AmlValue`.
 #[derive(Clone, Default, Debug)]
 pub struct Args(pub [Option<AmlValue>; 7]);
@@ -643,6 +1253,7 @@ impl Args {
 mod tests {
     use super::*;
     use crate::test_utils::*;
+    use alloc::vec;
     use core::cmp::Ordering;
 
     #[test]
@@ -655,4 +1266,797 @@ mod tests {
 
         // TODO: test the other combinations too, as well as conversions to the correct types for the second operand
     }
+
+    #[test]
+    fn test_as_string_and_as_buffer() {
+        let context = make_test_context();
+
+        assert_eq!(AmlValue::String(String::from("PNP0A03")).as_string(&context), Ok(String::from("PNP0A03")));
+        assert_eq!(
+            AmlValue::Device.as_string(&context),
+            Err(AmlError::IncompatibleValueConversion { current: AmlType::Device, target: AmlType::String })
+        );
+
+        let buffer = AmlValue::Buffer(Arc::new(Spinlock::new(alloc::vec![0x79, 0x00])));
+        assert_eq!(*buffer.as_buffer(&context).unwrap().lock(), alloc::vec![0x79, 0x00]);
+        assert_eq!(
+            AmlValue::Device.as_buffer(&context).map(|_| ()),
+            Err(AmlError::IncompatibleValueConversion { current: AmlType::Device, target: AmlType::Buffer })
+        );
+
+        assert!(crudely_cmp_values(&AmlValue::string("FOO"), &AmlValue::String(String::from("FOO"))));
+        assert!(crudely_cmp_values(
+            &AmlValue::buffer(alloc::vec![0x01, 0x02]),
+            &AmlValue::Buffer(Arc::new(Spinlock::new(alloc::vec![0x01, 0x02])))
+        ));
+        assert_eq!(AmlValue::string("1F").as_integer(&context), Ok(0x1f));
+        assert_eq!(AmlValue::buffer(alloc::vec![0x01, 0x02]).as_integer(&context), Ok(0x0201));
+        assert_eq!(
+            AmlValue::string("FOO").as_type(AmlType::Buffer, &context).and_then(|value| value.as_string(&context)),
+            Ok(String::from("46 4F 4F 00"))
+        );
+    }
+
+    #[test]
+    fn test_object_type_codes() {
+        assert_eq!(AmlValue::Uninitialized.object_type_code(), 0);
+        assert_eq!(AmlValue::Integer(4).object_type_code(), 1);
+        assert_eq!(AmlValue::Boolean(true).object_type_code(), 1);
+        assert_eq!(AmlValue::string("FOO").object_type_code(), 2);
+        assert_eq!(AmlValue::buffer(alloc::vec![]).object_type_code(), 3);
+        assert_eq!(AmlValue::Package(alloc::vec![]).object_type_code(), 4);
+        assert_eq!(AmlValue::Device.object_type_code(), 6);
+        assert_eq!(AmlValue::Event.object_type_code(), 7);
+        assert_eq!(AmlValue::native_method(0, false, 0, |_| Ok(AmlValue::zero())).object_type_code(), 8);
+        assert_eq!(AmlValue::Mutex { sync_level: 0 }.object_type_code(), 9);
+        assert_eq!(AmlValue::ThermalZone.object_type_code(), 13);
+        assert_eq!(AmlValue::Reference(Box::new(AmlValue::string("FOO"))).object_type_code(), 2);
+        assert_eq!(AmlValue::External { object_type: 8, arg_count: 1 }.object_type_code(), 8);
+        assert_eq!(AmlType::DebugObject.object_type_code(), 16);
+    }
+
+    #[test]
+    fn test_implicit_conversions() {
+        let context = make_test_context();
+        let buffer = |bytes: &[u8]| AmlValue::Buffer(Arc::new(Spinlock::new(bytes.to_vec())));
+
+        // Buffer -> Integer takes the first 8 bytes, least significant first
+        assert_eq!(buffer(&[0x01, 0x02]).as_integer(&context), Ok(0x0201));
+        assert_eq!(buffer(&[1, 2, 3, 4, 5, 6, 7, 8, 9]).as_integer(&context), Ok(0x0807060504030201));
+        // String -> Integer parses hexadecimal digits until the first non-hex character, up to 16 digits
+        assert_eq!(AmlValue::String(String::from("1aB")).as_integer(&context), Ok(0x1ab));
+        assert_eq!(AmlValue::String(String::from("12g4")).as_integer(&context), Ok(0x12));
+        assert_eq!(AmlValue::String(String::from("0x12")).as_integer(&context), Ok(0));
+        assert_eq!(
+            AmlValue::String(String::from("0123456789abcdef01")).as_integer(&context),
+            Ok(0x0123456789abcdef)
+        );
+
+        // Integer -> Buffer produces 8 bytes, and String -> Buffer includes the null terminator
+        assert_eq!(
+            *AmlValue::Integer(0x0201).as_buffer(&context).unwrap().lock(),
+            alloc::vec![1, 2, 0, 0, 0, 0, 0, 0]
+        );
+        assert_eq!(
+            *AmlValue::String(String::from("ab")).as_buffer(&context).unwrap().lock(),
+            alloc::vec![b'a', b'b', 0]
+        );
+
+        // Integer -> String produces 16 hex digits, and Buffer -> String produces space-separated hex bytes
+        assert_eq!(AmlValue::Integer(0xabc).as_string(&context), Ok(String::from("0000000000000ABC")));
+        assert_eq!(buffer(&[0x01, 0xab, 0x00]).as_string(&context), Ok(String::from("01 AB 00")));
+
+        assert!(crudely_cmp_values(
+            &AmlValue::Integer(0xf).as_type(AmlType::String, &context).unwrap(),
+            &AmlValue::String(String::from("000000000000000F"))
+        ));
+        assert!(crudely_cmp_values(
+            &AmlValue::String(String::from("ff")).as_type(AmlType::Integer, &context).unwrap(),
+            &AmlValue::Integer(0xff)
+        ));
+
+        // With 32-bit integers, integers are converted to 4 bytes or 8 hex digits
+        let mut context = make_test_context();
+        context.interpreter_revision = 1;
+        assert_eq!(*AmlValue::Integer(0x0201).as_buffer(&context).unwrap().lock(), alloc::vec![1, 2, 0, 0]);
+        assert_eq!(AmlValue::Integer(0xabc).as_string(&context), Ok(String::from("00000ABC")));
+        assert_eq!(AmlValue::Boolean(true).as_string(&context), Ok(String::from("FFFFFFFF")));
+        assert_eq!(AmlValue::String(String::from("0123456789ab")).as_integer(&context), Ok(0x01234567));
+    }
+
+    #[test]
+    fn test_display() {
+        use alloc::{string::ToString, vec};
+
+        assert_eq!(AmlValue::Integer(0x1f).to_string(), "0x1f");
+        assert_eq!(AmlValue::String(String::from("PNP0A03")).to_string(), "\"PNP0A03\"");
+        assert_eq!(
+            AmlValue::Buffer(Arc::new(Spinlock::new(vec![1, 2, 3]))).to_string(),
+            "Buffer(3 bytes) [01 02 03]"
+        );
+        assert_eq!(
+            AmlValue::Buffer(Arc::new(Spinlock::new(vec![0xff; 10]))).to_string(),
+            "Buffer(10 bytes) [ff ff ff ff ff ff ff ff ...]"
+        );
+        assert_eq!(
+            AmlValue::Package(vec![
+                AmlValue::Integer(5),
+                AmlValue::Package(vec![AmlValue::String(String::from("a"))]),
+            ])
+            .to_string(),
+            "Package [0x5, Package [\"a\"]]"
+        );
+        assert_eq!(
+            AmlValue::OpRegion { region: RegionSpace::SystemIo, offset: 0x80, length: 1, parent_device: None }
+                .to_string(),
+            "OpRegion(SystemIo, offset 0x80, length 0x1)"
+        );
+    }
+
+    #[test]
+    fn test_as_eisa_id() {
+        assert_eq!(AmlValue::Integer(0x030ad041).as_eisa_id().as_deref(), Some("PNP0A03"));
+        assert_eq!(AmlValue::Integer(0x0a0cd041).as_eisa_id().as_deref(), Some("PNP0C0A"));
+        assert_eq!(AmlValue::Integer(0x68004d24).as_eisa_id().as_deref(), Some("IBM0068"));
+        assert_eq!(AmlValue::Integer(0x01017004).as_eisa_id().as_deref(), Some("ACP0101"));
+        assert_eq!(AmlValue::String(String::from("ACPI0003")).as_eisa_id().as_deref(), Some("ACPI0003"));
+
+        assert_eq!(AmlValue::Integer(0x1_0000_0000).as_eisa_id(), None);
+        assert_eq!(AmlValue::Boolean(true).as_eisa_id(), None);
+        assert_eq!(
+            AmlValue::Buffer(Arc::new(Spinlock::new(alloc::vec![0x41, 0xd0, 0x0a, 0x03]))).as_eisa_id(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_system_io_fields() {
+        use crate::test_utils::{make_recording_test_context, Access};
+
+        let (mut context, hardware) = make_recording_test_context();
+
+        /*
+         * OperationRegion(GIO0, SystemIO, 0x80, 0x04)
+         * Field(GIO0, ByteAcc, NoLock, Preserve) {
+         *     FLD0, 8,
+         *     , 4,
+         *     FLD1, 4,
+         *     FLD2, 16
+         * }
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x5b, 0x80, b'G', b'I', b'O', b'0', 0x01, 0x0a, 0x80, 0x0a, 0x04, 0x5b, 0x81, 0x17, b'G', b'I',
+                b'O', b'0', 0x01, b'F', b'L', b'D', b'0', 0x08, 0x00, 0x04, b'F', b'L', b'D', b'1', 0x04, b'F',
+                b'L', b'D', b'2', 0x10,
+            ]),
+            Ok(())
+        );
+
+        {
+            let mut hardware = hardware.lock();
+            hardware.io_ports.insert(0x80, 0x12);
+            hardware.io_ports.insert(0x81, 0xa5);
+            hardware.io_ports.insert(0x82, 0x34);
+            hardware.io_ports.insert(0x83, 0x56);
+        }
+
+        let field = |name: &str| context.namespace.get_by_path(&AmlName::from_str(name).unwrap()).unwrap().clone();
+        let (fld0, mut fld1, fld2) = (field("\\FLD0"), field("\\FLD1"), field("\\FLD2"));
+
+        assert_eq!(fld0.read_field(&context).unwrap().as_integer(&context), Ok(0x12));
+        assert_eq!(fld1.read_field(&context).unwrap().as_integer(&context), Ok(0xa));
+        assert_eq!(fld2.read_field(&context).unwrap().as_integer(&context), Ok(0x5634));
+        assert_eq!(
+            hardware.lock().accesses,
+            [
+                Access::ReadIo { port: 0x80, width: 8 },
+                Access::ReadIo { port: 0x81, width: 8 },
+                Access::ReadIo { port: 0x82, width: 8 },
+                Access::ReadIo { port: 0x83, width: 8 }
+            ]
+        );
+
+        hardware.lock().accesses.clear();
+        assert_eq!(fld1.write_field(AmlValue::Integer(0x3), &mut context), Ok(()));
+        assert_eq!(
+            hardware.lock().accesses,
+            [Access::ReadIo { port: 0x81, width: 8 }, Access::WriteIo { port: 0x81, width: 8, value: 0x35 }]
+        );
+        assert_eq!(hardware.lock().io_ports.get(&0x81), Some(&0x35));
+    }
+
+    #[test]
+    fn test_pci_config_fields() {
+        use crate::test_utils::{make_recording_test_context, Access};
+
+        let (mut context, hardware) = make_recording_test_context();
+
+        /*
+         * Device(PCI0) {
+         *     Name(_ADR, 0x001f0003)
+         *     Name(_BBN, 2)
+         *     OperationRegion(PCFG, PCI_Config, 0x40, 0x10)
+         *     Field(PCFG, DWordAcc, NoLock, Preserve) {
+         *         , 16,
+         *         FLD0, 8
+         *     }
+         *     Field(PCFG, ByteAcc, NoLock, Preserve) {
+         *         Offset(0x4),
+         *         FLD1, 8
+         *     }
+         * }
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x5b, 0x82, 0x3f, b'P', b'C', b'I', b'0', 0x08, b'_', b'A', b'D', b'R', 0x0c, 0x03, 0x00, 0x1f,
+                0x00, 0x08, b'_', b'B', b'B', b'N', 0x0a, 0x02, 0x5b, 0x80, b'P', b'C', b'F', b'G', 0x02, 0x0a,
+                0x40, 0x0a, 0x10, 0x5b, 0x81, 0x0d, b'P', b'C', b'F', b'G', 0x03, 0x00, 0x10, b'F', b'L', b'D',
+                b'0', 0x08, 0x5b, 0x81, 0x0d, b'P', b'C', b'F', b'G', 0x01, 0x00, 0x20, b'F', b'L', b'D', b'1',
+                0x08,
+            ]),
+            Ok(())
+        );
+
+        {
+            let mut hardware = hardware.lock();
+            for (offset, byte) in [(0x40, 0x44), (0x41, 0x33), (0x42, 0x22), (0x43, 0x11), (0x44, 0x99)].iter() {
+                hardware.pci_config.insert((0, 2, 0x1f, 3, *offset), *byte);
+            }
+        }
+
+        let field = |name: &str| context.namespace.get_by_path(&AmlName::from_str(name).unwrap()).unwrap().clone();
+        let (mut fld0, fld1) = (field("\\PCI0.FLD0"), field("\\PCI0.FLD1"));
+
+        assert_eq!(fld0.read_field(&context).unwrap().as_integer(&context), Ok(0x22));
+        assert_eq!(fld1.read_field(&context).unwrap().as_integer(&context), Ok(0x99));
+        assert_eq!(fld0.write_field(AmlValue::Integer(0xab), &mut context), Ok(()));
+        assert_eq!(
+            hardware.lock().accesses,
+            [
+                Access::ReadPci { address: (0, 2, 0x1f, 3, 0x40), width: 32 },
+                Access::ReadPci { address: (0, 2, 0x1f, 3, 0x44), width: 8 },
+                Access::ReadPci { address: (0, 2, 0x1f, 3, 0x40), width: 32 },
+                Access::WritePci { address: (0, 2, 0x1f, 3, 0x40), width: 32, value: 0x11ab3344 }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_system_memory_fields() {
+        use crate::test_utils::{make_recording_test_context, Access};
+
+        let (mut context, hardware) = make_recording_test_context();
+
+        /*
+         * OperationRegion(MEM0, SystemMemory, 0xfed00000, 0x100)
+         * Field(MEM0, AnyAcc, NoLock, WriteAsZeros) {
+         *     Offset(0x4),
+         *     FLD0, 16,
+         *     , 16,
+         *     FLD1, 32,
+         *     , 32,
+         *     FLD2, 64
+         * }
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x5b, 0x80, b'M', b'E', b'M', b'0', 0x00, 0x0c, 0x00, 0x00, 0xd0, 0xfe, 0x0b, 0x00, 0x01, 0x5b,
+                0x81, 0x1c, b'M', b'E', b'M', b'0', 0x40, 0x00, 0x20, b'F', b'L', b'D', b'0', 0x10, 0x00, 0x10,
+                b'F', b'L', b'D', b'1', 0x20, 0x00, 0x20, b'F', b'L', b'D', b'2', 0x40, 0x04,
+            ]),
+            Ok(())
+        );
+
+        {
+            let mut hardware = hardware.lock();
+            for offset in 0x00..0x20 {
+                hardware.memory.insert(0xfed00000 + offset, offset as u8);
+            }
+        }
+
+        let field = |name: &str| context.namespace.get_by_path(&AmlName::from_str(name).unwrap()).unwrap().clone();
+        let (mut fld0, fld1, fld2) = (field("\\FLD0"), field("\\FLD1"), field("\\FLD2"));
+
+        assert_eq!(fld0.read_field(&context).unwrap().as_integer(&context), Ok(0x0504));
+        assert_eq!(fld1.read_field(&context).unwrap().as_integer(&context), Ok(0x0b0a0908));
+        assert_eq!(fld2.read_field(&context).unwrap().as_integer(&context), Ok(0x17161514_13121110));
+        assert_eq!(fld0.write_field(AmlValue::Integer(0xbeef), &mut context), Ok(()));
+        assert_eq!(
+            hardware.lock().accesses,
+            [
+                Access::ReadMemory { address: 0xfed00004, width: 16 },
+                Access::ReadMemory { address: 0xfed00008, width: 32 },
+                Access::ReadMemory { address: 0xfed00010, width: 64 },
+                Access::WriteMemory { address: 0xfed00004, width: 16, value: 0xbeef }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_embedded_control_fields() {
+        use crate::test_utils::{make_recording_test_context, Access};
+
+        let (mut context, hardware) = make_recording_test_context();
+
+        /*
+         * OperationRegion(ECF0, EmbeddedControl, 0x00, 0xff)
+         * Field(ECF0, ByteAcc, Lock, Preserve) {
+         *     Offset(0x10),
+         *     BTST, 8,
+         *     , 8,
+         *     BTRT, 16
+         * }
+         */
+        let table = [
+            0x5b, 0x80, b'E', b'C', b'F', b'0', 0x03, 0x00, 0x0a, 0xff, 0x5b, 0x81, 0x15, b'E', b'C', b'F', b'0',
+            0x11, 0x00, 0x40, 0x08, b'B', b'T', b'S', b'T', 0x08, 0x00, 0x08, b'B', b'T', b'R', b'T', 0x10,
+        ];
+        assert_eq!(context.parse_table(&table), Ok(()));
+
+        {
+            let mut hardware = hardware.lock();
+            hardware.ec.insert(0x10, 0x1f);
+            hardware.ec.insert(0x12, 0xcd);
+            hardware.ec.insert(0x13, 0xab);
+        }
+
+        let field = |name: &str| context.namespace.get_by_path(&AmlName::from_str(name).unwrap()).unwrap().clone();
+        let (btst, mut btrt) = (field("\\BTST"), field("\\BTRT"));
+
+        assert_eq!(btst.read_field(&context).unwrap().as_integer(&context), Ok(0x1f));
+        assert_eq!(btrt.read_field(&context).unwrap().as_integer(&context), Ok(0xabcd));
+        hardware.lock().accesses.clear();
+
+        assert_eq!(btrt.write_field(AmlValue::Integer(0x1234), &mut context), Ok(()));
+        assert_eq!(
+            hardware.lock().accesses,
+            [
+                Access::AcquireMutex(AmlName::from_str("\\_GL").unwrap()),
+                Access::WriteEc { address: 0x12, value: 0x34 },
+                Access::WriteEc { address: 0x13, value: 0x12 },
+                Access::ReleaseMutex(AmlName::from_str("\\_GL").unwrap()),
+            ]
+        );
+
+        // Handlers that don't implement the EC hooks can't access the fields, but this isn't fatal
+        let mut context = crate::test_utils::make_test_context();
+        assert_eq!(context.parse_table(&table), Ok(()));
+        assert_eq!(
+            context
+                .namespace
+                .get_by_path(&AmlName::from_str("\\BTST").unwrap())
+                .unwrap()
+                .read_field(&context)
+                .map(|_| ()),
+            Err(AmlError::UnsupportedRegionSpace(RegionSpace::EmbeddedControl))
+        );
+    }
+
+    #[test]
+    fn test_system_cmos_fields() {
+        use crate::test_utils::{make_recording_test_context, Access};
+
+        let (mut context, hardware) = make_recording_test_context();
+
+        /*
+         * OperationRegion(RTC0, SystemCMOS, 0x00, 0x80)
+         * Field(RTC0, ByteAcc, NoLock, Preserve) {
+         *     Offset(0x0e),
+         *     ALRM, 8
+         * }
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x5b, 0x80, b'R', b'T', b'C', b'0', 0x05, 0x00, 0x0a, 0x80, 0x5b, 0x81, 0x0e, b'R', b'T', b'C',
+                b'0', 0x01, 0x00, 0x40, 0x07, b'A', b'L', b'R', b'M', 0x08,
+            ]),
+            Ok(())
+        );
+        hardware.lock().cmos.insert(0x0e, 0x5a);
+
+        let mut alrm = context.namespace.get_by_path(&AmlName::from_str("\\ALRM").unwrap()).unwrap().clone();
+        assert_eq!(alrm.read_field(&context).unwrap().as_integer(&context), Ok(0x5a));
+        assert_eq!(alrm.write_field(AmlValue::Integer(0xa5), &mut context), Ok(()));
+        assert_eq!(hardware.lock().cmos.get(&0x0e), Some(&0xa5));
+        assert_eq!(
+            hardware.lock().accesses,
+            [Access::ReadCmos { index: 0x0e }, Access::WriteCmos { index: 0x0e, value: 0xa5 }]
+        );
+    }
+
+    #[test]
+    fn test_oem_defined_fields() {
+        use crate::test_utils::{make_recording_test_context, Access};
+
+        let (mut context, hardware) = make_recording_test_context();
+
+        /*
+         * OperationRegion(VND0, 0x80, 0x100, 0x10)
+         * Field(VND0, DWordAcc, NoLock, Preserve) {
+         *     Offset(0x04),
+         *     VREG, 32
+         * }
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x5b, 0x80, b'V', b'N', b'D', b'0', 0x80, 0x0b, 0x00, 0x01, 0x0a, 0x10, 0x5b, 0x81, 0x0d, b'V',
+                b'N', b'D', b'0', 0x03, 0x00, 0x20, b'V', b'R', b'E', b'G', 0x20,
+            ]),
+            Ok(())
+        );
+        hardware.lock().oem.insert((0x80, 0x104), 0xcafebabe);
+
+        let mut vreg = context.namespace.get_by_path(&AmlName::from_str("\\VREG").unwrap()).unwrap().clone();
+        assert_eq!(vreg.read_field(&context).unwrap().as_integer(&context), Ok(0xcafebabe));
+        assert_eq!(vreg.write_field(AmlValue::Integer(0xdeadbeef), &mut context), Ok(()));
+        assert_eq!(hardware.lock().oem.get(&(0x80, 0x104)), Some(&0xdeadbeef));
+        assert_eq!(
+            hardware.lock().accesses,
+            [
+                Access::ReadOem { space: 0x80, offset: 0x104, width: 32 },
+                Access::WriteOem { space: 0x80, offset: 0x104, width: 32, value: 0xdeadbeef },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_generic_serial_bus_fields() {
+        use crate::{
+            name_object::Target,
+            test_utils::{make_recording_test_context, Access},
+        };
+
+        let (mut context, hardware) = make_recording_test_context();
+
+        /*
+         * OperationRegion(GSB0, GenericSerialBus, 0x00, 0x100)
+         * Field(GSB0, BufferAcc, NoLock, Preserve) {
+         *     Connection(Buffer() { 0x8e, 0x00 }),
+         *     AccessAs(BufferAcc, AttribWord),
+         *     Offset(0x10),
+         *     CMD0, 8
+         * }
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x5b, 0x80, b'G', b'S', b'B', b'0', 0x09, 0x00, 0x0b, 0x00, 0x01, 0x5b, 0x81, 0x18, b'G', b'S',
+                b'B', b'0', 0x05, 0x02, 0x11, 0x05, 0x0a, 0x02, 0x8e, 0x00, 0x01, 0x05, 0x08, 0x00, 0x40, 0x08,
+                b'C', b'M', b'D', b'0', 0x08,
+            ]),
+            Ok(())
+        );
+        hardware.lock().serial_bus_response = vec![0x00, 0x02, 0xcd, 0xab];
+
+        let cmd0 = AmlName::from_str("\\CMD0").unwrap();
+        let read = context.read_field(&cmd0).unwrap();
+        assert_eq!(*read.as_buffer(&context).unwrap().lock(), [0x00, 0x02, 0xcd, 0xab]);
+
+        // Stores to the field evaluate to the response of the device
+        let response =
+            context.store(Target::Name(cmd0.clone()), AmlValue::buffer(vec![0x00, 0x02, 0x34, 0x12])).unwrap();
+        assert_eq!(*response.as_buffer(&context).unwrap().lock(), [0x00, 0x02, 0xcd, 0xab]);
+
+        let read_attribute = GsbAttribute { protocol: FieldAccessAttrib::Word, write: false };
+        let write_attribute = GsbAttribute { protocol: FieldAccessAttrib::Word, write: true };
+        assert_eq!(
+            hardware.lock().accesses,
+            [
+                Access::Gsb {
+                    connection: vec![0x8e, 0x00],
+                    attribute: read_attribute,
+                    command: 0x10,
+                    data: vec![0x00, 0x00, 0x00, 0x00]
+                },
+                Access::Gsb {
+                    connection: vec![0x8e, 0x00],
+                    attribute: write_attribute,
+                    command: 0x10,
+                    data: vec![0x00, 0x02, 0x34, 0x12]
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_smbus_fields() {
+        use crate::{
+            name_object::Target,
+            test_utils::{make_recording_test_context, Access},
+        };
+
+        let (mut context, hardware) = make_recording_test_context();
+
+        /*
+         * OperationRegion(SMB0, SMBus, 0x4200, 0x100)
+         * Field(SMB0, BufferAcc, NoLock, Preserve) {
+         *     AccessAs(BufferAcc, AttribWord),
+         *     Offset(0x08),
+         *     BATV, 8
+         * }
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x5b, 0x80, b'S', b'M', b'B', b'0', 0x04, 0x0b, 0x00, 0x42, 0x0b, 0x00, 0x01, 0x5b, 0x81, 0x11,
+                b'S', b'M', b'B', b'0', 0x05, 0x01, 0x05, 0x08, 0x00, 0x40, 0x04, b'B', b'A', b'T', b'V', 0x08,
+            ]),
+            Ok(())
+        );
+        hardware.lock().serial_bus_response = vec![0x00, 0x02, 0x34, 0x12];
+
+        let batv = AmlName::from_str("\\BATV").unwrap();
+        let response = context.read_field(&batv).unwrap().as_buffer(&context).unwrap();
+        assert_eq!(response.lock().len(), 34);
+        assert_eq!(response.lock()[0..4], [0x00, 0x02, 0x34, 0x12]);
+
+        assert!(context.store(Target::Name(batv), AmlValue::buffer(vec![0x00, 0x02, 0xcd, 0xab])).is_ok());
+        let mut write_data = vec![0x00; 34];
+        write_data[0..4].copy_from_slice(&[0x00, 0x02, 0xcd, 0xab]);
+        assert_eq!(
+            hardware.lock().accesses,
+            [
+                Access::Smbus {
+                    protocol: SmbusProtocol::ReadWord,
+                    address: 0x42,
+                    command: 0x08,
+                    data: vec![0; 34]
+                },
+                Access::Smbus {
+                    protocol: SmbusProtocol::WriteWord,
+                    address: 0x42,
+                    command: 0x08,
+                    data: write_data
+                },
+            ]
+        );
+
+        /*
+         * Fields without an access attribute don't have a protocol to use.
+         *
+         * Field(SMB0, BufferAcc, NoLock, Preserve) {
+         *     Offset(0x10),
+         *     NOAT, 8
+         * }
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x5b, 0x81, 0x0e, b'S', b'M', b'B', b'0', 0x05, 0x00, 0x40, 0x08, b'N', b'O', b'A', b'T', 0x08,
+            ]),
+            Ok(())
+        );
+        assert_eq!(
+            context.read_field(&AmlName::from_str("\\NOAT").unwrap()).map(|_| ()),
+            Err(AmlError::InvalidSmbusProtocol)
+        );
+    }
+
+    #[test]
+    fn test_ipmi_fields() {
+        use crate::{
+            name_object::Target,
+            test_utils::{make_recording_test_context, Access},
+        };
+
+        let (mut context, hardware) = make_recording_test_context();
+
+        /*
+         * OperationRegion(IPM0, IPMI, 0x3000, 0x100)
+         * Field(IPM0, BufferAcc, NoLock, Preserve) {
+         *     Offset(0x02),
+         *     PWRC, 8
+         * }
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x5b, 0x80, b'I', b'P', b'M', b'0', 0x07, 0x0b, 0x00, 0x30, 0x0b, 0x00, 0x01, 0x5b, 0x81, 0x0d,
+                b'I', b'P', b'M', b'0', 0x05, 0x00, 0x10, b'P', b'W', b'R', b'C', 0x08,
+            ]),
+            Ok(())
+        );
+        hardware.lock().serial_bus_response = vec![0x00, 0x02, 0x11, 0x22];
+
+        let pwrc = AmlName::from_str("\\PWRC").unwrap();
+        let response = context.read_field(&pwrc).unwrap().as_buffer(&context).unwrap();
+        assert_eq!(response.lock().len(), 66);
+        assert_eq!(response.lock()[0..4], [0x00, 0x02, 0x11, 0x22]);
+        assert!(response.lock()[4..].iter().all(|&byte| byte == 0));
+
+        // Only the bytes of the request's data covered by its length byte are sent
+        assert!(context.store(Target::Name(pwrc), AmlValue::buffer(vec![0x00, 0x02, 0xaa, 0xbb, 0xcc])).is_ok());
+        assert_eq!(
+            hardware.lock().accesses,
+            [
+                Access::Ipmi { network_function: 0x30, command: 0x02, request: vec![] },
+                Access::Ipmi { network_function: 0x30, command: 0x02, request: vec![0xaa, 0xbb] },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_general_purpose_io_fields() {
+        use crate::test_utils::{make_recording_test_context, Access};
+
+        let (mut context, hardware) = make_recording_test_context();
+
+        /*
+         * Name(RES0, Buffer() { 0x79, 0x00 })
+         * OperationRegion(GPO0, GeneralPurposeIo, 0x00, 0x01)
+         * Field(GPO0, ByteAcc, NoLock, Preserve) {
+         *     Connection(RES0),
+         *     PWR0, 1,
+         *     RST0, 2
+         * }
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x08, b'R', b'E', b'S', b'0', 0x11, 0x05, 0x0a, 0x02, 0x79, 0x00, 0x5b, 0x80, b'G', b'P', b'O',
+                b'0', 0x08, 0x00, 0x0a, 0x01, 0x5b, 0x81, 0x15, b'G', b'P', b'O', b'0', 0x01, 0x02, b'R', b'E',
+                b'S', b'0', b'P', b'W', b'R', b'0', 0x01, b'R', b'S', b'T', b'0', 0x02,
+            ]),
+            Ok(())
+        );
+        hardware.lock().gpio_pins = 0b101;
+
+        let field = |name: &str| context.namespace.get_by_path(&AmlName::from_str(name).unwrap()).unwrap().clone();
+        let (pwr0, mut rst0) = (field("\\PWR0"), field("\\RST0"));
+
+        assert_eq!(pwr0.read_field(&context).unwrap().as_integer(&context), Ok(0b1));
+        assert_eq!(rst0.read_field(&context).unwrap().as_integer(&context), Ok(0b10));
+        assert_eq!(rst0.write_field(AmlValue::Integer(0b01), &mut context), Ok(()));
+        assert_eq!(hardware.lock().gpio_pins, 0b011);
+        assert_eq!(
+            hardware.lock().accesses,
+            [
+                Access::ReadGpio { connection: vec![0x79, 0x00], pin_offset: 0, bit_count: 1 },
+                Access::ReadGpio { connection: vec![0x79, 0x00], pin_offset: 1, bit_count: 2 },
+                Access::WriteGpio { connection: vec![0x79, 0x00], pin_offset: 1, bit_count: 2, value: 0b01 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fields_spanning_access_units() {
+        use crate::test_utils::{make_recording_test_context, Access};
+
+        let (mut context, hardware) = make_recording_test_context();
+
+        /*
+         * OperationRegion(GIO0, SystemIO, 0x80, 0x10)
+         * Field(GIO0, ByteAcc, Lock, Preserve) {
+         *     , 4,
+         *     FLD0, 12
+         * }
+         * OperationRegion(MEM0, SystemMemory, 0x1000, 0x20)
+         * Field(MEM0, DWordAcc, NoLock, WriteAsOnes) {
+         *     BUF0, 96
+         * }
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x5b, 0x80, b'G', b'I', b'O', b'0', 0x01, 0x0a, 0x80, 0x0a, 0x10, 0x5b, 0x81, 0x0d, b'G', b'I',
+                b'O', b'0', 0x11, 0x00, 0x04, b'F', b'L', b'D', b'0', 0x0c, 0x5b, 0x80, b'M', b'E', b'M', b'0',
+                0x00, 0x0b, 0x00, 0x10, 0x0a, 0x20, 0x5b, 0x81, 0x0c, b'M', b'E', b'M', b'0', 0x23, b'B', b'U',
+                b'F', b'0', 0x40, 0x06,
+            ]),
+            Ok(())
+        );
+
+        {
+            let mut hardware = hardware.lock();
+            hardware.io_ports.insert(0x80, 0x5a);
+            hardware.io_ports.insert(0x81, 0xc3);
+            for offset in 0x00..0x0c {
+                hardware.memory.insert(0x1000 + offset, offset as u8);
+            }
+        }
+
+        let global_lock = AmlName::from_str("\\_GL").unwrap();
+        assert!(matches!(context.read_field(&AmlName::from_str("\\FLD0").unwrap()), Ok(AmlValue::Integer(0xc35))));
+        assert_eq!(context.write_field(&AmlName::from_str("FLD0").unwrap(), AmlValue::Integer(0x123)), Ok(()));
+        assert_eq!(
+            hardware.lock().accesses,
+            [
+                Access::AcquireMutex(global_lock.clone()),
+                Access::ReadIo { port: 0x80, width: 8 },
+                Access::ReadIo { port: 0x81, width: 8 },
+                Access::ReleaseMutex(global_lock.clone()),
+                Access::AcquireMutex(global_lock.clone()),
+                Access::ReadIo { port: 0x80, width: 8 },
+                Access::WriteIo { port: 0x80, width: 8, value: 0x3a },
+                Access::WriteIo { port: 0x81, width: 8, value: 0x12 },
+                Access::ReleaseMutex(global_lock),
+            ]
+        );
+        hardware.lock().accesses.clear();
+
+        match context.read_field(&AmlName::from_str("\\BUF0").unwrap()) {
+            Ok(AmlValue::Buffer(bytes)) => {
+                assert_eq!(*bytes.lock(), [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b])
+            }
+            other => panic!("Unexpected value: {:?}", other),
+        }
+        assert_eq!(
+            context.write_field(
+                &AmlName::from_str("\\BUF0").unwrap(),
+                AmlValue::Buffer(Arc::new(Spinlock::new(alloc::vec![
+                    0xff, 0xee, 0xdd, 0xcc, 0xbb, 0xaa, 0x99, 0x88
+                ])))
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            hardware.lock().accesses,
+            [
+                Access::ReadMemory { address: 0x1000, width: 32 },
+                Access::ReadMemory { address: 0x1004, width: 32 },
+                Access::ReadMemory { address: 0x1008, width: 32 },
+                Access::WriteMemory { address: 0x1000, width: 32, value: 0xccddeeff },
+                Access::WriteMemory { address: 0x1004, width: 32, value: 0x8899aabb },
+                Access::WriteMemory { address: 0x1008, width: 32, value: 0x00000000 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_index_fields() {
+        use crate::test_utils::{make_recording_test_context, Access};
+
+        let (mut context, hardware) = make_recording_test_context();
+
+        /*
+         * OperationRegion(CMS0, SystemIO, 0x70, 0x02)
+         * Field(CMS0, ByteAcc, NoLock, Preserve) {
+         *     IDX0, 8,
+         *     DAT0, 8
+         * }
+         * IndexField(IDX0, DAT0, ByteAcc, NoLock, Preserve) {
+         *     Offset(0x10),
+         *     RTC0, 8,
+         *     , 4,
+         *     RTC1, 8
+         * }
+         */
+        assert_eq!(
+            context.parse_table(&[
+                0x5b, 0x80, b'C', b'M', b'S', b'0', 0x01, 0x0a, 0x70, 0x0a, 0x02, 0x5b, 0x81, 0x10, b'C', b'M',
+                b'S', b'0', 0x01, b'I', b'D', b'X', b'0', 0x08, b'D', b'A', b'T', b'0', 0x08, 0x5b, 0x86, 0x19,
+                b'I', b'D', b'X', b'0', b'D', b'A', b'T', b'0', 0x01, 0x00, 0x40, 0x08, b'R', b'T', b'C', b'0',
+                0x08, 0x00, 0x04, b'R', b'T', b'C', b'1', 0x08,
+            ]),
+            Ok(())
+        );
+
+        // The mock doesn't model the registers selected by the index, so every data read returns the same value
+        hardware.lock().io_ports.insert(0x71, 0xa5);
+
+        assert!(matches!(context.read_field(&AmlName::from_str("\\RTC0").unwrap()), Ok(AmlValue::Integer(0xa5))));
+        assert!(matches!(context.read_field(&AmlName::from_str("\\RTC1").unwrap()), Ok(AmlValue::Integer(0x5a))));
+        assert_eq!(
+            hardware.lock().accesses,
+            [
+                Access::WriteIo { port: 0x70, width: 8, value: 0x10 },
+                Access::ReadIo { port: 0x71, width: 8 },
+                Access::WriteIo { port: 0x70, width: 8, value: 0x11 },
+                Access::ReadIo { port: 0x71, width: 8 },
+                Access::WriteIo { port: 0x70, width: 8, value: 0x12 },
+                Access::ReadIo { port: 0x71, width: 8 },
+            ]
+        );
+        hardware.lock().accesses.clear();
+
+        assert_eq!(context.write_field(&AmlName::from_str("\\RTC1").unwrap(), AmlValue::Integer(0x3c)), Ok(()));
+        assert_eq!(
+            hardware.lock().accesses,
+            [
+                Access::WriteIo { port: 0x70, width: 8, value: 0x11 },
+                Access::ReadIo { port: 0x71, width: 8 },
+                Access::WriteIo { port: 0x70, width: 8, value: 0x11 },
+                Access::WriteIo { port: 0x71, width: 8, value: 0xc5 },
+                Access::WriteIo { port: 0x70, width: 8, value: 0x12 },
+                Access::ReadIo { port: 0x71, width: 8 },
+                Access::WriteIo { port: 0x70, width: 8, value: 0x12 },
+                Access::WriteIo { port: 0x71, width: 8, value: 0xc3 },
+            ]
+        );
+    }
 }
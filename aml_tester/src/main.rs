@@ -57,7 +57,8 @@ fn main() -> std::io::Result<()> {
         file.read_to_end(&mut contents).unwrap();
 
         const AML_TABLE_HEADER_LENGTH: usize = 36;
-        let mut context = AmlContext::new(Box::new(Handler), DebugVerbosity::None);
+        let mut context =
+            AmlContext::new(Box::new(Handler { start: std::time::Instant::now() }), DebugVerbosity::None);
 
         match context.parse_table(&contents[AML_TABLE_HEADER_LENGTH..]) {
             Ok(()) => {
@@ -145,7 +146,9 @@ impl log::Log for Logger {
     }
 }
 
-struct Handler;
+struct Handler {
+    start: std::time::Instant,
+}
 
 impl aml::Handler for Handler {
     fn read_u8(&self, _address: usize) -> u8 {
@@ -212,4 +215,14 @@ impl aml::Handler for Handler {
     fn write_pci_u32(&self, _segment: u16, _bus: u8, _device: u8, _function: u8, _offset: u16, _value: u32) {
         unimplemented!()
     }
+
+    fn stall(&self, microseconds: u64) {
+        std::thread::sleep(std::time::Duration::from_micros(microseconds));
+    }
+    fn sleep(&self, milliseconds: u64) {
+        std::thread::sleep(std::time::Duration::from_millis(milliseconds));
+    }
+    fn monotonic_timer(&self) -> u64 {
+        (self.start.elapsed().as_nanos() / 100) as u64
+    }
 }
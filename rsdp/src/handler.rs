@@ -1,4 +1,8 @@
-use core::{ops::Deref, ptr::NonNull};
+use core::{
+    mem::{self, ManuallyDrop},
+    ops::Deref,
+    ptr::{self, NonNull},
+};
 
 /// Describes a physical mapping created by `AcpiHandler::map_physical_region` and unmapped by
 /// `AcpiHandler::unmap_physical_region`. The region mapped must be at least `size_of::<T>()`
@@ -47,6 +51,17 @@ where
         self.virtual_start
     }
 
+    /// Get a pointer to the start of the mapped `T`, e.g. to compute the address of data that follows it within
+    /// the region.
+    pub fn as_ptr(&self) -> *const T {
+        self.virtual_start.as_ptr()
+    }
+
+    /// Get a mutable pointer to the start of the mapped `T`.
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        self.virtual_start.as_ptr()
+    }
+
     pub fn region_length(&self) -> usize {
         self.region_length
     }
@@ -58,6 +73,29 @@ where
     pub fn handler(&self) -> &H {
         &self.handler
     }
+
+    /// Reinterpret the mapping as containing an `N` instead of a `T`. This fails, returning the original mapping,
+    /// if the mapped address is not suitably aligned for `N` or the region is too small to contain one. Table
+    /// data can point anywhere, so this should be used instead of casting the pointer when the address comes from
+    /// firmware.
+    pub fn try_coerce_type<N>(self) -> Result<PhysicalMapping<H, N>, PhysicalMapping<H, T>> {
+        if !self.virtual_start.as_ptr().cast::<N>().is_aligned() || self.region_length < mem::size_of::<N>() {
+            return Err(self);
+        }
+
+        /*
+         * The new mapping takes over responsibility for unmapping the region, so make sure this one isn't
+         * dropped.
+         */
+        let this = ManuallyDrop::new(self);
+        Ok(PhysicalMapping {
+            physical_start: this.physical_start,
+            virtual_start: this.virtual_start.cast(),
+            region_length: this.region_length,
+            mapped_length: this.mapped_length,
+            handler: unsafe { ptr::read(&this.handler) },
+        })
+    }
 }
 
 unsafe impl<H: AcpiHandler + Send, T: Send> Send for PhysicalMapping<H, T> {}
@@ -82,6 +120,15 @@ where
     }
 }
 
+/// Error returned by `AcpiHandler::try_map_physical_region` if a region of physical memory can't be mapped.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MapError {
+    /// The region is not valid to map, e.g. because it lies outside of physical memory.
+    InvalidPhysicalAddress,
+    /// There isn't enough free virtual address space to map the region.
+    OutOfVirtualMemory,
+}
+
 /// An implementation of this trait must be provided to allow `acpi` to access platform-specific
 /// functionality, such as mapping regions of physical memory. You are free to implement these
 /// however you please, as long as they conform to the documentation of each function. The handler is stored in
@@ -99,6 +146,21 @@ pub trait AcpiHandler: Clone {
     /// - `size` must be at least `size_of::<T>()`.
     unsafe fn map_physical_region<T>(&self, physical_address: usize, size: usize) -> PhysicalMapping<Self, T>;
 
+    /// Like `map_physical_region`, but returns an error if the region can't be mapped, instead of panicking. This
+    /// is what the library uses to map regions, and errors are propagated to the caller. The default
+    /// implementation calls `map_physical_region`, so only needs to be overridden by handlers that can fail.
+    ///
+    /// ## Safety
+    ///
+    /// The same requirements as `map_physical_region` apply.
+    unsafe fn try_map_physical_region<T>(
+        &self,
+        physical_address: usize,
+        size: usize,
+    ) -> Result<PhysicalMapping<Self, T>, MapError> {
+        Ok(unsafe { self.map_physical_region(physical_address, size) })
+    }
+
     /// Unmap the given physical mapping. This is called when a `PhysicalMapping` is dropped, you should **not** manually call this.
     ///
     /// Note: A reference to the handler used to construct `region` can be acquired by calling [`PhysicalMapping::handler`].
@@ -118,4 +180,42 @@ mod tests {
             test_send_sync::<PhysicalMapping<H, T>>();
         }
     }
+
+    #[derive(Clone)]
+    struct NoopHandler;
+
+    impl AcpiHandler for NoopHandler {
+        unsafe fn map_physical_region<T>(
+            &self,
+            _physical_address: usize,
+            _size: usize,
+        ) -> PhysicalMapping<Self, T> {
+            unimplemented!()
+        }
+
+        fn unmap_physical_region<T>(_region: &PhysicalMapping<Self, T>) {}
+    }
+
+    #[test]
+    fn test_try_coerce_type() {
+        let mut backing = [0u64; 2];
+        let base = backing.as_mut_ptr() as *mut u8;
+        let map = |offset: usize, length: usize| unsafe {
+            PhysicalMapping::<NoopHandler, u8>::new(
+                0x1000 + offset,
+                NonNull::new(base.add(offset)).unwrap(),
+                length,
+                length,
+                NoopHandler,
+            )
+        };
+
+        let coerced = map(0, 8).try_coerce_type::<u32>().ok().unwrap();
+        assert_eq!(coerced.physical_start(), 0x1000);
+        assert_eq!(coerced.region_length(), 8);
+
+        let misaligned = map(1, 8).try_coerce_type::<u32>().err().unwrap();
+        assert_eq!(misaligned.physical_start(), 0x1001);
+        assert!(map(0, 2).try_coerce_type::<u32>().is_err());
+    }
 }
@@ -17,7 +17,7 @@ extern crate std;
 pub mod handler;
 
 use core::{mem, ops::Range, slice, str};
-use handler::{AcpiHandler, PhysicalMapping};
+use handler::{AcpiHandler, MapError, PhysicalMapping};
 use log::warn;
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
@@ -26,6 +26,8 @@ pub enum RsdpError {
     IncorrectSignature,
     InvalidOemId,
     InvalidChecksum,
+    /// A region of memory needed to search for the RSDP couldn't be mapped.
+    MapError(MapError),
 }
 
 /// The first structure found in ACPI. It just tells us where the RSDT is.
@@ -84,11 +86,12 @@ impl Rsdp {
             let areas = find_search_areas(handler.clone());
 
             'areas: for area in areas.iter() {
-                let mapping = unsafe { handler.map_physical_region::<u8>(area.start, area.end - area.start) };
+                let mapping = unsafe { handler.try_map_physical_region::<u8>(area.start, area.end - area.start) }
+                    .map_err(RsdpError::MapError)?;
 
                 for address in area.clone().step_by(16) {
                     let ptr_in_mapping =
-                        unsafe { mapping.virtual_start().as_ptr().add(address - area.start) };
+                        unsafe { mapping.as_ptr().add(address - area.start) };
                     let signature = unsafe { *(ptr_in_mapping as *const [u8; 8]) };
 
                     if signature == RSDP_SIGNATURE {
@@ -107,10 +110,8 @@ impl Rsdp {
         };
 
         match rsdp_address {
-            Some(address) => {
-                let rsdp_mapping = unsafe { handler.map_physical_region::<Rsdp>(address, mem::size_of::<Rsdp>()) };
-                Ok(rsdp_mapping)
-            }
+            Some(address) => unsafe { handler.try_map_physical_region::<Rsdp>(address, mem::size_of::<Rsdp>()) }
+                .map_err(RsdpError::MapError),
             None => Err(RsdpError::NoValidRsdp),
         }
     }
@@ -180,9 +181,12 @@ where
      * Read the base address of the EBDA from its location in the BDA (BIOS Data Area). Not all BIOSs fill this out
      * unfortunately, so we might not get a sensible result. We shift it left 4, as it's a segment address.
      */
-    let ebda_start_mapping =
-        unsafe { handler.map_physical_region::<u16>(EBDA_START_SEGMENT_PTR, mem::size_of::<u16>()) };
-    let ebda_start = (*ebda_start_mapping as usize) << 4;
+    let ebda_start =
+        match unsafe { handler.try_map_physical_region::<u16>(EBDA_START_SEGMENT_PTR, mem::size_of::<u16>()) } {
+            Ok(ebda_start_mapping) => (*ebda_start_mapping as usize) << 4,
+            // If we can't read the BDA, we fall back to searching the largest possible EBDA (see below)
+            Err(_) => 0,
+        };
 
     [
         /*